@@ -0,0 +1,408 @@
+//! End-to-end timings for the pieces of the interpreter real scripts
+//! spend their time in: lexing, parsing, and evaluating. Also compares a
+//! few alternative representations against what's actually in `Value`
+//! and `Environment` today -- the real `Rc`-based `Environment` against
+//! the arena-backed alternative in `cracked_shell::arena` (opt-in via the
+//! `arena-env` feature), building a list one element at a time with
+//! `Value::list` (O(n) per cons) against `cracked_shell::plist::Plist`
+//! (O(1) per cons), and cloning a plain `String` symbol against cloning
+//! an interned `cracked_shell::intern::intern` handle of the same text,
+//! and assembling a log-style string out of many small pieces the naive
+//! way (`s = s + &next`, O(n^2)) against `cracked_shell::rope::RopeBuilder`
+//! (amortized O(n)), and splitting a `wc -l`-scale byte stream into lines
+//! with `BufRead::lines()` (which allocates a fresh internal read buffer
+//! per call) against reading into a single buffer reused across lines via
+//! `BufRead::read_until`, the way `proc/run-lines` does, and repeatedly
+//! looking up a global through several intervening frames with
+//! `Environment::get` (re-hashing the name in every frame on the way)
+//! against `Environment::get_cached` from the same call site (warms up
+//! on the first lookup, then skips straight to the frame that answered
+//! it via a pointer check instead of a name search).
+//!
+//! There is no criterion or other bench harness in this workspace, so,
+//! like `lexaddr`'s ignored hot-loop test, this is a manual `Instant`-based
+//! comparison meant for a human to read, not a pass/fail gate. Run with:
+//!
+//! ```sh
+//! cargo bench --features arena-env
+//! ```
+//!
+//! Without the feature, only the `Rc` side runs, since `cracked_shell::arena`
+//! doesn't exist in a default build.
+
+use cracked_shell::intern::intern;
+use cracked_shell::lexer::Lexer;
+use cracked_shell::plist::Plist;
+use cracked_shell::rope::RopeBuilder;
+use cracked_shell::{eval, Parser};
+use cracked_shell::{Environment, Value};
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: usize = 200_000;
+const SLOTS: usize = 32;
+const LOG_LINES: usize = 20_000;
+const SYMBOL_CLONES: usize = 1_000_000;
+const STREAM_LINES: usize = 2_000_000;
+
+/// Smaller than `ITERATIONS`: building a `Value::List` one element at a
+/// time is O(n^2) (each `cons` copies everything seen so far), so this
+/// stays small enough that the vector side still finishes quickly.
+const LIST_BUILD_ELEMENTS: usize = 4_000;
+
+fn bench_rc_environment() -> Duration {
+    let env = Environment::new_global();
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let name = format!("var{}", i % SLOTS);
+        env.define(name.clone(), Value::Int(i as i64));
+        env.get(&name);
+    }
+    start.elapsed()
+}
+
+/// Builds a list by repeatedly `cons`-ing a new head onto `Value::list`'s
+/// `Rc<Vec<Value>>` representation, the same way `(cons v lst)` does --
+/// O(n) per cons, since the whole vector is copied each time.
+fn bench_vec_list_building() -> Duration {
+    let start = Instant::now();
+    let mut list = Value::list(Vec::new());
+    for i in 0..LIST_BUILD_ELEMENTS {
+        let Value::List(items) = &list else { unreachable!() };
+        let mut next = vec![Value::Int(i as i64)];
+        next.extend(items.iter().cloned());
+        list = Value::list(next);
+    }
+    start.elapsed()
+}
+
+/// Builds the same list with [`Plist`], whose `cons` only allocates the
+/// new head cell and shares the rest of the structure -- O(1) per cons.
+fn bench_plist_building() -> Duration {
+    let start = Instant::now();
+    let mut list = Plist::nil();
+    for i in 0..LIST_BUILD_ELEMENTS {
+        list = list.cons(Value::Int(i as i64));
+    }
+    start.elapsed()
+}
+
+/// Repeatedly clones a `String` the way `Value::Symbol(String)` does on
+/// every lookup that returns one -- each clone copies the backing bytes
+/// into a fresh heap allocation.
+fn bench_string_symbol_clones() -> Duration {
+    let symbol = String::from("a-typical-symbol-name");
+    let start = Instant::now();
+    let mut total_len = 0;
+    for _ in 0..SYMBOL_CLONES {
+        let clone = symbol.clone();
+        total_len += clone.len();
+    }
+    std::hint::black_box(total_len);
+    start.elapsed()
+}
+
+/// Repeatedly clones an [`intern`]ed `Rc<str>` of the same text -- each
+/// clone is a refcount bump, no allocation.
+fn bench_interned_symbol_clones() -> Duration {
+    let symbol = intern("a-typical-symbol-name");
+    let start = Instant::now();
+    let mut total_len = 0;
+    for _ in 0..SYMBOL_CLONES {
+        let clone = symbol.clone();
+        total_len += clone.len();
+    }
+    std::hint::black_box(total_len);
+    start.elapsed()
+}
+
+/// Lexes a large generated source file: 20,000 forms of the shape
+/// `(+ i 1 2 3 4)`, one form per line.
+fn bench_lex_large_file() -> Duration {
+    let mut source = String::new();
+    for i in 0..20_000 {
+        source.push_str(&format!("(+ {i} 1 2 3 4)\n"));
+    }
+    let start = Instant::now();
+    let tokens = Lexer::tokenize(&source).unwrap();
+    let elapsed = start.elapsed();
+    std::hint::black_box(tokens.len());
+    elapsed
+}
+
+/// Parses a single deeply nested form: `(((...(0)...)))`.
+fn bench_parse_deep_nesting() -> Duration {
+    const DEPTH: usize = 3_000;
+    let mut source = String::new();
+    for _ in 0..DEPTH {
+        source.push('(');
+    }
+    source.push('0');
+    for _ in 0..DEPTH {
+        source.push(')');
+    }
+    let start = Instant::now();
+    let form = Parser::parse_with_max_depth(&source, DEPTH + 1).unwrap();
+    let elapsed = start.elapsed();
+    std::hint::black_box(form);
+    elapsed
+}
+
+/// Evaluates a tight arithmetic loop: a recursive countdown that does a
+/// handful of arithmetic ops per call. There's no tail-call optimization
+/// in `apply`, so the recursion depth is kept well inside the default
+/// stack size.
+fn bench_arithmetic_loop() -> Duration {
+    let env = Environment::new_global();
+    cracked_shell::builtins::install(&env);
+    eval(
+        &Parser::parse("(define (count n acc) (if (= n 0) acc (count (- n 1) (+ acc n))))").unwrap(),
+        &env,
+    )
+    .unwrap();
+    let call = Parser::parse("(count 3000 0)").unwrap();
+    let start = Instant::now();
+    let result = eval(&call, &env).unwrap();
+    let elapsed = start.elapsed();
+    std::hint::black_box(result);
+    elapsed
+}
+
+/// Evaluates naive recursive `fib`, exercising closure creation and deep
+/// call-stack recursion.
+fn bench_fib_recursion() -> Duration {
+    let env = Environment::new_global();
+    cracked_shell::builtins::install(&env);
+    eval(
+        &Parser::parse("(define (fib n) (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))").unwrap(),
+        &env,
+    )
+    .unwrap();
+    let call = Parser::parse("(fib 22)").unwrap();
+    let start = Instant::now();
+    let result = eval(&call, &env).unwrap();
+    let elapsed = start.elapsed();
+    std::hint::black_box(result);
+    elapsed
+}
+
+/// Evaluates `(str/split "," big-csv-line)` repeatedly.
+fn bench_string_splitting() -> Duration {
+    let env = Environment::new_global();
+    cracked_shell::builtins::install(&env);
+    let csv_line = (0..200).map(|i| i.to_string()).collect::<Vec<_>>().join(",");
+    eval(
+        &Parser::parse(&format!("(define line \"{csv_line}\")")).unwrap(),
+        &env,
+    )
+    .unwrap();
+    let call = Parser::parse("(str/split \",\" line)").unwrap();
+    let start = Instant::now();
+    for _ in 0..5_000 {
+        let result = eval(&call, &env).unwrap();
+        std::hint::black_box(result);
+    }
+    start.elapsed()
+}
+
+/// Evaluates many lookups of a symbol bound in the same global frame the
+/// lookup runs in, through the full `eval` path. The nested-frame case is
+/// measured separately by `bench_nested_lookup_uncached`/`_cached` below.
+fn bench_environment_lookup() -> Duration {
+    let env = Environment::new_global();
+    eval(&Parser::parse("(define x 42)").unwrap(), &env).unwrap();
+    let lookup = Parser::parse("x").unwrap();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let result = eval(&lookup, &env).unwrap();
+        std::hint::black_box(result);
+    }
+    start.elapsed()
+}
+
+/// How many nested child frames sit between the lookup and the global
+/// frame that actually holds the binding -- deep enough that re-hashing
+/// the name at every frame on the way down is the dominant cost, the way
+/// a few levels of `let`/lambda nesting inside a hot loop would be.
+const LOOKUP_DEPTH: usize = 50;
+
+fn deeply_nested_environment() -> Environment {
+    let global = Environment::new_global();
+    global.define("deeply-nested-global", Value::Int(42));
+    let mut env = global;
+    for _ in 0..LOOKUP_DEPTH {
+        env = Environment::child(&env);
+    }
+    env
+}
+
+/// Looks a global up through `LOOKUP_DEPTH` frames the plain way: each
+/// call re-hashes the name and misses in every frame until it reaches
+/// the one that has it.
+fn bench_nested_lookup_uncached() -> Duration {
+    let env = deeply_nested_environment();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let result = env.get("deeply-nested-global");
+        std::hint::black_box(result);
+    }
+    start.elapsed()
+}
+
+/// The same lookup, from the same call site every iteration, through
+/// `Environment::get_cached`: the first call walks and warms the cache
+/// like the uncached version, but every later call skips straight to the
+/// global frame with pointer checks instead of a name search per frame.
+fn bench_nested_lookup_cached() -> Duration {
+    let env = deeply_nested_environment();
+    let call_site = &env as *const Environment as usize;
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let result = env.get_cached("deeply-nested-global", call_site);
+        std::hint::black_box(result);
+    }
+    start.elapsed()
+}
+
+/// Assembles a log-style string out of many small lines the naive way:
+/// each append builds an entirely new `String` holding everything seen
+/// so far plus the next line -- O(n) per append, O(n^2) overall.
+fn bench_naive_log_assembly() -> Duration {
+    let start = Instant::now();
+    let mut log = String::new();
+    for i in 0..LOG_LINES {
+        log = log + &format!("line {i}: something happened\n");
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(log.len());
+    elapsed
+}
+
+/// The same log, assembled with [`RopeBuilder`]: each push only copies
+/// the new line in, never what came before -- amortized O(1) per push.
+fn bench_rope_log_assembly() -> Duration {
+    let start = Instant::now();
+    let mut rope = RopeBuilder::new();
+    for i in 0..LOG_LINES {
+        rope.push(&format!("line {i}: something happened\n"));
+    }
+    let log = rope.finish();
+    let elapsed = start.elapsed();
+    std::hint::black_box(log.len());
+    elapsed
+}
+
+/// `wc -l`-scale input, generated once and reused by both line-splitting
+/// benches below so neither pays for generating it.
+fn stream_input() -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for i in 0..STREAM_LINES {
+        bytes.extend_from_slice(format!("line {i}\n").as_bytes());
+    }
+    bytes
+}
+
+/// Splits `input` into owned `String`s the easy way, via `BufRead::lines()`
+/// -- each call grows and then discards its own internal read buffer.
+fn bench_lines_iterator(input: &[u8]) -> Duration {
+    let start = Instant::now();
+    let mut count = 0usize;
+    for line in std::io::Cursor::new(input).lines() {
+        let line = line.unwrap();
+        count += line.len();
+    }
+    std::hint::black_box(count);
+    start.elapsed()
+}
+
+/// Splits the same `input` the way `proc/run-lines` reads a child's
+/// stdout: one `Vec<u8>` buffer, cleared and refilled by `read_until` for
+/// every line instead of being reallocated.
+fn bench_reused_buffer_lines(input: &[u8]) -> Duration {
+    let mut reader = std::io::Cursor::new(input);
+    let mut buf = Vec::new();
+    let start = Instant::now();
+    let mut count = 0usize;
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf).unwrap();
+        if read == 0 {
+            break;
+        }
+        count += buf.len();
+    }
+    std::hint::black_box(count);
+    start.elapsed()
+}
+
+#[cfg(feature = "arena-env")]
+fn bench_arena_environment() -> Duration {
+    use cracked_shell::arena::Arena;
+
+    let (mut arena, root) = Arena::new();
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let name = format!("var{}", i % SLOTS);
+        arena.define(root, name.clone(), Value::Int(i as i64));
+        arena.get(root, &name);
+    }
+    start.elapsed()
+}
+
+fn main() {
+    let lex_elapsed = bench_lex_large_file();
+    println!("Lex large file:    {lex_elapsed:?} (20000 forms)");
+
+    let parse_elapsed = bench_parse_deep_nesting();
+    println!("Parse deep nest:   {parse_elapsed:?} (depth 3000)");
+
+    let arithmetic_elapsed = bench_arithmetic_loop();
+    println!("Arithmetic loop:   {arithmetic_elapsed:?} (3000 iterations)");
+
+    let fib_elapsed = bench_fib_recursion();
+    println!("Fib recursion:     {fib_elapsed:?} (fib 22)");
+
+    let split_elapsed = bench_string_splitting();
+    println!("String splitting:  {split_elapsed:?} (5000 splits of a 200-field line)");
+
+    let lookup_elapsed = bench_environment_lookup();
+    println!("Environment lookup:{lookup_elapsed:?} ({ITERATIONS} lookups)");
+
+    let rc_elapsed = bench_rc_environment();
+    println!("Rc environment:    {rc_elapsed:?} ({ITERATIONS} define+get pairs)");
+
+    let nested_uncached_elapsed = bench_nested_lookup_uncached();
+    let nested_cached_elapsed = bench_nested_lookup_cached();
+    println!("Nested lookup, uncached: {nested_uncached_elapsed:?} ({ITERATIONS} lookups, depth {LOOKUP_DEPTH})");
+    println!("Nested lookup, cached:   {nested_cached_elapsed:?} ({ITERATIONS} lookups, depth {LOOKUP_DEPTH})");
+
+    let vec_list_elapsed = bench_vec_list_building();
+    let plist_elapsed = bench_plist_building();
+    println!("Vec-backed list:   {vec_list_elapsed:?} ({LIST_BUILD_ELEMENTS} cons'd elements)");
+    println!("Persistent plist:  {plist_elapsed:?} ({LIST_BUILD_ELEMENTS} cons'd elements)");
+
+    let string_symbol_elapsed = bench_string_symbol_clones();
+    let interned_symbol_elapsed = bench_interned_symbol_clones();
+    println!("String symbol:     {string_symbol_elapsed:?} ({SYMBOL_CLONES} clones)");
+    println!("Interned symbol:   {interned_symbol_elapsed:?} ({SYMBOL_CLONES} clones)");
+
+    let naive_log_elapsed = bench_naive_log_assembly();
+    let rope_log_elapsed = bench_rope_log_assembly();
+    println!("Naive log assembly:{naive_log_elapsed:?} ({LOG_LINES} lines)");
+    println!("Rope log assembly: {rope_log_elapsed:?} ({LOG_LINES} lines)");
+
+    let stream_bytes = stream_input();
+    let lines_iterator_elapsed = bench_lines_iterator(&stream_bytes);
+    let reused_buffer_elapsed = bench_reused_buffer_lines(&stream_bytes);
+    println!("BufRead::lines():  {lines_iterator_elapsed:?} ({STREAM_LINES} lines)");
+    println!("Reused read buffer:{reused_buffer_elapsed:?} ({STREAM_LINES} lines)");
+
+    #[cfg(feature = "arena-env")]
+    {
+        let arena_elapsed = bench_arena_environment();
+        println!("Arena environment: {arena_elapsed:?} ({ITERATIONS} define+get pairs)");
+    }
+
+    #[cfg(not(feature = "arena-env"))]
+    println!("(run with `cargo bench --features arena-env` to compare against the arena allocator)");
+}
@@ -0,0 +1,25 @@
+#![no_main]
+
+use cracked_shell::eval::{eval, set_fuel};
+use cracked_shell::{builtins, Environment, Parser};
+use libfuzzer_sys::fuzz_target;
+
+// A fuzzed form can still be a form that parses but never finishes
+// evaluating (a self-recursive `lambda`, say), so this caps the run with
+// `set_fuel` instead of relying on the fuzzer's own timeout to notice --
+// without it, every such input looks identical to a hang to the fuzzer
+// and never gets minimized into a useful corpus entry.
+fuzz_target!(|bytes: &[u8]| {
+    let Ok(source) = std::str::from_utf8(bytes) else {
+        return;
+    };
+    let Ok(form) = Parser::parse(source) else {
+        return;
+    };
+
+    let env = Environment::new_global();
+    builtins::install(&env);
+    set_fuel(Some(10_000));
+    let _ = eval(&form, &env);
+    set_fuel(None);
+});
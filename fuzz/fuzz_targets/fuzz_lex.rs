@@ -0,0 +1,12 @@
+#![no_main]
+
+use cracked_shell::lexer::Lexer;
+use libfuzzer_sys::fuzz_target;
+
+// cargo-fuzz's implicit assertion is "never panics" -- tokenize should
+// always return Ok or a clean Err, never abort, for any byte string.
+fuzz_target!(|bytes: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(bytes) {
+        let _ = Lexer::tokenize(source);
+    }
+});
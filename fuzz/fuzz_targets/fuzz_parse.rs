@@ -0,0 +1,13 @@
+#![no_main]
+
+use cracked_shell::Parser;
+use libfuzzer_sys::fuzz_target;
+
+// Same contract as fuzz_lex, one layer up: Parser::parse should never
+// panic on arbitrary text, including text that tokenizes fine but
+// doesn't parse (unbalanced parens, a stray dot, ...).
+fuzz_target!(|bytes: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(bytes) {
+        let _ = Parser::parse(source);
+    }
+});
@@ -0,0 +1,114 @@
+//! An arena-backed alternative to [`crate::env::Environment`]'s
+//! `Rc<RefCell<Frame>>` chain, built only to benchmark against it (see
+//! `benches/eval_benchmark.rs`) -- it is not wired into `eval`, and nothing
+//! in the interpreter constructs one of these. Frames live in one `Vec`
+//! and are addressed by index instead of being individually
+//! reference-counted, which trades `Rc`'s per-frame allocation -- and the
+//! `clear()`-on-reset workaround `Environment` needs for self-capturing
+//! recursive closures -- for a single bulk deallocation when the arena
+//! itself is dropped. Whether that trade is worth the bigger rewrite it
+//! would take to actually replace `Environment` is exactly the question
+//! this feature exists to let someone measure.
+//!
+//! Gated behind the `arena-env` cargo feature so the default build never
+//! carries a second environment representation it doesn't use.
+
+use crate::value::Value;
+use std::collections::HashMap;
+
+struct ArenaFrame {
+    vars: HashMap<String, Value>,
+    parent: Option<usize>,
+}
+
+/// An index into an [`Arena`]'s frame vector. Stands in for `Environment`'s
+/// `Rc<RefCell<Frame>>` handle.
+pub type FrameId = usize;
+
+/// Owns every frame allocated during a run. Frames are never individually
+/// freed -- they live as long as the arena does -- so there is no
+/// equivalent of `Environment::clear()` to call here; dropping the whole
+/// `Arena` is the only cleanup.
+pub struct Arena {
+    frames: Vec<ArenaFrame>,
+}
+
+impl Arena {
+    /// Creates an arena with a single root frame and returns it.
+    pub fn new() -> (Self, FrameId) {
+        let arena = Arena {
+            frames: vec![ArenaFrame {
+                vars: HashMap::new(),
+                parent: None,
+            }],
+        };
+        (arena, 0)
+    }
+
+    /// Allocates a new frame whose lookups fall through to `parent`.
+    pub fn child(&mut self, parent: FrameId) -> FrameId {
+        self.frames.push(ArenaFrame {
+            vars: HashMap::new(),
+            parent: Some(parent),
+        });
+        self.frames.len() - 1
+    }
+
+    pub fn define(&mut self, frame: FrameId, name: impl Into<String>, value: Value) {
+        self.frames[frame].vars.insert(name.into(), value);
+    }
+
+    /// Walks `frame` and its ancestors, nearest first, same as
+    /// `Environment::get`.
+    pub fn get(&self, frame: FrameId, name: &str) -> Option<Value> {
+        let mut current = Some(frame);
+        while let Some(index) = current {
+            let f = &self.frames[index];
+            if let Some(value) = f.vars.get(name) {
+                return Some(value.clone());
+            }
+            current = f.parent;
+        }
+        None
+    }
+}
+
+impl Default for Arena {
+    fn default() -> Self {
+        Arena::new().0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defines_and_looks_up_a_binding_in_the_same_frame() {
+        let (mut arena, root) = Arena::new();
+        arena.define(root, "x", Value::Int(1));
+        assert!(matches!(arena.get(root, "x"), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn child_frames_fall_through_to_the_parent() {
+        let (mut arena, root) = Arena::new();
+        arena.define(root, "x", Value::Int(1));
+        let child = arena.child(root);
+        assert!(matches!(arena.get(child, "x"), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn a_child_definition_does_not_leak_into_the_parent() {
+        let (mut arena, root) = Arena::new();
+        let child = arena.child(root);
+        arena.define(child, "x", Value::Int(1));
+        assert!(arena.get(root, "x").is_none());
+    }
+
+    #[test]
+    fn unbound_name_is_none() {
+        let (arena, root) = Arena::new();
+        assert!(arena.get(root, "nowhere").is_none());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::error::{ParseError, ShellError};
+use crate::parser::Parser;
+use crate::span::Spanned;
+use crate::value::Value;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// The serializable subset of [`Value`]: plain parsed data, with no
+/// `Builtin`, `Native`, `Lambda`, `Future`, or `Memo` variant, since those hold
+/// things (a function pointer, an open channel, a cache) that have no
+/// meaningful JSON form. This is what [`parse_str`]/[`parse_file`]
+/// return, for external tools that want parsed Cracked Shell source as
+/// plain data without linking against the interpreter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sexpr {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Symbol(String),
+    Keyword(String),
+    Char(char),
+    List(Vec<Sexpr>),
+    DottedList(Vec<Sexpr>, Box<Sexpr>),
+}
+
+impl Sexpr {
+    /// Converts a [`Value`] to its data representation, returning `None`
+    /// for a `Builtin`, `Native`, `Lambda`, `Future`, `Memo`, `Plist`,
+    /// `Vector`, or `Error` --
+    /// the variants with no `Sexpr` counterpart. `Plist` and `Vector` could
+    /// in principle round-trip (they're already plain data), but they're
+    /// kept out of the stable serialization format for now since nothing
+    /// outside the interpreter consumes them yet; convert a `Plist` to an
+    /// ordinary list with `plist/->list`, or a `Vector` with `vector->list`,
+    /// before exporting. `Error` isn't data-only either: its `kind` and
+    /// `message` are, but `location` and `irritants` aren't worth a stable
+    /// format until something outside the interpreter needs to consume one.
+    pub fn from_value(value: &Value) -> Option<Sexpr> {
+        Some(match value {
+            Value::Nil => Sexpr::Nil,
+            Value::Bool(b) => Sexpr::Bool(*b),
+            Value::Int(i) => Sexpr::Int(*i),
+            Value::Float(n) => Sexpr::Float(*n),
+            Value::Str(s) => Sexpr::Str(s.clone()),
+            Value::Symbol(s) => Sexpr::Symbol(s.clone()),
+            Value::Keyword(s) => Sexpr::Keyword(s.clone()),
+            Value::Char(c) => Sexpr::Char(*c),
+            Value::List(items) => {
+                Sexpr::List(items.iter().map(Sexpr::from_value).collect::<Option<_>>()?)
+            }
+            Value::DottedList(items, tail) => Sexpr::DottedList(
+                items.iter().map(Sexpr::from_value).collect::<Option<_>>()?,
+                Box::new(Sexpr::from_value(tail)?),
+            ),
+            Value::Builtin(..)
+            | Value::Native(..)
+            | Value::Lambda(_)
+            | Value::Future(_)
+            | Value::Plist(_)
+            | Value::Memo(_)
+            | Value::Vector(_)
+            | Value::Error(_) => return None,
+        })
+    }
+
+    /// The inverse of [`Sexpr::from_value`]: rebuilds the live `Value` a
+    /// piece of parsed data denotes. Always succeeds, since every `Sexpr`
+    /// variant has a `Value` counterpart.
+    pub fn to_value(&self) -> Value {
+        match self {
+            Sexpr::Nil => Value::Nil,
+            Sexpr::Bool(b) => Value::Bool(*b),
+            Sexpr::Int(i) => Value::Int(*i),
+            Sexpr::Float(n) => Value::Float(*n),
+            Sexpr::Str(s) => Value::Str(s.clone()),
+            Sexpr::Symbol(s) => Value::Symbol(s.clone()),
+            Sexpr::Keyword(s) => Value::Keyword(s.clone()),
+            Sexpr::Char(c) => Value::Char(*c),
+            Sexpr::List(items) => Value::list(items.iter().map(Sexpr::to_value).collect()),
+            Sexpr::DottedList(items, tail) => Value::DottedList(
+                Rc::new(items.iter().map(Sexpr::to_value).collect()),
+                Rc::new(tail.to_value()),
+            ),
+        }
+    }
+}
+
+/// A name -> [`Sexpr`] table: the stable serialization boundary between
+/// evaluators. [`crate::env::Environment::export`] produces one and
+/// [`crate::env::Environment::import`] consumes one, so an embedder can
+/// pre-seed a fresh evaluator with host-provided values, or pull plain
+/// results back out, without either side touching the other's live
+/// `Environment` directly. Sorted by name (a `BTreeMap`, not a `HashMap`)
+/// so two exports of the same bindings serialize identically.
+pub type Bindings = BTreeMap<String, Sexpr>;
+
+fn non_data_error() -> ShellError {
+    ParseError::new(
+        "non-data-value",
+        "form contains a non-data value (builtin, lambda, future, plist, memo, vector, or error)",
+    )
+    .into()
+}
+
+/// Parses every top-level form in `source` into the stable, serializable
+/// [`Sexpr`] AST. The main entry point for external tools that want
+/// parsed Cracked Shell code as JSON rather than linking against the
+/// interpreter's `Value` type.
+pub fn parse_str(source: &str) -> Result<Vec<Sexpr>, ShellError> {
+    Parser::parse_all(source)?
+        .iter()
+        .map(|value| Sexpr::from_value(value).ok_or_else(non_data_error))
+        .collect()
+}
+
+/// Like [`parse_str`], but also reports the source span each form was
+/// read from.
+pub fn parse_str_spanned(source: &str) -> Result<Vec<Spanned<Sexpr>>, ShellError> {
+    Parser::parse_all_spanned(source)?
+        .into_iter()
+        .map(|form| {
+            let value = Sexpr::from_value(&form.value).ok_or_else(non_data_error)?;
+            Ok(Spanned {
+                value,
+                span: form.span,
+            })
+        })
+        .collect()
+}
+
+/// Like [`parse_str`], but reads the source from `path` first.
+pub fn parse_file(path: &std::path::Path) -> Result<Vec<Sexpr>, ShellError> {
+    parse_str(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_plain_source_into_sexprs() {
+        let forms = parse_str("(+ 1 2.5) \"hi\"").unwrap();
+        assert_eq!(
+            forms,
+            vec![
+                Sexpr::List(vec![
+                    Sexpr::Symbol("+".into()),
+                    Sexpr::Int(1),
+                    Sexpr::Float(2.5),
+                ]),
+                Sexpr::Str("hi".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let forms = parse_str("(define (f x) (* x x))").unwrap();
+        let json = serde_json::to_string(&forms).unwrap();
+        let back: Vec<Sexpr> = serde_json::from_str(&json).unwrap();
+        assert_eq!(forms, back);
+    }
+
+    #[test]
+    fn spans_are_reported_alongside_each_form() {
+        let forms = parse_str_spanned("(+ 1 2)\n(+ 3 4)").unwrap();
+        assert_eq!(forms.len(), 2);
+        assert_eq!(forms[1].span.start.line, 2);
+    }
+
+    #[test]
+    fn to_value_inverts_from_value() {
+        let forms = Parser::parse_all("(define (f x) (* x x)) \"hi\" 2.5").unwrap();
+        for form in forms {
+            let sexpr = Sexpr::from_value(&form).unwrap();
+            assert_eq!(sexpr.to_value().to_string(), form.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_file_reads_and_parses_a_path() {
+        let path = std::env::temp_dir().join("cracked_shell_ast_test.lisp");
+        std::fs::write(&path, "(list 1 2 3)").unwrap();
+        let forms = parse_file(&path).unwrap();
+        assert_eq!(
+            forms,
+            vec![Sexpr::List(vec![
+                Sexpr::Symbol("list".into()),
+                Sexpr::Int(1),
+                Sexpr::Int(2),
+                Sexpr::Int(3),
+            ])]
+        );
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::error::{ParseError, ShellError};
+
+/// Expands shell-style brace patterns: `{a,b,c}` for a literal list and
+/// `{1..5}` (or `{a..e}`) for an inclusive range, either of which may
+/// appear more than once in the same pattern (`img-{1..2}.{png,jpg}`).
+/// A `{...}` group with neither a comma nor a valid range is left
+/// untouched, same as a real shell does with `{foo}`.
+///
+/// This is a reader-adjacent convenience for building argument lists, not
+/// part of the s-expression syntax itself -- it's exposed as the
+/// `(expand-braces s)` builtin rather than a lexer token.
+pub fn expand(pattern: &str) -> Result<Vec<String>, ShellError> {
+    let Some((prefix, body, suffix)) = find_brace_group(pattern)? else {
+        return Ok(vec![pattern.to_string()]);
+    };
+
+    match expand_group(&body) {
+        Some(alternatives) => {
+            let mut out = Vec::new();
+            for alt in alternatives {
+                out.extend(expand(&format!("{prefix}{alt}{suffix}"))?);
+            }
+            Ok(out)
+        }
+        // Not expandable: keep the group as literal text and carry on
+        // looking for an expandable group later in the pattern, rather
+        // than re-scanning this same `{body}` forever.
+        None => Ok(expand(&suffix)?
+            .into_iter()
+            .map(|s| format!("{prefix}{{{body}}}{s}"))
+            .collect()),
+    }
+}
+
+/// Finds the first `{...}` group in `pattern`, returning the text before
+/// it, its inner contents, and the text after it. Errors on an
+/// unterminated `{`.
+fn find_brace_group(pattern: &str) -> Result<Option<(String, String, String)>, ShellError> {
+    let Some(open) = pattern.find('{') else {
+        return Ok(None);
+    };
+    let close = pattern[open..].find('}').map(|i| open + i).ok_or_else(|| {
+        ShellError::from(ParseError::new(
+            "unterminated-brace",
+            format!("unterminated '{{' in brace pattern: {pattern}"),
+        ))
+    })?;
+
+    Ok(Some((
+        pattern[..open].to_string(),
+        pattern[open + 1..close].to_string(),
+        pattern[close + 1..].to_string(),
+    )))
+}
+
+/// Expands the contents of a single `{...}` group (without its braces)
+/// into its alternatives, or `None` when it's neither a comma list nor a
+/// range (the caller, [`expand`], then keeps the group as literal text).
+fn expand_group(body: &str) -> Option<Vec<String>> {
+    if body.contains(',') {
+        return Some(body.split(',').map(str::to_string).collect());
+    }
+    expand_range(body)
+}
+
+fn expand_range(body: &str) -> Option<Vec<String>> {
+    let parts: Vec<&str> = body.split("..").collect();
+    if parts.len() != 2 && parts.len() != 3 {
+        return None;
+    }
+    let step: i64 = match parts.get(2) {
+        Some(s) => s.parse::<i64>().ok()?,
+        None => 1,
+    }
+    .abs()
+    .max(1);
+
+    if let (Ok(start), Ok(end)) = (parts[0].parse::<i64>(), parts[1].parse::<i64>()) {
+        return Some(numeric_range(start, end, step));
+    }
+
+    let mut start_chars = parts[0].chars();
+    let mut end_chars = parts[1].chars();
+    if let (Some(start), None, Some(end), None) = (
+        start_chars.next(),
+        start_chars.next(),
+        end_chars.next(),
+        end_chars.next(),
+    ) {
+        return Some(char_range(start, end, step as u32));
+    }
+
+    None
+}
+
+fn numeric_range(start: i64, end: i64, step: i64) -> Vec<String> {
+    let mut out = Vec::new();
+    if start <= end {
+        let mut n = start;
+        while n <= end {
+            out.push(n.to_string());
+            n = match n.checked_add(step) {
+                Some(n) => n,
+                None => break,
+            };
+        }
+    } else {
+        let mut n = start;
+        while n >= end {
+            out.push(n.to_string());
+            n = match n.checked_sub(step) {
+                Some(n) => n,
+                None => break,
+            };
+        }
+    }
+    out
+}
+
+fn char_range(start: char, end: char, step: u32) -> Vec<String> {
+    let start = start as u32;
+    let end = end as u32;
+    let mut out = Vec::new();
+    if start <= end {
+        let mut n = start;
+        while n <= end {
+            if let Some(c) = char::from_u32(n) {
+                out.push(c.to_string());
+            }
+            n = match n.checked_add(step) {
+                Some(n) => n,
+                None => break,
+            };
+        }
+    } else {
+        let mut n = start;
+        while n >= end {
+            if let Some(c) = char::from_u32(n) {
+                out.push(c.to_string());
+            }
+            if n < step {
+                break;
+            }
+            n -= step;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_comma_list() {
+        assert_eq!(expand("a{x,y,z}b").unwrap(), vec!["axb", "ayb", "azb"]);
+    }
+
+    #[test]
+    fn expands_numeric_range() {
+        assert_eq!(
+            expand("file-{1..3}.txt").unwrap(),
+            vec!["file-1.txt", "file-2.txt", "file-3.txt"]
+        );
+    }
+
+    #[test]
+    fn expands_descending_numeric_range() {
+        assert_eq!(expand("{3..1}").unwrap(), vec!["3", "2", "1"]);
+    }
+
+    #[test]
+    fn expands_char_range() {
+        assert_eq!(expand("{a..d}").unwrap(), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn expands_multiple_groups_as_cartesian_product() {
+        assert_eq!(
+            expand("{a,b}-{1,2}").unwrap(),
+            vec!["a-1", "a-2", "b-1", "b-2"]
+        );
+    }
+
+    #[test]
+    fn leaves_non_expandable_braces_untouched() {
+        assert_eq!(expand("{hello}").unwrap(), vec!["{hello}"]);
+    }
+
+    #[test]
+    fn reports_unterminated_brace() {
+        assert!(expand("file-{1..3.txt").is_err());
+    }
+
+    #[test]
+    fn char_range_skips_the_surrogate_gap_instead_of_panicking() {
+        assert_eq!(
+            expand("{\u{D7FF}..\u{E000}}").unwrap(),
+            vec!["\u{D7FF}", "\u{E000}"]
+        );
+    }
+
+    #[test]
+    fn numeric_range_stops_at_i64_bounds_instead_of_overflowing() {
+        assert_eq!(
+            expand("{9223372036854775806..9223372036854775807}").unwrap(),
+            vec!["9223372036854775806", "9223372036854775807"]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::eval::eval;
+use crate::value::{values_equal, Value};
+use std::time::{Duration, Instant};
+
+/// How many times each form runs when `compare-bench` isn't given an
+/// explicit iteration count. Large enough to smooth out scheduler noise
+/// for a quick process spawn, small enough that comparing two forms stays
+/// an interactive, seconds-scale command rather than a batch job.
+const DEFAULT_ITERATIONS: usize = 20;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![spec(
+        "compare-bench",
+        Arity::Range(2, 3),
+        "(compare-bench form-a form-b) or (compare-bench form-a form-b iterations): runs both quoted forms repeatedly and reports timing and output equality.",
+        builtin_compare_bench,
+    )]
+}
+
+/// `(compare-bench '(...) '(...))`: runs each of two quoted forms
+/// `iterations` times (20 by default), and reports how long each took and
+/// whether they produced the same result -- meant for deciding whether an
+/// in-process pipeline is worth it over shelling out to an external tool,
+/// or any other "which of these two ways is faster" question.
+///
+/// This only reports timing and output equality, not allocation counts:
+/// the request asked for allocation as a third metric, but this crate
+/// never overrides the global allocator, so there's no instrumentation to
+/// read a count from.
+fn builtin_compare_bench(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let (a, b, iterations) = match args.as_slice() {
+        [a, b] => (a, b, DEFAULT_ITERATIONS),
+        [a, b, Value::Int(n)] if *n > 0 => (a, b, *n as usize),
+        [_, _, Value::Int(_)] => {
+            return Err(ShellError::Eval("compare-bench iterations must be positive".into()))
+        }
+        _ => {
+            return Err(ShellError::Arity(
+                "compare-bench expects (form-a form-b) or (form-a form-b iterations)".into(),
+            ))
+        }
+    };
+
+    let (a_result, a_elapsed) = run_timed(a, env, iterations)?;
+    let (b_result, b_elapsed) = run_timed(b, env, iterations)?;
+
+    Ok(Value::Str(format!(
+        "a: {:?} total, {:?} per call\nb: {:?} total, {:?} per call\noutputs equal: {}",
+        a_elapsed,
+        a_elapsed / iterations as u32,
+        b_elapsed,
+        b_elapsed / iterations as u32,
+        values_equal(&a_result, &b_result),
+    )))
+}
+
+fn run_timed(form: &Value, env: &Environment, iterations: usize) -> Result<(Value, Duration), ShellError> {
+    let started = Instant::now();
+    let mut last = Value::Nil;
+    for _ in 0..iterations {
+        last = eval(form, env)?;
+    }
+    Ok((last, started.elapsed()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Value {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse(source).unwrap(), &env).unwrap()
+    }
+
+    #[test]
+    fn reports_equal_outputs_for_equivalent_forms() {
+        let report = run("(compare-bench '(+ 1 2) '(+ 2 1) 5)").to_string();
+        assert!(report.contains("outputs equal: true"));
+    }
+
+    #[test]
+    fn reports_unequal_outputs_for_different_forms() {
+        let report = run("(compare-bench '(+ 1 2) '(+ 1 3) 5)").to_string();
+        assert!(report.contains("outputs equal: false"));
+    }
+
+    #[test]
+    fn defaults_to_twenty_iterations() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse("(define calls 0)").unwrap(), &env).unwrap();
+        eval(
+            &Parser::parse("(define (bump) (set! calls (+ calls 1)))").unwrap(),
+            &env,
+        )
+        .unwrap();
+        eval(&Parser::parse("(compare-bench '(bump) '(bump))").unwrap(), &env).unwrap();
+        assert_eq!(env.get("calls").unwrap().to_string(), "40");
+    }
+
+    #[test]
+    fn zero_iterations_is_rejected() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(&Parser::parse("(compare-bench '(+ 1 1) '(+ 1 1) 0)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn an_error_in_either_form_propagates() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(
+            &Parser::parse("(compare-bench '(undefined-fn) '(+ 1 1))").unwrap(),
+            &env
+        )
+        .is_err());
+    }
+}
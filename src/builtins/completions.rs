@@ -0,0 +1,110 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::completions::{CommandSpec, Shell};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "register-completion",
+            Arity::Exact(3),
+            "(register-completion name flags subcommands): declares name's completable flags and subcommands (lists of strings) for later (completions ...) calls.",
+            builtin_register_completion,
+        ),
+        spec(
+            "completions",
+            Arity::Exact(2),
+            "(completions name shell): the bash|zsh|fish completion script for a command previously declared with register-completion.",
+            builtin_completions,
+        ),
+    ]
+}
+
+fn string_list(value: &Value, caller: &str) -> Result<Vec<String>, ShellError> {
+    match value {
+        Value::List(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Str(s) => Ok(s.clone()),
+                other => Err(ShellError::Eval(format!(
+                    "{caller} expects a list of strings, got {}",
+                    other.type_name()
+                ))),
+            })
+            .collect(),
+        other => Err(ShellError::Eval(format!(
+            "{caller} expects a list of strings, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn builtin_register_completion(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let (name, flags, subcommands) = match args.as_slice() {
+        [Value::Str(name), flags, subcommands] => (name.clone(), flags, subcommands),
+        _ => {
+            return Err(ShellError::Arity(
+                "register-completion expects a name string and two lists of strings".into(),
+            ))
+        }
+    };
+
+    let mut command = CommandSpec::new(name);
+    command.flags = string_list(flags, "register-completion")?;
+    command.subcommands = string_list(subcommands, "register-completion")?;
+    crate::completions::register(command);
+    Ok(Value::Nil)
+}
+
+fn builtin_completions(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let (name, shell) = match args.as_slice() {
+        [Value::Str(name), Value::Str(shell)] => (name, shell),
+        _ => return Err(ShellError::Arity("completions expects a name string and a shell string".into())),
+    };
+    let shell = Shell::parse(shell)
+        .ok_or_else(|| ShellError::Eval(format!("completions: unknown shell {shell:?} (want bash, zsh, or fish)")))?;
+    crate::completions::completion_script(shell, name)
+        .map(Value::Str)
+        .ok_or_else(|| ShellError::Eval(format!("completions: no command registered under {name:?}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    #[test]
+    fn register_then_completions_round_trips_through_lisp() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(
+            &Parser::parse("(register-completion \"mytool\" (list \"--verbose\") (list \"build\"))").unwrap(),
+            &env,
+        )
+        .unwrap();
+        let result = eval(&Parser::parse("(completions \"mytool\" \"fish\")").unwrap(), &env).unwrap();
+        match result {
+            Value::Str(s) => assert!(s.contains("complete -c mytool -l 'verbose'")),
+            other => panic!("expected a string, got {other}"),
+        }
+    }
+
+    #[test]
+    fn completions_reports_an_unregistered_name() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        let result = eval(&Parser::parse("(completions \"no-such-tool\" \"bash\")").unwrap(), &env);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn completions_rejects_an_unknown_shell() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse("(register-completion \"anytool\" '() '())").unwrap(), &env).unwrap();
+        let result = eval(&Parser::parse("(completions \"anytool\" \"powershell\")").unwrap(), &env);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,182 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "make-error",
+            Arity::AtLeast(2),
+            "(make-error kind msg irritant...): builds a first-class error value with the given kind (a symbol or keyword) and message, and any number of extra irritant values.",
+            builtin_make_error,
+        ),
+        spec(
+            "error-kind",
+            Arity::Exact(1),
+            "(error-kind e): the kind of error e was built or caught as, as a keyword.",
+            builtin_error_kind,
+        ),
+        spec(
+            "error-message",
+            Arity::Exact(1),
+            "(error-message e): the human-readable message carried by error e.",
+            builtin_error_message,
+        ),
+        spec(
+            "error-location",
+            Arity::Exact(1),
+            "(error-location e): the innermost function call in progress when e was raised, or nil if it was built with make-error or raised outside any function call.",
+            builtin_error_location,
+        ),
+        spec(
+            "error-irritants",
+            Arity::Exact(1),
+            "(error-irritants e): the extra values e was built with, as a list; empty for an error caught with catch.",
+            builtin_error_irritants,
+        ),
+    ]
+}
+
+/// `(make-error 'timeout "no response" 30)`: builds a [`Value::Error`]
+/// directly, for scripts that want to signal a structured failure of
+/// their own rather than only inspecting one `catch` produced.
+fn builtin_make_error(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let mut args = args.into_iter();
+    let kind = match args.next() {
+        Some(Value::Keyword(kind) | Value::Symbol(kind)) => kind,
+        Some(other) => {
+            return Err(ShellError::Eval(format!(
+                "make-error expects a symbol or keyword kind, got {}",
+                other.type_name()
+            )))
+        }
+        None => return Err(ShellError::Arity("make-error expects a kind and a message".into())),
+    };
+    let message = match args.next() {
+        Some(Value::Str(message)) => message,
+        Some(other) => {
+            return Err(ShellError::Eval(format!(
+                "make-error expects a string message, got {}",
+                other.type_name()
+            )))
+        }
+        None => return Err(ShellError::Arity("make-error expects a kind and a message".into())),
+    };
+    Ok(Value::error(kind, message, None, args.collect()))
+}
+
+fn as_error<'a>(args: &'a [Value], caller: &str) -> Result<&'a crate::value::ErrorRecord, ShellError> {
+    match args {
+        [Value::Error(e)] => Ok(e),
+        [other] => Err(ShellError::Eval(format!(
+            "{caller} expects an error, got {}",
+            other.type_name()
+        ))),
+        _ => Err(ShellError::Arity(format!("{caller} expects 1 argument"))),
+    }
+}
+
+fn builtin_error_kind(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(Value::Keyword(as_error(&args, "error-kind")?.kind.clone()))
+}
+
+fn builtin_error_message(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(Value::Str(as_error(&args, "error-message")?.message.clone()))
+}
+
+fn builtin_error_location(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(match &as_error(&args, "error-location")?.location {
+        Some(location) => Value::Str(location.clone()),
+        None => Value::Nil,
+    })
+}
+
+fn builtin_error_irritants(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(Value::list(as_error(&args, "error-irritants")?.irritants.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    fn fresh_env() -> Environment {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        env
+    }
+
+    fn eval_str(src: &str, env: &Environment) -> Value {
+        eval(&Parser::parse(src).unwrap(), env).unwrap()
+    }
+
+    #[test]
+    fn make_error_accepts_a_symbol_kind() {
+        let env = fresh_env();
+        let result = eval_str("(make-error 'timeout \"no response\")", &env);
+        assert!(matches!(result, Value::Error(_)));
+        assert_eq!(eval_str("(error-kind (make-error 'timeout \"no response\"))", &env).to_string(), ":timeout");
+    }
+
+    #[test]
+    fn make_error_accepts_a_keyword_kind_and_irritants() {
+        let env = fresh_env();
+        assert_eq!(
+            eval_str("(error-irritants (make-error :bad-input \"oops\" 1 2 3))", &env).to_string(),
+            "(1 2 3)"
+        );
+    }
+
+    #[test]
+    fn error_message_reads_back_the_message() {
+        let env = fresh_env();
+        assert_eq!(
+            eval_str("(error-message (make-error 'oops \"bad thing happened\"))", &env).to_string(),
+            "\"bad thing happened\""
+        );
+    }
+
+    #[test]
+    fn error_location_is_nil_for_a_made_error() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(error-location (make-error 'oops \"bad thing happened\"))", &env),
+            Value::Nil
+        ));
+    }
+
+    #[test]
+    fn caught_undefined_symbol_reports_its_kind_and_message() {
+        let env = fresh_env();
+        let kind = eval_str("(error-kind (catch undefined-name))", &env);
+        assert_eq!(kind.to_string(), ":undefined");
+        let message = eval_str("(error-message (catch undefined-name))", &env);
+        assert!(message.to_string().contains("undefined-name"));
+    }
+
+    #[test]
+    fn caught_error_reports_the_function_it_failed_inside() {
+        let env = fresh_env();
+        for form in Parser::parse_all("(define (risky) (undefined-fn))").unwrap() {
+            eval(&form, &env).unwrap();
+        }
+        assert_eq!(
+            eval_str("(error-location (catch (risky)))", &env).to_string(),
+            "\"risky\""
+        );
+    }
+
+    #[test]
+    fn accessors_reject_a_non_error_argument() {
+        let env = fresh_env();
+        assert!(eval(&Parser::parse("(error-kind 5)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn make_error_requires_a_kind_and_a_message() {
+        let env = fresh_env();
+        assert!(eval(&Parser::parse("(make-error 'oops)").unwrap(), &env).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "use-feature",
+            Arity::Exact(1),
+            "(use-feature 'name): opts into a staged language change by name; see (features) for what's available.",
+            builtin_use_feature,
+        ),
+        spec(
+            "features",
+            Arity::Exact(0),
+            "(features): the list of (name . description) pairs for every feature use-feature accepts.",
+            builtin_features,
+        ),
+    ]
+}
+
+fn builtin_use_feature(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(name)] => {
+            crate::features::enable(name).map_err(ShellError::Eval)?;
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("use-feature expects 'name".into())),
+    }
+}
+
+fn builtin_features(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::list(
+            crate::features::names()
+                .into_iter()
+                .map(|(name, description)| {
+                    Value::dotted(vec![Value::Symbol(name.into())], Value::Str(description.into()))
+                })
+                .collect(),
+        )),
+        _ => Err(ShellError::Arity("features expects no arguments".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::apply;
+
+    #[test]
+    fn use_feature_enables_a_known_flag() {
+        let env = Environment::new_global();
+        apply(&Value::Builtin("use-feature", builtin_use_feature), vec![Value::Symbol("strict-arity".into())], &env)
+            .unwrap();
+        assert!(crate::features::is_enabled("strict-arity"));
+    }
+
+    #[test]
+    fn use_feature_rejects_an_unknown_flag() {
+        let env = Environment::new_global();
+        let result = apply(
+            &Value::Builtin("use-feature", builtin_use_feature),
+            vec![Value::Symbol("cracked_shell_builtins_features_test_missing".into())],
+            &env,
+        );
+        assert!(matches!(result, Err(ShellError::Eval(_))));
+    }
+
+    #[test]
+    fn features_lists_strict_arity() {
+        let env = Environment::new_global();
+        let result = apply(&Value::Builtin("features", builtin_features), vec![], &env).unwrap();
+        assert!(result.to_string().contains("strict-arity"));
+    }
+}
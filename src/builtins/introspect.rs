@@ -0,0 +1,248 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec("print", Arity::Any, "(print v...): prints its arguments separated by spaces.", builtin_print),
+        spec("pp", Arity::Exact(1), "(pp v): pretty-prints v, wrapping long values across lines.", builtin_pp),
+        spec("inspect", Arity::Exact(1), "(inspect v): prints a detailed, structural view of v.", builtin_inspect),
+        spec(
+            "describe",
+            Arity::Exact(1),
+            "(describe v): prints v's type, length/arity, sharing (Rc strong count), and a rough memory estimate -- for chasing sharing/mutation bugs.",
+            builtin_describe,
+        ),
+        spec("await", Arity::Exact(1), "(await future): blocks until an async/parallel future resolves, and returns its value.", builtin_await),
+        spec("bindings", Arity::Exact(0), "(bindings): the sorted names bound directly in the calling frame.", builtin_bindings),
+        spec(
+            "global-bindings",
+            Arity::Exact(0),
+            "(global-bindings): the sorted names bound in the outermost frame, walking past any local let/lambda frames to get there.",
+            builtin_global_bindings,
+        ),
+        spec(
+            "use",
+            Arity::Exact(1),
+            "(use 'namespace): imports every namespace/name binding from the global frame into the calling environment as a plain, unprefixed name.",
+            builtin_use,
+        ),
+        spec(
+            "error-stack",
+            Arity::Exact(0),
+            "(error-stack): the call stack captured when the most recent top-level form errored out, innermost call first.",
+            builtin_error_stack,
+        ),
+        spec(
+            "sandbox-eval",
+            Arity::Range(1, 2),
+            "(sandbox-eval source) or (sandbox-eval source allow-list): parses and evaluates source inside a sandbox of the calling environment.",
+            builtin_sandbox_eval,
+        ),
+    ]
+}
+
+fn builtin_print(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let rendered: Vec<String> = args.iter().map(|v| v.to_string()).collect();
+    crate::output::writeln(&rendered.join(" "));
+    Ok(Value::Nil)
+}
+
+fn builtin_pp(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [value] => {
+            crate::output::writeln(&crate::pretty::pretty(value, crate::pretty::DEFAULT_WIDTH));
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("pp expects 1 argument".into())),
+    }
+}
+
+fn builtin_inspect(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [value] => {
+            crate::inspect::inspect(value);
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("inspect expects 1 argument".into())),
+    }
+}
+
+fn builtin_describe(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [value] => {
+            crate::output::writeln(&crate::describe::describe(value));
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("describe expects 1 argument".into())),
+    }
+}
+
+fn builtin_await(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [handle] => crate::eval::eval_await(handle),
+        _ => Err(ShellError::Arity("await expects 1 argument".into())),
+    }
+}
+
+/// `(bindings)`: the sorted names bound directly in the calling frame.
+fn builtin_bindings(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            let mut names = env.local_names();
+            names.sort();
+            Ok(Value::list(names.into_iter().map(Value::Symbol).collect()))
+        }
+        _ => Err(ShellError::Arity("bindings expects no arguments".into())),
+    }
+}
+
+/// `(global-bindings)`: the sorted names bound in the outermost frame,
+/// walking past any local `let`/lambda frames to get there.
+fn builtin_global_bindings(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            let global = env
+                .frames()
+                .last()
+                .expect("Environment::frames always yields at least the current frame");
+            let mut names = global.local_names();
+            names.sort();
+            Ok(Value::list(names.into_iter().map(Value::Symbol).collect()))
+        }
+        _ => Err(ShellError::Arity("global-bindings expects no arguments".into())),
+    }
+}
+
+/// `(use 'str)`: imports every `str/name` binding from the global frame
+/// into the calling environment as a plain, unprefixed `name`. Namespaced
+/// builtins stay out of the flat global namespace until a script opts
+/// into them, so the growing builtin set doesn't crowd out script-defined
+/// names of the same short form.
+fn builtin_use(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let namespace = match args.as_slice() {
+        [Value::Symbol(namespace)] => namespace,
+        _ => return Err(ShellError::Arity("use expects a namespace symbol".into())),
+    };
+
+    let global = env
+        .frames()
+        .last()
+        .expect("Environment::frames always yields at least the current frame");
+    if !crate::builtins::install_namespace(&global, namespace) {
+        return Err(ShellError::Eval(format!("unknown namespace: {namespace}")));
+    }
+    let prefix = format!("{namespace}/");
+    let mut names: Vec<String> = global
+        .local_names()
+        .into_iter()
+        .filter(|name| name.starts_with(&prefix))
+        .collect();
+
+    names.sort();
+    for name in names {
+        if let Some(value) = global.get(&name) {
+            env.define(&name[prefix.len()..], value);
+        }
+    }
+    Ok(Value::Nil)
+}
+
+/// `(error-stack)`: the names of the user functions that were being
+/// called when the most recent top-level form failed, innermost first.
+/// Cleared before every top-level form runs (see [`crate::callstack`]), so
+/// this is empty after a form that succeeded.
+///
+/// This takes no argument: it reads back the one backtrace every
+/// top-level caller already keeps around for printing, not one attached
+/// to a particular error value returned by `catch`.
+fn builtin_error_stack(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::list(
+            crate::callstack::snapshot().into_iter().map(Value::Str).collect(),
+        )),
+        _ => Err(ShellError::Arity("error-stack expects no arguments".into())),
+    }
+}
+
+/// `(sandbox-eval "(+ 1 2)")` or `(sandbox-eval source '(proc/run))`:
+/// parses and evaluates `source` inside a [`crate::sandbox::sandbox`] of
+/// the calling environment, for running untrusted snippets or plugin code
+/// without handing it the filesystem or process builtins outright. The
+/// optional second argument names privileged builtins to whitelist.
+fn builtin_sandbox_eval(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let (source, allow) = match args.as_slice() {
+        [Value::Str(source)] => (source, Vec::new()),
+        [Value::Str(source), Value::List(names)] => {
+            let allow = names
+                .iter()
+                .map(|name| match name {
+                    Value::Symbol(s) => Ok(s.as_str()),
+                    other => Err(ShellError::Eval(format!(
+                        "sandbox-eval allowlist must be symbols, got {}",
+                        other.type_name()
+                    ))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            (source, allow)
+        }
+        _ => {
+            return Err(ShellError::Arity(
+                "sandbox-eval expects (source) or (source allow-list)".into(),
+            ))
+        }
+    };
+
+    let sandboxed = crate::sandbox::sandbox(env, &allow);
+    let form = crate::parser::Parser::parse(source)?;
+    crate::eval::eval(&form, &sandboxed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Value {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse(source).unwrap(), &env).unwrap()
+    }
+
+    #[test]
+    fn error_stack_is_empty_with_nothing_in_progress() {
+        crate::callstack::clear();
+        assert_eq!(run("(error-stack)").to_string(), "()");
+    }
+
+    #[test]
+    fn error_stack_records_calls_left_on_the_way_down_to_a_failure() {
+        crate::callstack::clear();
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        for form in Parser::parse_all("(define (inner) (undefined-fn)) (define (outer) (inner))").unwrap() {
+            eval(&form, &env).unwrap();
+        }
+        assert!(eval(&Parser::parse("(outer)").unwrap(), &env).is_err());
+        let result = eval(&Parser::parse("(error-stack)").unwrap(), &env).unwrap();
+        assert_eq!(result.to_string(), "(\"inner\" \"outer\")");
+        crate::callstack::clear();
+    }
+
+    #[test]
+    fn error_stack_rejects_arguments() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(&Parser::parse("(error-stack 1)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn describe_returns_nil_and_accepts_exactly_one_argument() {
+        assert!(matches!(run("(describe 5)"), Value::Nil));
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(&Parser::parse("(describe)").unwrap(), &env).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+use std::rc::Rc;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec("list", Arity::Any, "(list v...): builds a list of its arguments.", builtin_list),
+        spec("car", Arity::Exact(1), "(car lst): the first element of lst.", builtin_car),
+        spec("cdr", Arity::Exact(1), "(cdr lst): lst with its first element removed.", builtin_cdr),
+        spec("cons", Arity::Exact(2), "(cons v lst): lst with v prepended.", builtin_cons),
+        spec("null?", Arity::Exact(1), "(null? v): true if v is an empty list or nil.", builtin_is_null),
+        spec(
+            "equal?",
+            Arity::Exact(2),
+            "(equal? a b): true if a and b are structurally equal, recursively for lists.",
+            builtin_equal,
+        ),
+    ]
+}
+
+fn builtin_list(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(Value::list(args))
+}
+
+fn builtin_car(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::List(items)] => items
+            .first()
+            .cloned()
+            .ok_or_else(|| ShellError::Eval("car of empty list".into())),
+        _ => Err(ShellError::Arity("car expects a single list argument".into())),
+    }
+}
+
+/// `(cdr lst)`: `lst` with its first element removed. When `lst` is the
+/// only owner of its backing vector (the common case in a pipeline of list
+/// transformations, where the list argument is a freshly built value that
+/// nothing else holds a reference to), [`Rc::try_unwrap`] hands back that
+/// vector instead of cloning it, so shifting off the first element is a
+/// single in-place `remove` rather than an `O(n)` clone of every remaining
+/// element. A shared list (e.g. a variable bound elsewhere) still falls
+/// back to cloning, same as before.
+fn builtin_cdr(mut args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::List(_)] => {}
+        _ => return Err(ShellError::Arity("cdr expects a single list argument".into())),
+    }
+    let Some(Value::List(rc)) = args.pop() else {
+        unreachable!("just matched Value::List above");
+    };
+    let mut items = match Rc::try_unwrap(rc) {
+        Ok(items) => items,
+        Err(rc) => (*rc).clone(),
+    };
+    if items.is_empty() {
+        return Ok(Value::list(items));
+    }
+    items.remove(0);
+    Ok(Value::list(items))
+}
+
+/// `(cons v lst)`: `lst` with `v` prepended. Takes ownership of `lst` and,
+/// when nothing else holds a reference to its backing vector, uses
+/// [`Rc::try_unwrap`] to grow it in place instead of allocating a fresh
+/// vector and cloning every existing element into it -- the same
+/// uniquely-referenced fast path as [`builtin_cdr`].
+fn builtin_cons(mut args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [_, Value::List(_)] => {}
+        _ => return Err(ShellError::Arity("cons expects (value list)".into())),
+    }
+    let Some(Value::List(rc)) = args.pop() else {
+        unreachable!("just matched Value::List above");
+    };
+    let head = args.pop().expect("arity checked above: exactly 2 arguments");
+    let mut items = match Rc::try_unwrap(rc) {
+        Ok(items) => items,
+        Err(rc) => (*rc).clone(),
+    };
+    items.insert(0, head);
+    Ok(Value::list(items))
+}
+
+fn builtin_is_null(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::List(items)] => Ok(Value::Bool(items.is_empty())),
+        [Value::Nil] => Ok(Value::Bool(true)),
+        [_] => Ok(Value::Bool(false)),
+        _ => Err(ShellError::Arity("null? expects 1 argument".into())),
+    }
+}
+
+fn builtin_equal(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [a, b] => Ok(Value::Bool(crate::value::values_equal(a, b))),
+        _ => Err(ShellError::Arity("equal? expects 2 arguments".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_lists_with_the_same_elements_are_equal() {
+        let a = Value::list(vec![Value::Int(1), Value::Str("x".into())]);
+        let b = Value::list(vec![Value::Int(1), Value::Str("x".into())]);
+        let result = builtin_equal(vec![a, b], &Environment::new_global()).unwrap();
+        assert!(matches!(result, Value::Bool(true)));
+    }
+
+    #[test]
+    fn lists_with_different_elements_are_not_equal() {
+        let a = Value::list(vec![Value::Int(1)]);
+        let b = Value::list(vec![Value::Int(2)]);
+        let result = builtin_equal(vec![a, b], &Environment::new_global()).unwrap();
+        assert!(matches!(result, Value::Bool(false)));
+    }
+
+    #[test]
+    fn an_int_and_an_equal_looking_float_are_not_equal() {
+        let result = builtin_equal(vec![Value::Int(1), Value::Float(1.0)], &Environment::new_global()).unwrap();
+        assert!(matches!(result, Value::Bool(false)));
+    }
+
+    #[test]
+    fn cons_prepends_a_uniquely_owned_list_in_place() {
+        let tail = Value::list(vec![Value::Int(2), Value::Int(3)]);
+        let result = builtin_cons(vec![Value::Int(1), tail], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "(1 2 3)");
+    }
+
+    #[test]
+    fn cons_still_works_when_the_list_is_shared() {
+        let tail = Value::list(vec![Value::Int(2), Value::Int(3)]);
+        let shared = tail.clone();
+        let result = builtin_cons(vec![Value::Int(1), tail], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "(1 2 3)");
+        assert_eq!(shared.to_string(), "(2 3)");
+    }
+
+    #[test]
+    fn cdr_removes_the_first_element_of_a_uniquely_owned_list() {
+        let list = Value::list(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+        let result = builtin_cdr(vec![list], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "(2 3)");
+    }
+
+    #[test]
+    fn cdr_still_works_when_the_list_is_shared() {
+        let list = Value::list(vec![Value::Int(1), Value::Int(2)]);
+        let shared = list.clone();
+        let result = builtin_cdr(vec![list], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "(2)");
+        assert_eq!(shared.to_string(), "(1 2)");
+    }
+
+    #[test]
+    fn cdr_of_an_empty_list_is_empty() {
+        let result = builtin_cdr(vec![Value::list(vec![])], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "()");
+    }
+}
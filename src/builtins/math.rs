@@ -0,0 +1,174 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec("+", Arity::Any, "(+ n...): sums its arguments.", builtin_add),
+        spec(
+            "-",
+            Arity::AtLeast(1),
+            "(- n...): subtracts every argument after the first from it, or negates a single argument.",
+            builtin_sub,
+        ),
+        spec("*", Arity::Any, "(* n...): multiplies its arguments.", builtin_mul),
+        spec(
+            "/",
+            Arity::AtLeast(2),
+            "(/ n d...): divides the first argument by each of the rest in turn.",
+            builtin_div,
+        ),
+        spec(
+            "=",
+            Arity::AtLeast(2),
+            "(= n...): true if every argument is numerically equal.",
+            builtin_eq,
+        ),
+        spec(
+            "<",
+            Arity::AtLeast(2),
+            "(< n...): true if the arguments are strictly increasing.",
+            builtin_lt,
+        ),
+        spec(
+            ">",
+            Arity::AtLeast(2),
+            "(> n...): true if the arguments are strictly decreasing.",
+            builtin_gt,
+        ),
+    ]
+}
+
+pub(super) fn as_f64(v: &Value) -> Result<f64, ShellError> {
+    match v {
+        Value::Int(i) => Ok(*i as f64),
+        Value::Float(n) => Ok(*n),
+        other => Err(ShellError::Eval(format!(
+            "expected a number, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
+fn numeric_result(values: &[Value], fold: impl Fn(f64, f64) -> f64, init: f64) -> Result<Value, ShellError> {
+    let all_ints = values.iter().all(|v| matches!(v, Value::Int(_)));
+    let result = values
+        .iter()
+        .map(as_f64)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .fold(init, fold);
+    if all_ints {
+        Ok(Value::Int(result as i64))
+    } else {
+        Ok(Value::Float(result))
+    }
+}
+
+fn builtin_add(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    numeric_result(&args, |a, b| a + b, 0.0)
+}
+
+fn builtin_mul(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    numeric_result(&args, |a, b| a * b, 1.0)
+}
+
+fn builtin_sub(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Err(ShellError::Arity("- expects at least 1 argument".into())),
+        [single] => numeric_result(&[Value::Int(0), single.clone()], |a, b| a - b, 0.0),
+        [first, rest @ ..] => {
+            let mut acc = as_f64(first)?;
+            let all_ints =
+                matches!(first, Value::Int(_)) && rest.iter().all(|v| matches!(v, Value::Int(_)));
+            for v in rest {
+                acc -= as_f64(v)?;
+            }
+            Ok(if all_ints {
+                Value::Int(acc as i64)
+            } else {
+                Value::Float(acc)
+            })
+        }
+    }
+}
+
+fn builtin_div(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [first, rest @ ..] if !rest.is_empty() => {
+            let mut acc = as_f64(first)?;
+            for v in rest {
+                let divisor = as_f64(v)?;
+                if divisor == 0.0 {
+                    return Err(ShellError::Eval("division by zero".into()));
+                }
+                acc /= divisor;
+            }
+            Ok(Value::Float(acc))
+        }
+        _ => Err(ShellError::Arity("/ expects at least 2 arguments".into())),
+    }
+}
+
+/// Under the `strict-arity` feature (see [`crate::features`]), a
+/// non-numeric argument is a type error instead of silently comparing
+/// unequal -- the staged replacement for this builtin's long-standing
+/// "anything that isn't a number just isn't equal" behavior.
+fn builtin_eq(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    if args.len() < 2 {
+        return Err(ShellError::Arity("= expects at least 2 arguments".into()));
+    }
+    let first = as_f64(&args[0])?;
+    if crate::features::is_enabled("strict-arity") {
+        for v in &args[1..] {
+            if as_f64(v)? != first {
+                return Ok(Value::Bool(false));
+            }
+        }
+        return Ok(Value::Bool(true));
+    }
+    Ok(Value::Bool(
+        args[1..].iter().all(|v| as_f64(v).map(|n| n == first).unwrap_or(false)),
+    ))
+}
+
+fn builtin_lt(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    compare(&args, |a, b| a < b)
+}
+
+fn builtin_gt(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    compare(&args, |a, b| a > b)
+}
+
+fn compare(args: &[Value], op: impl Fn(f64, f64) -> bool) -> Result<Value, ShellError> {
+    if args.len() < 2 {
+        return Err(ShellError::Arity("comparison expects at least 2 arguments".into()));
+    }
+    for pair in args.windows(2) {
+        if !op(as_f64(&pair[0])?, as_f64(&pair[1])?) {
+            return Ok(Value::Bool(false));
+        }
+    }
+    Ok(Value::Bool(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eq_treats_a_non_numeric_argument_as_unequal_by_default() {
+        let env = Environment::new_global();
+        let result = builtin_eq(vec![Value::Int(1), Value::Str("a".into())], &env).unwrap();
+        assert!(matches!(result, Value::Bool(false)));
+    }
+
+    #[test]
+    fn eq_errors_on_a_non_numeric_argument_under_strict_arity() {
+        crate::features::enable("strict-arity").unwrap();
+        let env = Environment::new_global();
+        let result = builtin_eq(vec![Value::Int(1), Value::Str("a".into())], &env);
+        assert!(matches!(result, Err(ShellError::Eval(_))));
+    }
+}
@@ -0,0 +1,127 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::memo::Memo;
+use crate::value::Value;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "memoize",
+            Arity::Exact(1),
+            "(memoize f): wraps f in a cache keyed on equal? arguments, so repeated calls with the same arguments skip straight to the cached result.",
+            builtin_memoize,
+        ),
+        spec(
+            "memo-clear!",
+            Arity::Exact(1),
+            "(memo-clear! m): discards every result cached in m, so the next call for each argument list re-invokes the wrapped function.",
+            builtin_memo_clear,
+        ),
+        spec(
+            "memo-size",
+            Arity::Exact(1),
+            "(memo-size m): the number of distinct argument lists currently cached in m.",
+            builtin_memo_size,
+        ),
+    ]
+}
+
+/// `(memoize f)`: wraps `f` -- a builtin, native function, lambda, or
+/// already-memoized function -- in a [`Memo`] that's callable exactly
+/// like `f` itself, via `apply`'s `Value::Memo` arm.
+fn builtin_memoize(mut args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Builtin(..) | Value::Native(..) | Value::Lambda(_) | Value::Memo(_)] => {
+            Ok(Value::Memo(Rc::new(RefCell::new(Memo::new(args.pop().unwrap())))))
+        }
+        [other] => Err(ShellError::Eval(format!(
+            "memoize expects a callable, got {}",
+            other.type_name()
+        ))),
+        _ => Err(ShellError::Arity("memoize expects 1 argument".into())),
+    }
+}
+
+fn builtin_memo_clear(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Memo(memo)] => {
+            memo.borrow_mut().clear();
+            Ok(Value::Nil)
+        }
+        [other] => Err(ShellError::Eval(format!(
+            "memo-clear! expects a memoized function, got {}",
+            other.type_name()
+        ))),
+        _ => Err(ShellError::Arity("memo-clear! expects 1 argument".into())),
+    }
+}
+
+fn builtin_memo_size(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Memo(memo)] => Ok(Value::Int(memo.borrow().len() as i64)),
+        [other] => Err(ShellError::Eval(format!(
+            "memo-size expects a memoized function, got {}",
+            other.type_name()
+        ))),
+        _ => Err(ShellError::Arity("memo-size expects 1 argument".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::{apply, eval};
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Value {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        let forms = Parser::parse_all(source).unwrap();
+        let mut result = Value::Nil;
+        for form in &forms {
+            result = eval(form, &env).unwrap();
+        }
+        result
+    }
+
+    #[test]
+    fn memoized_lambda_returns_the_same_result() {
+        let result = run("(define slow-square (memoize (lambda (x) (* x x)))) (slow-square 7)");
+        assert!(matches!(result, Value::Int(49)));
+    }
+
+    #[test]
+    fn memo_size_counts_distinct_argument_lists() {
+        let result = run(
+            "(define f (memoize (lambda (x) x))) (f 1) (f 2) (f 1) (memo-size f)",
+        );
+        assert!(matches!(result, Value::Int(2)));
+    }
+
+    #[test]
+    fn memo_clear_empties_the_cache() {
+        let result = run(
+            "(define f (memoize (lambda (x) x))) (f 1) (memo-clear! f) (memo-size f)",
+        );
+        assert!(matches!(result, Value::Int(0)));
+    }
+
+    #[test]
+    fn memoize_rejects_a_non_callable() {
+        let env = Environment::new_global();
+        assert!(builtin_memoize(vec![Value::Int(1)], &env).is_err());
+    }
+
+    #[test]
+    fn memoized_value_is_callable_through_apply() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        let double = env.get("+").expect("+ should be installed");
+        let memoized = builtin_memoize(vec![double], &env).unwrap();
+        let result = apply(&memoized, vec![Value::Int(2), Value::Int(3)], &env).unwrap();
+        assert!(matches!(result, Value::Int(5)));
+    }
+}
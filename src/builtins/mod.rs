@@ -0,0 +1,322 @@
+use crate::env::Environment;
+use crate::value::{Builtin, Value};
+
+mod bench;
+mod completions;
+mod errors;
+mod features;
+mod introspect;
+mod list;
+mod math;
+mod memo;
+mod options;
+mod plist;
+mod plugins;
+#[cfg(all(not(target_arch = "wasm32"), feature = "process"))]
+mod process;
+mod profile;
+mod session;
+mod string;
+#[cfg(feature = "terminal")]
+mod terminal;
+mod vector;
+
+/// How many arguments a builtin accepts. Recorded purely as metadata for
+/// `,help` (and, eventually, completion) -- each builtin still validates
+/// its own arguments and returns its own `ShellError::Arity`, since only
+/// it knows the exact shape it needs (e.g. `cons` wants a list as its
+/// second argument, not just "2 arguments").
+#[derive(Debug, Clone, Copy)]
+pub enum Arity {
+    Exact(usize),
+    AtLeast(usize),
+    Range(usize, usize),
+    Any,
+}
+
+impl std::fmt::Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exact(1) => write!(f, "1 argument"),
+            Arity::Exact(n) => write!(f, "{n} arguments"),
+            Arity::AtLeast(n) => write!(f, "at least {n} argument(s)"),
+            Arity::Range(lo, hi) => write!(f, "{lo} to {hi} arguments"),
+            Arity::Any => write!(f, "any number of arguments"),
+        }
+    }
+}
+
+/// One entry in the builtin registry: everything [`install`] needs to bind
+/// a name in the global environment, plus the metadata [`doc_for`] reads
+/// back out for `,help`.
+pub struct BuiltinSpec {
+    pub name: &'static str,
+    pub arity: Arity,
+    pub doc: &'static str,
+    pub func: Builtin,
+}
+
+/// Shorthand the per-module `specs()` tables use to build a [`BuiltinSpec`]
+/// without naming every field.
+fn spec(name: &'static str, arity: Arity, doc: &'static str, func: Builtin) -> BuiltinSpec {
+    BuiltinSpec { name, arity, doc, func }
+}
+
+/// Every builtin's registry entry, grouped by the module that owns it.
+/// Adding a builtin means adding one line to the owning module's
+/// `specs()` -- this function, [`install`], and [`doc_for`] never change.
+fn all_specs() -> Vec<BuiltinSpec> {
+    let mut specs = Vec::new();
+    specs.extend(bench::specs());
+    specs.extend(completions::specs());
+    specs.extend(errors::specs());
+    specs.extend(features::specs());
+    specs.extend(math::specs());
+    specs.extend(list::specs());
+    specs.extend(string::specs());
+    #[cfg(all(not(target_arch = "wasm32"), feature = "process"))]
+    specs.extend(process::specs());
+    specs.extend(session::specs());
+    #[cfg(feature = "terminal")]
+    specs.extend(terminal::specs());
+    specs.extend(options::specs());
+    specs.extend(memo::specs());
+    specs.extend(plist::specs());
+    specs.extend(plugins::specs());
+    specs.extend(introspect::specs());
+    specs.extend(profile::specs());
+    specs.extend(vector::specs());
+    specs
+}
+
+/// Installs every *core* builtin procedure into `env` -- one with no `/`
+/// in its name, e.g. `cons` or `+`. Namespaced builtins (`str/split`,
+/// `proc/run`, `plist/cons`, ...) are left uninstalled here; they're
+/// registered on demand by [`install_namespace`] the first time something
+/// resolves a name in their namespace, via [`resolve_lazy`]. Most scripts
+/// and one-liners never touch most namespaces, so this keeps the common
+/// case -- startup, plus running a handful of core builtins -- from paying
+/// for registry entries it never uses. There's no precompiled prelude to
+/// ship alongside this: the Lisp-level standard library the original
+/// request imagined doesn't exist in this tree, only builtins defined in
+/// Rust, so there's no AST or bytecode to precompile.
+pub fn install(env: &Environment) {
+    for spec in all_specs() {
+        if namespace_of(spec.name).is_none() {
+            env.define(spec.name, Value::Builtin(spec.name, spec.func));
+        }
+    }
+}
+
+/// The part of `name` before its first `/`, for namespaced builtins like
+/// `str/split` -- `None` for a core builtin such as `cons`.
+fn namespace_of(name: &str) -> Option<&str> {
+    name.split_once('/').map(|(namespace, _)| namespace)
+}
+
+/// Installs every spec belonging to `namespace` (e.g. `"str"`) into `env`,
+/// if it isn't already there. Returns whether `namespace` names any
+/// builtins at all, so callers can tell "already loaded or just loaded"
+/// from "no such namespace".
+pub fn install_namespace(env: &Environment, namespace: &str) -> bool {
+    let mut found = false;
+    for spec in all_specs() {
+        if namespace_of(spec.name) == Some(namespace) {
+            found = true;
+            if env.get(spec.name).is_none() {
+                env.define(spec.name, Value::Builtin(spec.name, spec.func));
+            }
+        }
+    }
+    found
+}
+
+/// Called from [`crate::eval::eval`]'s symbol lookup as a last resort,
+/// alongside [`crate::dynamic::get`]: if `name` is namespaced and its
+/// namespace hasn't been installed yet, installs it and looks `name` up
+/// again. This is what lets a script say `(str/split ...)` without first
+/// calling `(use 'str)` -- the first reference to any `str/*` name loads
+/// the whole namespace.
+pub fn resolve_lazy(name: &str, env: &Environment) -> Option<Value> {
+    let namespace = namespace_of(name)?;
+    let global = env.frames().last()?;
+    install_namespace(&global, namespace);
+    env.get(name)
+}
+
+/// Looks up a builtin's docstring and arity by name, for `,help NAME`.
+pub fn doc_for(name: &str) -> Option<(Arity, &'static str)> {
+    all_specs()
+        .into_iter()
+        .find(|s| s.name == name)
+        .map(|s| (s.arity, s.doc))
+}
+
+/// Every registered builtin's name, core and namespaced alike -- whether
+/// or not its namespace has been lazily installed into any particular
+/// `Environment` yet. Used for "did you mean?" suggestions, where a typo
+/// in a namespaced name (`str/upcse`) is still worth surfacing even
+/// before `(use 'str)` has run.
+pub fn all_names() -> Vec<&'static str> {
+    all_specs().into_iter().map(|s| s.name).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_core_builtin_is_installed() {
+        let env = Environment::new_global();
+        install(&env);
+        for spec in all_specs() {
+            if namespace_of(spec.name).is_some() {
+                continue;
+            }
+            assert!(
+                matches!(env.get(spec.name), Some(Value::Builtin(..))),
+                "{} was registered but not installed",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn every_namespaced_builtin_is_installed_by_its_namespace() {
+        let env = Environment::new_global();
+        install(&env);
+        for spec in all_specs() {
+            let Some(namespace) = namespace_of(spec.name) else {
+                continue;
+            };
+            assert!(install_namespace(&env, namespace), "{namespace} should have builtins");
+            assert!(
+                matches!(env.get(spec.name), Some(Value::Builtin(..))),
+                "{} was not installed by install_namespace",
+                spec.name
+            );
+        }
+    }
+
+    #[test]
+    fn install_namespace_reports_false_for_an_unknown_namespace() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(!install_namespace(&env, "nope"));
+    }
+
+    #[test]
+    fn resolve_lazy_installs_a_namespace_on_first_use() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(env.get("str/upcase").is_none());
+        assert!(matches!(resolve_lazy("str/upcase", &env), Some(Value::Builtin(..))));
+        assert!(matches!(env.get("str/upcase"), Some(Value::Builtin(..))));
+    }
+
+    #[test]
+    fn resolve_lazy_returns_none_for_a_core_or_unknown_name() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(resolve_lazy("cons", &env).is_none());
+        assert!(resolve_lazy("not-a-real-builtin", &env).is_none());
+    }
+
+    #[test]
+    fn doc_for_finds_a_registered_builtin() {
+        let (arity, doc) = doc_for("cons").expect("cons should be registered");
+        assert!(matches!(arity, Arity::Exact(2)));
+        assert!(doc.contains("cons"));
+    }
+
+    #[test]
+    fn doc_for_unknown_name_is_none() {
+        assert!(doc_for("not-a-real-builtin").is_none());
+    }
+}
+
+#[cfg(test)]
+mod namespace_tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    #[test]
+    fn namespaced_builtins_are_not_visible_unprefixed() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(env.get("split").is_none());
+        assert!(matches!(
+            eval(&Parser::parse("str/split").unwrap(), &env),
+            Ok(Value::Builtin(..))
+        ));
+    }
+
+    #[test]
+    fn namespaced_builtins_are_not_eagerly_installed() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(env.get("str/split").is_none());
+    }
+
+    #[test]
+    fn use_imports_a_namespace_unprefixed() {
+        let env = Environment::new_global();
+        install(&env);
+        eval(&Parser::parse("(use 'str)").unwrap(), &env).unwrap();
+        let result = eval(&Parser::parse("(split \",\" \"a,b,c\")").unwrap(), &env).unwrap();
+        assert_eq!(result.to_string(), "(\"a\" \"b\" \"c\")");
+    }
+
+    #[test]
+    fn use_on_an_unknown_namespace_is_an_error() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(eval(&Parser::parse("(use 'nope)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn sandbox_eval_runs_ordinary_code() {
+        let env = Environment::new_global();
+        install(&env);
+        let result = eval(&Parser::parse("(sandbox-eval \"(+ 1 2)\")").unwrap(), &env).unwrap();
+        assert!(matches!(result, Value::Int(3)));
+    }
+
+    #[test]
+    fn sandbox_eval_forbids_privileged_builtins_by_default() {
+        let env = Environment::new_global();
+        install(&env);
+        assert!(eval(
+            &Parser::parse("(sandbox-eval \"(proc/run \\\"echo\\\")\")").unwrap(),
+            &env
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn proc_run_lines_splits_stdout_into_a_list_of_lines() {
+        let env = Environment::new_global();
+        install(&env);
+        let result = eval(
+            &Parser::parse("(proc/run-lines \"printf\" \"a\\nb\\nc\")").unwrap(),
+            &env,
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "(\"a\" \"b\" \"c\")");
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn sandbox_eval_honors_an_explicit_allowlist() {
+        let env = Environment::new_global();
+        install(&env);
+        let result = eval(
+            &Parser::parse("(sandbox-eval \"(proc/run \\\"echo\\\" \\\"hi\\\")\" '(proc/run))").unwrap(),
+            &env,
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "\"hi\\n\"");
+    }
+}
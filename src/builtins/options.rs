@@ -0,0 +1,51 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "set-option",
+            Arity::Exact(2),
+            "(set-option 'name value): records a named setting, of any value type, in the current thread's settings registry.",
+            builtin_set_option,
+        ),
+        spec(
+            "get-option",
+            Arity::Exact(1),
+            "(get-option 'name): the value previously recorded with set-option, or nil.",
+            builtin_get_option,
+        ),
+        spec("options", Arity::Exact(0), "(options): the sorted list of configured option names.", builtin_options),
+    ]
+}
+
+/// `(set-option 'width 100)`: records a named setting, of any `Value`
+/// type, in the current thread's settings registry.
+fn builtin_set_option(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(name), value] => {
+            crate::config::set(name.clone(), value.clone());
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("set-option expects ('name value)".into())),
+    }
+}
+
+fn builtin_get_option(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(name)] => Ok(crate::config::get(name).unwrap_or(Value::Nil)),
+        _ => Err(ShellError::Arity("get-option expects 'name".into())),
+    }
+}
+
+/// `(options)`: returns the sorted list of configured option names.
+fn builtin_options(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::list(
+            crate::config::names().into_iter().map(Value::Symbol).collect(),
+        )),
+        _ => Err(ShellError::Arity("options expects no arguments".into())),
+    }
+}
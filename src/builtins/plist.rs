@@ -0,0 +1,130 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::plist::Plist;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec("plist/nil", Arity::Exact(0), "(plist/nil): the empty persistent list.", builtin_nil),
+        spec(
+            "plist/cons",
+            Arity::Exact(2),
+            "(plist/cons v plst): plst with v prepended, in O(1) -- unlike cons, it never copies plst.",
+            builtin_cons,
+        ),
+        spec("plist/car", Arity::Exact(1), "(plist/car plst): the first element of plst, in O(1).", builtin_car),
+        spec(
+            "plist/cdr",
+            Arity::Exact(1),
+            "(plist/cdr plst): plst with its first element removed, in O(1) -- unlike cdr, it never copies the rest.",
+            builtin_cdr,
+        ),
+        spec("plist/null?", Arity::Exact(1), "(plist/null? plst): true if plst is empty.", builtin_is_null),
+        spec("plist/list", Arity::Any, "(plist/list v...): builds a persistent list of its arguments.", builtin_list),
+        spec(
+            "plist/->list",
+            Arity::Exact(1),
+            "(plist/->list plst): plst converted to an ordinary list value.",
+            builtin_to_list,
+        ),
+    ]
+}
+
+fn builtin_nil(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::Plist(Plist::nil())),
+        _ => Err(ShellError::Arity("plist/nil expects no arguments".into())),
+    }
+}
+
+fn builtin_cons(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [head, Value::Plist(tail)] => Ok(Value::Plist(tail.cons(head.clone()))),
+        _ => Err(ShellError::Arity("plist/cons expects (value plist)".into())),
+    }
+}
+
+fn builtin_car(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Plist(plst)] => plst
+            .head()
+            .cloned()
+            .ok_or_else(|| ShellError::Eval("plist/car of empty plist".into())),
+        _ => Err(ShellError::Arity("plist/car expects a single plist argument".into())),
+    }
+}
+
+fn builtin_cdr(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Plist(plst)] => Ok(Value::Plist(plst.tail().unwrap_or_else(Plist::nil))),
+        _ => Err(ShellError::Arity("plist/cdr expects a single plist argument".into())),
+    }
+}
+
+fn builtin_is_null(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Plist(plst)] => Ok(Value::Bool(plst.is_nil())),
+        _ => Err(ShellError::Arity("plist/null? expects 1 argument".into())),
+    }
+}
+
+fn builtin_list(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Ok(Value::Plist(Plist::from_values(args.iter().cloned())))
+}
+
+fn builtin_to_list(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Plist(plst)] => Ok(Value::list(plst.iter().collect())),
+        _ => Err(ShellError::Arity("plist/->list expects a single plist argument".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Value {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse(source).unwrap(), &env).unwrap()
+    }
+
+    #[test]
+    fn cons_and_car_round_trip() {
+        assert_eq!(run("(plist/car (plist/cons 1 (plist/nil)))").to_string(), "1");
+    }
+
+    #[test]
+    fn cdr_of_nil_is_nil() {
+        assert_eq!(run("(plist/->list (plist/cdr (plist/nil)))").to_string(), "()");
+    }
+
+    #[test]
+    fn car_of_empty_plist_is_an_error() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(&Parser::parse("(plist/car (plist/nil))").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn list_builds_in_order() {
+        assert_eq!(run("(plist/->list (plist/list 1 2 3))").to_string(), "(1 2 3)");
+    }
+
+    #[test]
+    fn cons_shares_structure_with_the_original() {
+        assert_eq!(
+            run("(plist/->list (plist/cdr (plist/cons 0 (plist/list 1 2))))").to_string(),
+            "(1 2)"
+        );
+    }
+
+    #[test]
+    fn null_check_distinguishes_empty_from_nonempty() {
+        assert_eq!(run("(plist/null? (plist/nil))").to_string(), "#t");
+        assert_eq!(run("(plist/null? (plist/list 1))").to_string(), "#f");
+    }
+}
@@ -0,0 +1,23 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![spec(
+        "load-plugin",
+        Arity::Exact(1),
+        "(load-plugin path): loads and evaluates a single plugin file into the calling environment.",
+        builtin_load_plugin,
+    )]
+}
+
+fn builtin_load_plugin(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(path)] => {
+            crate::plugin::load_plugin(&std::path::PathBuf::from(path), env)?;
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("load-plugin expects a path string".into())),
+    }
+}
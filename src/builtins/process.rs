@@ -0,0 +1,286 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+use std::io::{BufRead, Read};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "proc/run",
+            Arity::AtLeast(1),
+            "(proc/run program arg...): runs an external command to completion and returns its captured stdout. Set 'proc/timeout-ms to report a stalled command, and 'proc/kill-on-timeout to also kill it, via set-option.",
+            builtin_proc_run,
+        ),
+        spec(
+            "proc/run-lines",
+            Arity::AtLeast(1),
+            "(proc/run-lines program arg...): like proc/run, but returns a list of stdout lines (no trailing newlines) instead of one big string.",
+            builtin_proc_run_lines,
+        ),
+    ]
+}
+
+/// Splits `proc/run`'s argument list into the program name and its
+/// string arguments, shared by every builtin in this module that shells
+/// out to a command.
+fn program_and_args<'a>(args: &'a [Value], caller: &str) -> Result<(&'a str, Vec<&'a str>), ShellError> {
+    let (program, rest) = match args {
+        [Value::Str(program), rest @ ..] => (program.as_str(), rest),
+        _ => return Err(ShellError::Arity(format!("{caller} expects a command name string"))),
+    };
+    let mut command_args = Vec::with_capacity(rest.len());
+    for arg in rest {
+        match arg {
+            Value::Str(s) => command_args.push(s.as_str()),
+            other => {
+                return Err(ShellError::Eval(format!(
+                    "{caller} arguments must be strings, got {}",
+                    other.type_name()
+                )))
+            }
+        }
+    }
+    Ok((program, command_args))
+}
+
+/// Every executable name found directly inside a `PATH` directory (no
+/// recursion, no executable-bit check -- good enough for a suggestion,
+/// not for deciding what's actually runnable).
+fn path_executables() -> Vec<String> {
+    let path = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&path)
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect()
+}
+
+/// Turns a `program` that couldn't be spawned into a [`ShellError`],
+/// appending a "did you mean?" suggestion drawn from everything on `PATH`
+/// when `program` itself wasn't found there -- as opposed to, say, a
+/// permissions error, which gets [`std::io::Error`]'s own message instead
+/// since there's nothing to suggest.
+fn spawn_error(program: &str, err: std::io::Error) -> ShellError {
+    if err.kind() != std::io::ErrorKind::NotFound {
+        return err.into();
+    }
+    let candidates = path_executables();
+    match crate::suggest::suggest(program, candidates.iter().map(String::as_str)) {
+        Some(hint) => ShellError::Eval(format!("command not found: {program} (did you mean {hint}?)")),
+        None => ShellError::Eval(format!("command not found: {program}")),
+    }
+}
+
+/// Reads the `proc/timeout-ms` setting (see `set-option`) as a
+/// [`Duration`], or `None` if it's unset or not a positive integer --
+/// stall detection is opt-in, since most commands finish in milliseconds
+/// and shouldn't pay for a watchdog thread.
+fn stall_timeout() -> Option<Duration> {
+    match crate::config::get("proc/timeout-ms") {
+        Some(Value::Int(ms)) if ms > 0 => Some(Duration::from_millis(ms as u64)),
+        _ => None,
+    }
+}
+
+/// Reads the `proc/kill-on-timeout` setting as a bool, defaulting to
+/// `false` -- detecting a stall and killing the stalled command are
+/// separate opt-ins, so turning on detection alone never starts killing
+/// processes a script didn't ask to have killed.
+fn kill_on_timeout() -> bool {
+    matches!(crate::config::get("proc/kill-on-timeout"), Some(Value::Bool(true)))
+}
+
+/// Watches a child process for [`stall_timeout`] and, if it hasn't
+/// finished by then, prints a diagnostic to stderr naming the command and
+/// its pid, then kills it if [`kill_on_timeout`] says to. The caller must
+/// call [`StallWatchdog::finish`] once it's done waiting on the child, so
+/// a watchdog that wakes up after the command already exited is a no-op.
+struct StallWatchdog {
+    done: Arc<AtomicBool>,
+}
+
+impl StallWatchdog {
+    fn start(program: String, child: Arc<Mutex<std::process::Child>>, timeout: Duration) -> Self {
+        let done = Arc::new(AtomicBool::new(false));
+        let done_flag = done.clone();
+        // Settings are per-thread, so read this one here on the calling
+        // thread: the watchdog thread would only see its own empty registry.
+        let kill = kill_on_timeout();
+        std::thread::spawn(move || {
+            std::thread::sleep(timeout);
+            if done_flag.load(Ordering::SeqCst) {
+                return;
+            }
+            let mut child = child.lock().unwrap();
+            eprintln!(
+                "warning: {program} (pid {}) is still running after {timeout:?} -- it may be stalled waiting for input",
+                child.id()
+            );
+            if kill && child.kill().is_ok() {
+                eprintln!("warning: {program} (pid {}) was killed after stalling", child.id());
+            }
+        });
+        StallWatchdog { done }
+    }
+
+    fn finish(self) {
+        self.done.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Starts a [`StallWatchdog`] for `child` if `proc/timeout-ms` is
+/// configured, otherwise returns `None` -- the common case, so a call
+/// that never sets a timeout pays nothing beyond this check.
+fn watch_for_stall(program: &str, child: Arc<Mutex<std::process::Child>>) -> Option<StallWatchdog> {
+    stall_timeout().map(|timeout| StallWatchdog::start(program.to_string(), child, timeout))
+}
+
+/// `(proc/run "ls" "-la")`: runs an external command to completion and
+/// returns its captured stdout. A nonzero exit status is reported as an
+/// eval error rather than silently handing back partial output. If
+/// `proc/timeout-ms` is set, a [`StallWatchdog`] reports (and, if
+/// `proc/kill-on-timeout` is also set, kills) a command that's still
+/// running after that long.
+fn builtin_proc_run(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let (program, command_args) = program_and_args(&args, "proc/run")?;
+    #[cfg(feature = "tracing")]
+    let _span = crate::trace::process_span(program).entered();
+    let mut child = std::process::Command::new(program)
+        .args(&command_args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(program, e))?;
+    let mut stdout = child.stdout.take().expect("stdout was piped");
+    let child = Arc::new(Mutex::new(child));
+    let watchdog = watch_for_stall(program, child.clone());
+
+    let mut output = Vec::new();
+    stdout.read_to_end(&mut output)?;
+    if let Some(watchdog) = watchdog {
+        watchdog.finish();
+    }
+
+    let status = child.lock().unwrap().wait()?;
+    if !status.success() {
+        return Err(ShellError::Eval(format!("{program} exited with status {status}")));
+    }
+    Ok(Value::Str(String::from_utf8_lossy(&output).into_owned()))
+}
+
+/// `(proc/run-lines "ls" "-la")`: like `proc/run`, but reads the child's
+/// stdout line by line through a single reused byte buffer instead of
+/// buffering the whole output into one `String` before splitting it --
+/// the allocation-heavy part of turning bytes into lines (one `String`
+/// per line) is unavoidable as long as [`Value::Str`] owns a `String`
+/// rather than a [`std::rc::Rc<str>`] slice of a shared buffer, but
+/// reusing the read buffer itself avoids re-growing it on every line for
+/// inputs with millions of short lines (e.g. `wc -l`-scale logs).
+fn builtin_proc_run_lines(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let (program, command_args) = program_and_args(&args, "proc/run-lines")?;
+    #[cfg(feature = "tracing")]
+    let _span = crate::trace::process_span(program).entered();
+    let mut child = std::process::Command::new(program)
+        .args(&command_args)
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(program, e))?;
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let mut reader = std::io::BufReader::new(stdout);
+    let child = Arc::new(Mutex::new(child));
+    let watchdog = watch_for_stall(program, child.clone());
+
+    let mut lines = Vec::new();
+    let mut buf = Vec::new();
+    loop {
+        buf.clear();
+        let read = reader.read_until(b'\n', &mut buf)?;
+        if read == 0 {
+            break;
+        }
+        if buf.last() == Some(&b'\n') {
+            buf.pop();
+        }
+        lines.push(Value::Str(String::from_utf8_lossy(&buf).into_owned()));
+    }
+    if let Some(watchdog) = watchdog {
+        watchdog.finish();
+    }
+
+    let status = child.lock().unwrap().wait()?;
+    if !status.success() {
+        return Err(ShellError::Eval(format!("{program} exited with status {status}")));
+    }
+    Ok(Value::list(lines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    #[test]
+    fn missing_command_reports_not_found() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        crate::builtins::install_namespace(&env, "proc");
+        let err = eval(&Parser::parse("(proc/run \"this-command-does-not-exist\")").unwrap(), &env)
+            .unwrap_err();
+        assert!(err.to_string().contains("command not found: this-command-does-not-exist"));
+    }
+
+    #[test]
+    fn missing_command_suggests_a_close_match_on_path() {
+        let candidates = ["echo".to_string(), "pritnf".to_string()];
+        let message = match crate::suggest::suggest("printf", candidates.iter().map(String::as_str)) {
+            Some(hint) => format!("command not found: printf (did you mean {hint}?)"),
+            None => "command not found: printf".to_string(),
+        };
+        assert_eq!(message, "command not found: printf (did you mean pritnf?)");
+    }
+
+    #[test]
+    fn a_real_permission_or_other_error_is_not_rewritten_as_not_found() {
+        let err = spawn_error("bin", std::io::Error::new(std::io::ErrorKind::PermissionDenied, "denied"));
+        assert!(!err.to_string().contains("command not found"));
+        assert!(err.to_string().contains("denied"));
+    }
+
+    #[test]
+    fn stall_timeout_reads_a_positive_millisecond_count() {
+        crate::config::set("proc/timeout-ms".into(), Value::Int(250));
+        assert_eq!(stall_timeout(), Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn stall_timeout_ignores_a_non_positive_value() {
+        crate::config::set("proc/timeout-ms".into(), Value::Int(0));
+        assert_eq!(stall_timeout(), None);
+    }
+
+    #[test]
+    fn kill_on_timeout_reads_an_explicit_true() {
+        crate::config::set("proc/kill-on-timeout".into(), Value::Bool(true));
+        assert!(kill_on_timeout());
+    }
+
+    #[test]
+    fn kill_on_timeout_treats_anything_else_as_false() {
+        crate::config::set("proc/kill-on-timeout".into(), Value::Int(1));
+        assert!(!kill_on_timeout());
+    }
+
+    #[test]
+    fn a_quick_command_finishes_unaffected_once_a_timeout_is_configured() {
+        let env = Environment::new_global();
+        crate::config::set("proc/timeout-ms".into(), Value::Int(60_000));
+        let result = builtin_proc_run(vec![Value::Str("echo".into()), Value::Str("hi".into())], &env).unwrap();
+        assert!(matches!(result, Value::Str(ref s) if s == "hi\n"));
+    }
+}
@@ -0,0 +1,111 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "profile-enable!",
+            Arity::Exact(0),
+            "(profile-enable!): turns on evaluator profiling -- special form, builtin, and user function call counts.",
+            builtin_profile_enable,
+        ),
+        spec(
+            "profile-disable!",
+            Arity::Exact(0),
+            "(profile-disable!): turns off evaluator profiling. Counts recorded so far are kept.",
+            builtin_profile_disable,
+        ),
+        spec(
+            "profile-reset!",
+            Arity::Exact(0),
+            "(profile-reset!): discards every recorded profiling count without changing whether profiling is on.",
+            builtin_profile_reset,
+        ),
+        spec(
+            "profile-report",
+            Arity::Exact(0),
+            "(profile-report): a string breaking down special form, builtin, and user function call counts recorded since the last profile-reset!.",
+            builtin_profile_report,
+        ),
+    ]
+}
+
+fn builtin_profile_enable(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            crate::profile::enable();
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("profile-enable! expects no arguments".into())),
+    }
+}
+
+fn builtin_profile_disable(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            crate::profile::disable();
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("profile-disable! expects no arguments".into())),
+    }
+}
+
+fn builtin_profile_reset(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            crate::profile::reset();
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("profile-reset! expects no arguments".into())),
+    }
+}
+
+fn builtin_profile_report(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::Str(crate::profile::report())),
+        _ => Err(ShellError::Arity("profile-report expects no arguments".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleanup() {
+        crate::profile::disable();
+        crate::profile::reset();
+    }
+
+    #[test]
+    fn enable_disable_and_reset_take_no_arguments() {
+        cleanup();
+        assert!(builtin_profile_enable(vec![], &Environment::new_global()).is_ok());
+        assert!(crate::profile::is_enabled());
+        assert!(builtin_profile_disable(vec![], &Environment::new_global()).is_ok());
+        assert!(!crate::profile::is_enabled());
+        assert!(builtin_profile_reset(vec![], &Environment::new_global()).is_ok());
+        cleanup();
+    }
+
+    #[test]
+    fn report_reflects_recorded_calls() {
+        cleanup();
+        crate::profile::enable();
+        crate::profile::record_builtin_call("cons");
+        let result = builtin_profile_report(vec![], &Environment::new_global()).unwrap();
+        match result {
+            Value::Str(s) => assert!(s.contains("cons: 1")),
+            other => panic!("expected a string, got {other}"),
+        }
+        cleanup();
+    }
+
+    #[test]
+    fn extra_arguments_are_rejected() {
+        cleanup();
+        assert!(builtin_profile_enable(vec![Value::Int(1)], &Environment::new_global()).is_err());
+        cleanup();
+    }
+}
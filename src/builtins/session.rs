@@ -0,0 +1,216 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "save-session",
+            Arity::Exact(1),
+            "(save-session path): dumps every binding in the current frame to path as a reloadable script.",
+            builtin_save_session,
+        ),
+        spec(
+            "load-session",
+            Arity::Exact(1),
+            "(load-session path): evaluates every form in a file previously written by save-session.",
+            builtin_load_session,
+        ),
+        spec(
+            "persist-define",
+            Arity::Exact(2),
+            "(persist-define name value): defines name like `define`, and also appends it to the persisted definitions file the REPL replays on startup.",
+            builtin_persist_define,
+        ),
+    ]
+}
+
+/// Dumps every binding in the current frame as a `(define name value)`
+/// form, so `load-session` can later evaluate the file to restore them.
+/// Data values round-trip as themselves; user-defined functions round-trip
+/// as their `lambda` source, since that is all a `Value` can express.
+fn builtin_save_session(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let path = match args.as_slice() {
+        [Value::Str(path)] => path,
+        _ => return Err(ShellError::Arity("save-session expects a path string".into())),
+    };
+
+    std::fs::write(path, env.snapshot_defines())?;
+    Ok(Value::Nil)
+}
+
+fn builtin_load_session(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let path = match args.as_slice() {
+        [Value::Str(path)] => path,
+        _ => return Err(ShellError::Arity("load-session expects a path string".into())),
+    };
+
+    let source = std::fs::read_to_string(path)?;
+    for form in crate::parser::Parser::parse_all(&source)? {
+        crate::eval::eval(&form, env)?;
+    }
+    Ok(Value::Nil)
+}
+
+/// Defines `name` in the current environment exactly like `(define name
+/// value)`, and additionally appends a `(define name value)` form to the
+/// persisted definitions file (`~/.config/cracked/defs.lisp`), which the
+/// REPL replays on every startup alongside the rc file. Persistence is
+/// opt-in per binding -- an ordinary `define` is never written there, only
+/// one that is explicitly routed through `persist-define` -- so a function
+/// built interactively survives a restart without every scratch variable
+/// from the session piling up in the file too.
+fn builtin_persist_define(args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let (name, value) = match args.as_slice() {
+        [Value::Symbol(name), value] => (name, value),
+        _ => return Err(ShellError::Arity(
+            "persist-define expects a name and a value".into(),
+        )),
+    };
+
+    env.define_checked(name, value.clone())?;
+
+    #[cfg(feature = "repl")]
+    if let Some(path) = crate::repl::persisted_defs_path() {
+        append_persisted_define(&path, name, value)?;
+    }
+    Ok(value.clone())
+}
+
+#[cfg(feature = "repl")]
+fn append_persisted_define(path: &std::path::Path, name: &str, value: &Value) -> Result<(), ShellError> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    use std::io::Write;
+    writeln!(file, "(define {name} {value})")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod session_tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    #[test]
+    fn save_and_load_session_round_trips_bindings() {
+        let path = std::env::temp_dir().join("cracked_shell_session_test.lisp");
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse("(define pi 3)").unwrap(), &env).unwrap();
+        eval(
+            &Parser::parse(&format!("(save-session \"{}\")", path.display())).unwrap(),
+            &env,
+        )
+        .unwrap();
+
+        let fresh = Environment::new_global();
+        crate::builtins::install(&fresh);
+        eval(
+            &Parser::parse(&format!("(load-session \"{}\")", path.display())).unwrap(),
+            &fresh,
+        )
+        .unwrap();
+
+        assert!(matches!(fresh.get("pi"), Some(Value::Int(3))));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn save_and_load_session_round_trips_a_string_containing_a_quote() {
+        let path = std::env::temp_dir().join("cracked_shell_session_quote_test.lisp");
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse(r#"(define msg "say \"hi\"")"#).unwrap(), &env).unwrap();
+        eval(
+            &Parser::parse(&format!("(save-session \"{}\")", path.display())).unwrap(),
+            &env,
+        )
+        .unwrap();
+
+        let fresh = Environment::new_global();
+        crate::builtins::install(&fresh);
+        eval(
+            &Parser::parse(&format!("(load-session \"{}\")", path.display())).unwrap(),
+            &fresh,
+        )
+        .unwrap();
+
+        assert!(matches!(fresh.get("msg"), Some(Value::Str(s)) if s == "say \"hi\""));
+        std::fs::remove_file(&path).ok();
+    }
+}
+
+#[cfg(all(test, feature = "repl"))]
+mod persist_define_tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    #[test]
+    fn append_persisted_define_writes_a_reloadable_define_form() {
+        let path = std::env::temp_dir().join("cracked_shell_persist_define_test.lisp");
+        std::fs::remove_file(&path).ok();
+
+        append_persisted_define(&path, "answer", &Value::Int(42)).unwrap();
+        append_persisted_define(&path, "greeting", &Value::Str("hi".into())).unwrap();
+
+        let fresh = Environment::new_global();
+        crate::builtins::install(&fresh);
+        for form in Parser::parse_all(&std::fs::read_to_string(&path).unwrap()).unwrap() {
+            eval(&form, &fresh).unwrap();
+        }
+        assert!(matches!(fresh.get("answer"), Some(Value::Int(42))));
+        assert!(matches!(fresh.get("greeting"), Some(Value::Str(s)) if s == "hi"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn persist_define_defines_in_the_calling_environment() {
+        let path = crate::repl::persisted_defs_path().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse("(persist-define 'square (lambda (x) (* x x)))").unwrap(), &env).unwrap();
+        assert!(env.get("square").is_some());
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("(define square"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn persist_define_round_trips_a_string_containing_a_quote() {
+        let path = crate::repl::persisted_defs_path().unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(
+            &Parser::parse(r#"(persist-define 'msg "has \"quotes\"")"#).unwrap(),
+            &env,
+        )
+        .unwrap();
+        assert!(matches!(env.get("msg"), Some(Value::Str(s)) if s == "has \"quotes\""));
+
+        let fresh = Environment::new_global();
+        crate::builtins::install(&fresh);
+        for form in Parser::parse_all(&std::fs::read_to_string(&path).unwrap()).unwrap() {
+            eval(&form, &fresh).unwrap();
+        }
+        assert!(matches!(fresh.get("msg"), Some(Value::Str(s)) if s == "has \"quotes\""));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn persist_define_rejects_a_non_symbol_name() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        let result = eval(&Parser::parse("(persist-define \"square\" 1)").unwrap(), &env);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,248 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::rope::RopeBuilder;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "str/split",
+            Arity::Exact(2),
+            "(str/split sep s): splits s on every occurrence of the literal separator sep.",
+            builtin_str_split,
+        ),
+        spec(
+            "str/join",
+            Arity::Exact(2),
+            "(str/join sep lst): joins a list of strings with sep between each pair.",
+            builtin_str_join,
+        ),
+        spec(
+            "str/append",
+            Arity::Any,
+            "(str/append s...): concatenates any number of strings, in amortized O(total length).",
+            builtin_str_append,
+        ),
+        spec(
+            "str/format",
+            Arity::AtLeast(1),
+            "(str/format template v...): template with each {} replaced, in order, by the printed form of the next v.",
+            builtin_str_format,
+        ),
+        spec("str/upcase", Arity::Exact(1), "(str/upcase s): s converted to uppercase.", builtin_str_upcase),
+        spec(
+            "str/downcase",
+            Arity::Exact(1),
+            "(str/downcase s): s converted to lowercase.",
+            builtin_str_downcase,
+        ),
+        spec(
+            "shell-words",
+            Arity::Exact(1),
+            "(shell-words line): splits line into shell-style words, for commands typed as plain text rather than s-expressions.",
+            builtin_shell_words,
+        ),
+        spec(
+            "expand-braces",
+            Arity::Exact(1),
+            "(expand-braces pattern): expands shell-style {a,b,c} and {1..5} groups into the list of strings they denote.",
+            builtin_expand_braces,
+        ),
+        spec(
+            "format-code",
+            Arity::Exact(1),
+            "(format-code source): re-emits Cracked Shell source with canonical indentation, as used by the `cracked fmt` CLI subcommand.",
+            builtin_format_code,
+        ),
+    ]
+}
+
+/// `(str/split "," "a,b,c")`: splits `s` on every occurrence of the
+/// literal separator `sep`.
+fn builtin_str_split(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(sep), Value::Str(s)] => Ok(Value::list(
+            s.split(sep.as_str()).map(|part| Value::Str(part.to_string())).collect(),
+        )),
+        _ => Err(ShellError::Arity("str/split expects (separator string)".into())),
+    }
+}
+
+/// `(str/join "," '("a" "b" "c"))`: joins a list of strings with `sep`
+/// between each pair.
+fn builtin_str_join(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(sep), Value::List(items)] => {
+            let mut rope = RopeBuilder::new();
+            for (i, item) in items.iter().enumerate() {
+                match item {
+                    Value::Str(s) => {
+                        if i > 0 {
+                            rope.push(sep);
+                        }
+                        rope.push(s);
+                    }
+                    other => {
+                        return Err(ShellError::Eval(format!(
+                            "str/join expects a list of strings, got {}",
+                            other.type_name()
+                        )))
+                    }
+                }
+            }
+            Ok(Value::Str(rope.finish()))
+        }
+        _ => Err(ShellError::Arity("str/join expects (separator list)".into())),
+    }
+}
+
+/// `(str/append "a" "b" "c")`: concatenates any number of strings, in
+/// amortized O(total length) via [`RopeBuilder`] rather than the O(n^2)
+/// cost of repeatedly building a new string out of the ones seen so far.
+fn builtin_str_append(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let mut rope = RopeBuilder::new();
+    for arg in args {
+        match arg {
+            Value::Str(s) => rope.push(&s),
+            other => {
+                return Err(ShellError::Eval(format!(
+                    "str/append expects strings, got {}",
+                    other.type_name()
+                )))
+            }
+        }
+    }
+    Ok(Value::Str(rope.finish()))
+}
+
+/// `(str/format "{} is {} years old" name age)`: `template` with each
+/// `{}` replaced, in order, by the printed (`Display`) form of the next
+/// argument. Assembled with [`RopeBuilder`] so formatting a template with
+/// many placeholders stays linear in the output length.
+fn builtin_str_format(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    let (template, values) = match args.as_slice() {
+        [Value::Str(template), values @ ..] => (template, values),
+        _ => return Err(ShellError::Arity("str/format expects (template v...)".into())),
+    };
+
+    let mut rope = RopeBuilder::with_capacity(template.len());
+    let mut values = values.iter();
+    let mut rest = template.as_str();
+    while let Some(pos) = rest.find("{}") {
+        rope.push(&rest[..pos]);
+        let value = values
+            .next()
+            .ok_or_else(|| ShellError::Arity("str/format: not enough arguments for template".into()))?;
+        rope.push(&value.to_string());
+        rest = &rest[pos + 2..];
+    }
+    rope.push(rest);
+
+    if values.next().is_some() {
+        return Err(ShellError::Arity("str/format: too many arguments for template".into()));
+    }
+
+    Ok(Value::Str(rope.finish()))
+}
+
+fn builtin_str_upcase(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(s)] => Ok(Value::Str(s.to_uppercase())),
+        _ => Err(ShellError::Arity("str/upcase expects a string".into())),
+    }
+}
+
+fn builtin_str_downcase(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(s)] => Ok(Value::Str(s.to_lowercase())),
+        _ => Err(ShellError::Arity("str/downcase expects a string".into())),
+    }
+}
+
+/// `(shell-words "ls -la \"my file\"")`: splits a line into shell-style
+/// words, for commands typed as plain text rather than s-expressions.
+fn builtin_shell_words(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(line)] => {
+            let words = crate::shellwords::split(line)?;
+            Ok(Value::list(words.into_iter().map(Value::Str).collect()))
+        }
+        _ => Err(ShellError::Arity("shell-words expects a string".into())),
+    }
+}
+
+/// `(format-code s)`: re-emits Cracked Shell source `s` with canonical
+/// indentation, as used by the `cracked fmt` CLI subcommand.
+fn builtin_format_code(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(source)] => Ok(Value::Str(crate::fmt::format_code(source)?)),
+        _ => Err(ShellError::Arity("format-code expects a source string".into())),
+    }
+}
+
+/// `(expand-braces "file-{1..3}.txt")`: expands shell-style `{a,b,c}` and
+/// `{1..5}` groups into the list of strings they denote, for splatting
+/// into a command's argument list.
+fn builtin_expand_braces(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(pattern)] => {
+            let expanded = crate::brace::expand(pattern)?;
+            Ok(Value::list(expanded.into_iter().map(Value::Str).collect()))
+        }
+        _ => Err(ShellError::Arity("expand-braces expects a string".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn append_concatenates_in_order() {
+        let result = builtin_str_append(
+            vec![Value::Str("a".into()), Value::Str("b".into()), Value::Str("c".into())],
+            &Environment::new_global(),
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "\"abc\"");
+    }
+
+    #[test]
+    fn append_with_no_arguments_is_empty() {
+        let result = builtin_str_append(vec![], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "\"\"");
+    }
+
+    #[test]
+    fn format_substitutes_each_placeholder_in_order() {
+        let result = builtin_str_format(
+            vec![Value::Str("{} is {}".into()), Value::Int(2), Value::Int(1)],
+            &Environment::new_global(),
+        )
+        .unwrap();
+        assert_eq!(result.to_string(), "\"2 is 1\"");
+    }
+
+    #[test]
+    fn format_rejects_too_few_arguments() {
+        let result = builtin_str_format(vec![Value::Str("{} {}".into()), Value::Int(1)], &Environment::new_global());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn format_rejects_too_many_arguments() {
+        let result = builtin_str_format(
+            vec![Value::Str("{}".into()), Value::Int(1), Value::Int(2)],
+            &Environment::new_global(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn join_still_joins_with_the_separator() {
+        let list = Value::list(vec![Value::Str("a".into()), Value::Str("b".into())]);
+        let result = builtin_str_join(vec![Value::Str(",".into()), list], &Environment::new_global()).unwrap();
+        assert_eq!(result.to_string(), "\"a,b\"");
+    }
+}
@@ -0,0 +1,106 @@
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "set-edit-mode",
+            Arity::Exact(1),
+            "(set-edit-mode 'vi) or (set-edit-mode 'emacs): selects the line editor's key bindings.",
+            builtin_set_edit_mode,
+        ),
+        spec(
+            "bind-key",
+            Arity::Exact(2),
+            "(bind-key key-name action): binds a key name to a Lisp expression, evaluated unquoted once the key is pressed.",
+            builtin_bind_key,
+        ),
+        spec("terminal-width", Arity::Exact(0), "(terminal-width): the terminal's width in columns.", builtin_terminal_width),
+        spec("terminal-height", Arity::Exact(0), "(terminal-height): the terminal's height in rows.", builtin_terminal_height),
+        spec(
+            "isatty?",
+            Arity::Exact(1),
+            "(isatty? 'stdin) or (isatty? 'stdout): true if that stream is connected to a terminal.",
+            builtin_isatty,
+        ),
+        spec(
+            "color",
+            Arity::Exact(2),
+            "(color 'red text): wraps text in the named ANSI color escape.",
+            builtin_color,
+        ),
+        spec("clear-screen", Arity::Exact(0), "(clear-screen): clears the terminal.", builtin_clear_screen),
+    ]
+}
+
+/// `(set-edit-mode 'vi)` or `(set-edit-mode 'emacs)`.
+fn builtin_set_edit_mode(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(mode)] if mode == "vi" => {
+            crate::keymap::set_mode(crate::keymap::EditMode::Vi);
+            Ok(Value::Nil)
+        }
+        [Value::Symbol(mode)] if mode == "emacs" => {
+            crate::keymap::set_mode(crate::keymap::EditMode::Emacs);
+            Ok(Value::Nil)
+        }
+        [Value::Symbol(other)] => Err(ShellError::Eval(format!("unknown edit mode: {other}"))),
+        _ => Err(ShellError::Arity("set-edit-mode expects 'vi or 'emacs".into())),
+    }
+}
+
+/// `(bind-key "F5" '(run "cargo" "test"))`: binds a key name to a Lisp
+/// expression, evaluated unquoted once the key is pressed.
+fn builtin_bind_key(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Str(key), action] => {
+            crate::keymap::bind(key.clone(), action.clone());
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("bind-key expects (key-name action)".into())),
+    }
+}
+
+fn builtin_terminal_width(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::Int(crate::terminal::width() as i64)),
+        _ => Err(ShellError::Arity("terminal-width expects no arguments".into())),
+    }
+}
+
+fn builtin_terminal_height(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => Ok(Value::Int(crate::terminal::height() as i64)),
+        _ => Err(ShellError::Arity("terminal-height expects no arguments".into())),
+    }
+}
+
+/// `(isatty? 'stdin)` or `(isatty? 'stdout)`.
+fn builtin_isatty(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(port)] if port == "stdin" => Ok(Value::Bool(crate::terminal::stdin_is_tty())),
+        [Value::Symbol(port)] if port == "stdout" => Ok(Value::Bool(crate::terminal::stdout_is_tty())),
+        [Value::Symbol(other)] => Err(ShellError::Eval(format!("unknown port: {other}"))),
+        _ => Err(ShellError::Arity("isatty? expects 'stdin or 'stdout".into())),
+    }
+}
+
+/// `(color 'red "text")`: wraps `text` in the named ANSI color escape.
+fn builtin_color(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Symbol(name), Value::Str(text)] => Ok(Value::Str(crate::terminal::color(name, text))),
+        _ => Err(ShellError::Arity("color expects (color 'name string)".into())),
+    }
+}
+
+fn builtin_clear_screen(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [] => {
+            crate::terminal::clear_screen();
+            Ok(Value::Nil)
+        }
+        _ => Err(ShellError::Arity("clear-screen expects no arguments".into())),
+    }
+}
@@ -0,0 +1,148 @@
+use super::math::as_f64;
+use super::{spec, Arity, BuiltinSpec};
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::eval::apply;
+use crate::value::Value;
+
+pub fn specs() -> Vec<BuiltinSpec> {
+    vec![
+        spec(
+            "vector-from-list",
+            Arity::Exact(1),
+            "(vector-from-list lst): lst, a list of numbers, packed into a contiguous numeric vector.",
+            builtin_vector_from_list,
+        ),
+        spec(
+            "vector->list",
+            Arity::Exact(1),
+            "(vector->list v): v converted back to an ordinary list of floats.",
+            builtin_vector_to_list,
+        ),
+        spec(
+            "vector-map",
+            Arity::AtLeast(2),
+            "(vector-map f v...): f applied elementwise across one or more same-length vectors, returning a new vector.",
+            builtin_vector_map,
+        ),
+        spec(
+            "vector-sum",
+            Arity::Exact(1),
+            "(vector-sum v): the sum of every element of v.",
+            builtin_vector_sum,
+        ),
+    ]
+}
+
+fn builtin_vector_from_list(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::List(items)] => {
+            let buf = items.iter().map(as_f64).collect::<Result<Vec<_>, _>>()?;
+            Ok(Value::vector(buf))
+        }
+        _ => Err(ShellError::Arity("vector-from-list expects a single list argument".into())),
+    }
+}
+
+fn builtin_vector_to_list(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Vector(items)] => Ok(Value::list(items.iter().map(|n| Value::Float(*n)).collect())),
+        _ => Err(ShellError::Arity("vector->list expects a single vector argument".into())),
+    }
+}
+
+/// `(vector-map f v1 v2 ...)`: calls `f` once per index with the
+/// corresponding element of every vector, the way Scheme's `vector-map`
+/// does -- `(vector-map + v1 v2)` adds `v1` and `v2` elementwise. `f` can
+/// be any callable `Value`, so each call still goes through [`apply`]
+/// rather than a tight scalar loop; the fast path this buys over a plain
+/// list is avoiding a `Value::List`/`Rc<Value>` allocation for the
+/// vectors themselves, not for each individual call to `f`.
+fn builtin_vector_map(mut args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    let f = args.remove(0);
+    let vectors = args
+        .iter()
+        .map(|v| match v {
+            Value::Vector(items) => Ok(items.clone()),
+            other => Err(ShellError::Eval(format!(
+                "vector-map expects vector arguments, got {}",
+                other.type_name()
+            ))),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let len = vectors[0].len();
+    if vectors.iter().any(|v| v.len() != len) {
+        return Err(ShellError::Eval("vector-map expects vectors of the same length".into()));
+    }
+
+    let mut result = Vec::with_capacity(len);
+    for i in 0..len {
+        let call_args = vectors.iter().map(|v| Value::Float(v[i])).collect();
+        result.push(as_f64(&apply(&f, call_args, env)?)?);
+    }
+    Ok(Value::vector(result))
+}
+
+fn builtin_vector_sum(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    match args.as_slice() {
+        [Value::Vector(items)] => Ok(Value::Float(items.iter().sum())),
+        _ => Err(ShellError::Arity("vector-sum expects a single vector argument".into())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::eval;
+    use crate::parser::Parser;
+
+    fn run(source: &str) -> Value {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        eval(&Parser::parse(source).unwrap(), &env).unwrap()
+    }
+
+    #[test]
+    fn from_list_and_back_round_trips() {
+        assert_eq!(
+            run("(vector->list (vector-from-list (list 1 2 3)))").to_string(),
+            "(1 2 3)"
+        );
+    }
+
+    #[test]
+    fn sum_adds_every_element() {
+        assert_eq!(run("(vector-sum (vector-from-list (list 1 2 3 4)))").to_string(), "10");
+    }
+
+    #[test]
+    fn map_applies_elementwise() {
+        assert_eq!(
+            run(
+                "(vector->list (vector-map + (vector-from-list (list 1 2 3)) (vector-from-list (list 10 20 30))))"
+            )
+            .to_string(),
+            "(11 22 33)"
+        );
+    }
+
+    #[test]
+    fn map_rejects_mismatched_lengths() {
+        let env = Environment::new_global();
+        crate::builtins::install(&env);
+        assert!(eval(
+            &Parser::parse(
+                "(vector-map + (vector-from-list (list 1 2)) (vector-from-list (list 1 2 3)))"
+            )
+            .unwrap(),
+            &env
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn vectors_print_with_a_leading_hash() {
+        assert_eq!(run("(vector-from-list (list 1 2 3))").to_string(), "#(1 2 3)");
+    }
+}
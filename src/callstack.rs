@@ -0,0 +1,145 @@
+use std::cell::RefCell;
+
+// The user function calls currently in progress, for backtraces when an
+// evaluation fails.
+//
+// Unlike `crate::profile`'s counters, this is always on: `push` costs one
+// `String` clone per call, the same as `record_function_call`'s
+// record-keeping, and a backtrace is only useful if it was being kept
+// *before* the error happened.
+//
+// `push`/`pop` are meant to bracket one `crate::eval::apply` call each,
+// called from `crate::eval::eval_list` the same way
+// `crate::profile::record_function_call` is. A frame is popped only on
+// success; an error leaves every frame on the way down still on the
+// stack, so `snapshot` -- read right after the error propagates to a
+// top-level caller -- shows exactly the calls that were in progress when
+// it happened, innermost first. Callers at that top-level boundary
+// (`crate::repl::run`, `crate::repl::eval_source_and_print`, ...) call
+// `clear` before evaluating the next form so a past failure's frames
+// don't bleed into later backtraces.
+//
+// This only tracks which function was being called, not *where* in its
+// body -- the parsed `crate::value::Value` tree `crate::eval::eval` walks
+// carries no source span (spans exist only on the separate
+// `crate::ast::parse_str_spanned` path used by external tooling), so
+// there's no call-site location to attach here without a larger change to
+// how forms are parsed and evaluated.
+thread_local! {
+    static STACK: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn push(name: &str) {
+    STACK.with(|s| s.borrow_mut().push(name.to_string()));
+}
+
+pub fn pop() {
+    STACK.with(|s| {
+        s.borrow_mut().pop();
+    });
+}
+
+/// The names of every call currently on the stack, innermost (most
+/// recently entered) first.
+pub fn snapshot() -> Vec<String> {
+    STACK.with(|s| s.borrow().iter().rev().cloned().collect())
+}
+
+pub fn clear() {
+    STACK.with(|s| s.borrow_mut().clear());
+}
+
+/// The number of frames currently on the stack. Paired with [`truncate`] so
+/// `catch` can restore the stack to how it looked before the expression it
+/// ran failed, rather than leaving frames behind for an error it already
+/// handled.
+pub fn depth() -> usize {
+    STACK.with(|s| s.borrow().len())
+}
+
+/// Drops every frame past `depth`, the way [`pop`] would if called
+/// repeatedly -- but without assuming the caller knows how many frames
+/// that is.
+pub fn truncate(depth: usize) {
+    STACK.with(|s| s.borrow_mut().truncate(depth));
+}
+
+/// `snapshot()` rendered as `,help`/error output wants it: one frame per
+/// line, indented to suggest nesting depth, or an empty string when
+/// nothing was in progress.
+pub fn render() -> String {
+    let frames = snapshot();
+    let mut out = String::new();
+    for (depth, frame) in frames.iter().enumerate() {
+        out.push_str(&"  ".repeat(depth));
+        out.push_str("in ");
+        out.push_str(frame);
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleanup() {
+        clear();
+    }
+
+    #[test]
+    fn empty_by_default() {
+        cleanup();
+        assert!(snapshot().is_empty());
+        cleanup();
+    }
+
+    #[test]
+    fn push_adds_innermost_first() {
+        cleanup();
+        push("outer");
+        push("inner");
+        assert_eq!(snapshot(), vec!["inner", "outer"]);
+        cleanup();
+    }
+
+    #[test]
+    fn pop_removes_the_most_recent_frame() {
+        cleanup();
+        push("outer");
+        push("inner");
+        pop();
+        assert_eq!(snapshot(), vec!["outer"]);
+        cleanup();
+    }
+
+    #[test]
+    fn clear_empties_the_stack() {
+        cleanup();
+        push("a");
+        push("b");
+        clear();
+        assert!(snapshot().is_empty());
+    }
+
+    #[test]
+    fn render_indents_by_depth() {
+        cleanup();
+        push("outer");
+        push("inner");
+        assert_eq!(render(), "in inner\n  in outer\n");
+        cleanup();
+    }
+
+    #[test]
+    fn truncate_drops_frames_past_the_given_depth() {
+        cleanup();
+        push("outer");
+        let saved = depth();
+        push("inner");
+        push("innermost");
+        truncate(saved);
+        assert_eq!(snapshot(), vec!["outer"]);
+        cleanup();
+    }
+}
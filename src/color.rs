@@ -0,0 +1,84 @@
+use crate::value::Value;
+
+/// Maximum number of list elements shown before eliding the rest.
+pub const MAX_ELEMENTS: usize = 100;
+
+const GREEN: &str = "\x1b[32m";
+const CYAN: &str = "\x1b[36m";
+const RED: &str = "\x1b[31m";
+const RESET: &str = "\x1b[0m";
+
+/// Replaces the tail of an over-long top-level list with an elision
+/// marker, unless `full` is requested.
+pub fn truncate(value: &Value, full: bool) -> Value {
+    match value {
+        Value::List(items) if !full && items.len() > MAX_ELEMENTS => {
+            let mut shown: Vec<Value> = items[..MAX_ELEMENTS].to_vec();
+            shown.push(Value::Symbol(format!(
+                "...{}more",
+                items.len() - MAX_ELEMENTS
+            )));
+            Value::list(shown)
+        }
+        other => other.clone(),
+    }
+}
+
+/// Wraps `text` (the already-rendered form of `value`) in an ANSI color
+/// chosen by `value`'s type, when `colorize` is enabled.
+pub fn colorize_value(value: &Value, text: &str, colorize: bool) -> String {
+    if !colorize {
+        return text.to_string();
+    }
+    match value {
+        Value::Str(_) => format!("{GREEN}{text}{RESET}"),
+        Value::Int(_) | Value::Float(_) => format!("{CYAN}{text}{RESET}"),
+        _ => text.to_string(),
+    }
+}
+
+/// Renders an error message in red when `colorize` is enabled.
+pub fn render_error(message: &str, colorize: bool) -> String {
+    if colorize {
+        format!("{RED}{message}{RESET}")
+    } else {
+        message.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_long_lists() {
+        let items: Vec<Value> = (0..150).map(Value::Int).collect();
+        let result = truncate(&Value::list(items), false);
+        match result {
+            Value::List(items) => assert_eq!(items.len(), MAX_ELEMENTS + 1),
+            _ => panic!("expected a list"),
+        }
+    }
+
+    #[test]
+    fn full_disables_truncation() {
+        let items: Vec<Value> = (0..150).map(Value::Int).collect();
+        let result = truncate(&Value::list(items), true);
+        match result {
+            Value::List(items) => assert_eq!(items.len(), 150),
+            _ => panic!("expected a list"),
+        }
+    }
+
+    #[test]
+    fn colorizes_strings() {
+        let out = colorize_value(&Value::Str("hi".into()), "\"hi\"", true);
+        assert!(out.contains(GREEN));
+    }
+
+    #[test]
+    fn skips_color_when_disabled() {
+        let out = colorize_value(&Value::Str("hi".into()), "\"hi\"", false);
+        assert_eq!(out, "\"hi\"");
+    }
+}
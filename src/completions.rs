@@ -0,0 +1,199 @@
+//! Shell completion-script generation, shared by the `cracked completions`
+//! subcommand (for the `cracked` binary's own flags and subcommands) and
+//! the `register-completion`/`completions` builtins (for a script
+//! embedding this interpreter that wants the same thing for its own
+//! command line).
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+/// One completable command: its name, the flags it accepts, and the
+/// subcommands nested under it, if any. `cracked fmt`/`cracked lint`/
+/// `cracked translate`/`cracked completions`/`cracked learn` are each a
+/// bare subcommand with no flags of their own; `cracked` itself is the
+/// root, with both flags (`--no-rc`, ...) and those five subcommands.
+#[derive(Debug, Clone, Default)]
+pub struct CommandSpec {
+    pub name: String,
+    pub flags: Vec<String>,
+    pub subcommands: Vec<String>,
+}
+
+impl CommandSpec {
+    pub fn new(name: impl Into<String>) -> Self {
+        CommandSpec { name: name.into(), ..Default::default() }
+    }
+
+    pub fn flag(mut self, flag: impl Into<String>) -> Self {
+        self.flags.push(flag.into());
+        self
+    }
+
+    pub fn subcommand(mut self, name: impl Into<String>) -> Self {
+        self.subcommands.push(name.into());
+        self
+    }
+}
+
+/// The `cracked` binary's own completable surface -- kept here rather
+/// than generated from [`std::env::args`] parsing in `main.rs`, so it
+/// stays in sync by inspection rather than by running the binary.
+pub fn cracked_spec() -> CommandSpec {
+    CommandSpec::new("cracked")
+        .flag("--no-rc")
+        .flag("--strict")
+        .flag("--no-plugins")
+        .flag("--rc")
+        .flag("--crash-report")
+        .flag("-e")
+        .flag("-c")
+        .subcommand("fmt")
+        .subcommand("lint")
+        .subcommand("translate")
+        .subcommand("completions")
+        .subcommand("learn")
+}
+
+/// A shell `cracked completions` (or `(completions ...)`) can target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    pub fn parse(name: &str) -> Option<Shell> {
+        match name {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+}
+
+/// Renders a completion script for `spec` targeting `shell`. Only
+/// completes flags and immediate subcommands -- none of the three
+/// generated formats attempt subcommand-specific flag completion, since
+/// [`CommandSpec`] doesn't model that nesting.
+pub fn generate(shell: Shell, spec: &CommandSpec) -> String {
+    match shell {
+        Shell::Bash => bash_script(spec),
+        Shell::Zsh => zsh_script(spec),
+        Shell::Fish => fish_script(spec),
+    }
+}
+
+fn words(spec: &CommandSpec) -> String {
+    spec.flags.iter().chain(spec.subcommands.iter()).cloned().collect::<Vec<_>>().join(" ")
+}
+
+fn bash_script(spec: &CommandSpec) -> String {
+    let name = &spec.name;
+    let words = words(spec);
+    format!(
+        "_{name}_completions() {{\n    COMPREPLY=($(compgen -W \"{words}\" -- \"${{COMP_WORDS[COMP_CWORD]}}\"))\n}}\ncomplete -F _{name}_completions {name}\n"
+    )
+}
+
+fn zsh_script(spec: &CommandSpec) -> String {
+    let name = &spec.name;
+    let mut out = format!("#compdef {name}\n\n_{name}() {{\n    local -a words\n    words=(\n");
+    for word in spec.flags.iter().chain(spec.subcommands.iter()) {
+        out.push_str("        '");
+        out.push_str(word);
+        out.push_str("'\n");
+    }
+    out.push_str("    )\n    _describe 'command' words\n}\n\n_");
+    out.push_str(name);
+    out.push('\n');
+    out
+}
+
+fn fish_script(spec: &CommandSpec) -> String {
+    let name = &spec.name;
+    let mut out = String::new();
+    for flag in &spec.flags {
+        out.push_str(&format!("complete -c {name} -l '{}'\n", flag.trim_start_matches('-')));
+    }
+    for subcommand in &spec.subcommands {
+        out.push_str(&format!("complete -c {name} -a '{subcommand}'\n"));
+    }
+    out
+}
+
+thread_local! {
+    static REGISTRY: RefCell<BTreeMap<String, CommandSpec>> = const { RefCell::new(BTreeMap::new()) };
+}
+
+/// Registers (or replaces) the completable surface of `spec.name`, for
+/// [`completion_script`] to later render. This is the hook
+/// `(register-completion ...)` exposes: a script embedding this
+/// interpreter as a library can describe its own command's flags and
+/// subcommands without this crate knowing anything about it.
+pub fn register(spec: CommandSpec) {
+    REGISTRY.with(|r| r.borrow_mut().insert(spec.name.clone(), spec));
+}
+
+/// The completion script for a previously [`register`]ed command, or
+/// `None` if nothing is registered under that name.
+pub fn completion_script(shell: Shell, name: &str) -> Option<String> {
+    REGISTRY.with(|r| r.borrow().get(name).map(|spec| generate(shell, spec)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CommandSpec {
+        CommandSpec::new("mytool").flag("--verbose").subcommand("build")
+    }
+
+    #[test]
+    fn bash_script_completes_flags_and_subcommands() {
+        let script = generate(Shell::Bash, &sample());
+        assert!(script.contains("complete -F _mytool_completions mytool"));
+        assert!(script.contains("--verbose build"));
+    }
+
+    #[test]
+    fn zsh_script_lists_every_word() {
+        let script = generate(Shell::Zsh, &sample());
+        assert!(script.contains("#compdef mytool"));
+        assert!(script.contains("'--verbose'"));
+        assert!(script.contains("'build'"));
+    }
+
+    #[test]
+    fn fish_script_separates_flags_from_subcommands() {
+        let script = generate(Shell::Fish, &sample());
+        assert!(script.contains("complete -c mytool -l 'verbose'"));
+        assert!(script.contains("complete -c mytool -a 'build'"));
+    }
+
+    #[test]
+    fn shell_parse_rejects_unknown_names() {
+        assert_eq!(Shell::parse("bash"), Some(Shell::Bash));
+        assert_eq!(Shell::parse("powershell"), None);
+    }
+
+    #[test]
+    fn cracked_spec_lists_its_own_subcommands() {
+        let spec = cracked_spec();
+        assert!(spec.subcommands.contains(&"translate".to_string()));
+        assert!(spec.flags.contains(&"--no-rc".to_string()));
+    }
+
+    #[test]
+    fn register_then_completion_script_round_trips() {
+        register(CommandSpec::new("roundtrip_test_tool").flag("--dry-run"));
+        let script = completion_script(Shell::Fish, "roundtrip_test_tool").unwrap();
+        assert!(script.contains("complete -c roundtrip_test_tool -l 'dry-run'"));
+    }
+
+    #[test]
+    fn completion_script_is_none_for_an_unregistered_name() {
+        assert!(completion_script(Shell::Bash, "no_such_tool_registered").is_none());
+    }
+}
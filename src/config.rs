@@ -0,0 +1,52 @@
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Per-thread settings registry, read and written by the
+/// `set-option`/`get-option`/`options` builtins.
+#[derive(Default)]
+pub struct Settings {
+    options: HashMap<String, Value>,
+}
+
+thread_local! {
+    static SETTINGS: RefCell<Settings> = RefCell::new(Settings::default());
+}
+
+pub fn set(name: String, value: Value) {
+    SETTINGS.with(|settings| {
+        settings.borrow_mut().options.insert(name, value);
+    });
+}
+
+pub fn get(name: &str) -> Option<Value> {
+    SETTINGS.with(|settings| settings.borrow().options.get(name).cloned())
+}
+
+/// Returns all configured option names, sorted.
+pub fn names() -> Vec<String> {
+    SETTINGS.with(|settings| {
+        let mut names: Vec<String> = settings.borrow().options.keys().cloned().collect();
+        names.sort();
+        names
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_option_is_none() {
+        assert!(get("cracked_shell_config_test_missing").is_none());
+    }
+
+    #[test]
+    fn set_then_get_round_trips() {
+        set("cracked_shell_config_test_round_trip".into(), Value::Int(7));
+        assert!(matches!(
+            get("cracked_shell_config_test_round_trip"),
+            Some(Value::Int(7))
+        ));
+    }
+}
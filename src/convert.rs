@@ -0,0 +1,163 @@
+//! `From`/`TryFrom` between [`Value`] and common Rust container types,
+//! for embedders who'd rather write `Vec::try_from(&value)?` than reach
+//! for [`crate::native::FromValue`] directly. The scalar impls here just
+//! delegate to [`crate::native::IntoValue`]/[`crate::native::FromValue`]
+//! rather than re-deriving the same match arms a second time.
+use crate::error::ShellError;
+use crate::native::{FromValue, IntoValue};
+use crate::value::Value;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+macro_rules! impl_from_for_value {
+    ($ty:ty) => {
+        impl From<$ty> for Value {
+            fn from(v: $ty) -> Value {
+                IntoValue::into_value(v)
+            }
+        }
+    };
+}
+
+impl_from_for_value!(i64);
+impl_from_for_value!(f64);
+impl_from_for_value!(bool);
+impl_from_for_value!(char);
+impl_from_for_value!(String);
+
+macro_rules! impl_try_from_for_scalar {
+    ($ty:ty) => {
+        impl TryFrom<&Value> for $ty {
+            type Error = ShellError;
+            fn try_from(v: &Value) -> Result<$ty, ShellError> {
+                FromValue::from_value(v)
+            }
+        }
+    };
+}
+
+impl_try_from_for_scalar!(i64);
+impl_try_from_for_scalar!(f64);
+impl_try_from_for_scalar!(bool);
+impl_try_from_for_scalar!(char);
+impl_try_from_for_scalar!(String);
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Value {
+        Value::list(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<'a, T> TryFrom<&'a Value> for Vec<T>
+where
+    T: TryFrom<&'a Value, Error = ShellError>,
+{
+    type Error = ShellError;
+    fn try_from(value: &'a Value) -> Result<Vec<T>, ShellError> {
+        match value {
+            Value::List(items) => items.iter().map(T::try_from).collect(),
+            other => Err(ShellError::Eval(format!("expected a list, got {}", other.type_name()))),
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(opt: Option<T>) -> Value {
+        match opt {
+            Some(v) => v.into(),
+            None => Value::Nil,
+        }
+    }
+}
+
+impl<'a, T> TryFrom<&'a Value> for Option<T>
+where
+    T: TryFrom<&'a Value, Error = ShellError>,
+{
+    type Error = ShellError;
+    fn try_from(value: &'a Value) -> Result<Option<T>, ShellError> {
+        match value {
+            Value::Nil => Ok(None),
+            other => T::try_from(other).map(Some),
+        }
+    }
+}
+
+/// A Rust `HashMap` round-trips as an association list of `(symbol .
+/// value)` pairs -- the same shape [`crate::builtins::features::builtin_features`]
+/// already returns -- rather than introducing a dedicated map [`Value`]
+/// variant just for this conversion.
+impl<T: Into<Value>> From<HashMap<String, T>> for Value {
+    fn from(map: HashMap<String, T>) -> Value {
+        Value::list(
+            map.into_iter()
+                .map(|(k, v)| Value::dotted(vec![Value::Symbol(k)], v.into()))
+                .collect(),
+        )
+    }
+}
+
+impl<'a, T> TryFrom<&'a Value> for HashMap<String, T>
+where
+    T: TryFrom<&'a Value, Error = ShellError>,
+{
+    type Error = ShellError;
+    fn try_from(value: &'a Value) -> Result<HashMap<String, T>, ShellError> {
+        match value {
+            Value::List(items) => items
+                .iter()
+                .map(|pair| match pair {
+                    Value::DottedList(key_items, tail) => match key_items.as_slice() {
+                        [Value::Symbol(key)] => Ok((key.clone(), T::try_from(tail)?)),
+                        _ => Err(ShellError::Eval("expected a (key . value) pair".into())),
+                    },
+                    other => Err(ShellError::Eval(format!(
+                        "expected a (key . value) pair, got {}",
+                        other.type_name()
+                    ))),
+                })
+                .collect(),
+            other => Err(ShellError::Eval(format!("expected an association list, got {}", other.type_name()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_from_round_trips_through_try_from() {
+        let value: Value = 42i64.into();
+        assert_eq!(i64::try_from(&value).unwrap(), 42);
+    }
+
+    #[test]
+    fn vec_round_trips() {
+        let value: Value = vec![1i64, 2, 3].into();
+        assert_eq!(Vec::<i64>::try_from(&value).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn option_some_and_none_round_trip() {
+        let some: Value = Some(5i64).into();
+        assert_eq!(Option::<i64>::try_from(&some).unwrap(), Some(5));
+
+        let none: Value = Option::<i64>::None.into();
+        assert_eq!(Option::<i64>::try_from(&none).unwrap(), None);
+    }
+
+    #[test]
+    fn hashmap_round_trips() {
+        let mut map = HashMap::new();
+        map.insert("a".to_string(), 1i64);
+        let value: Value = map.clone().into();
+        assert_eq!(HashMap::<String, i64>::try_from(&value).unwrap(), map);
+    }
+
+    #[test]
+    fn wrong_shape_is_a_type_error() {
+        assert!(i64::try_from(&Value::Str("nope".into())).is_err());
+        assert!(Vec::<i64>::try_from(&Value::Int(1)).is_err());
+    }
+}
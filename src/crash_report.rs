@@ -0,0 +1,97 @@
+//! The `--crash-report FILE` report written when a script fails in
+//! non-interactive mode (see [`crate::repl::eval_source_and_print`]):
+//! the error, the call stack captured at the time, a tail of recently
+//! evaluated top-level forms, and a data-only snapshot of the top-level
+//! environment -- enough for a bug report without asking the reporter to
+//! reconstruct what they ran.
+use crate::ast::Sexpr;
+use crate::env::Environment;
+use crate::error::ShellError;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+
+/// How many of the most recently evaluated top-level forms to keep around
+/// in case the next one crashes -- enough to show what led up to a
+/// failure without the report growing unbounded on a long-running script.
+pub const RECENT_FORMS_LIMIT: usize = 20;
+
+#[derive(Debug, Serialize)]
+pub struct CrashReport {
+    pub error: String,
+    pub location: String,
+    pub backtrace: Vec<String>,
+    pub recent_forms: Vec<String>,
+    /// Every top-level binding whose value has a plain data
+    /// representation -- a `Builtin`, `Native`, `Lambda`, `Future`, `Memo`,
+    /// `Plist`, `Vector`, or `Error` value is left out, the same exclusions
+    /// [`Sexpr::from_value`] already makes for [`Environment::export`].
+    pub environment: BTreeMap<String, Sexpr>,
+}
+
+impl CrashReport {
+    pub fn capture(error: &ShellError, location: String, recent_forms: &[String], env: &Environment) -> CrashReport {
+        let mut environment = BTreeMap::new();
+        for name in env.local_names() {
+            if let Some(sexpr) = env.get(&name).and_then(|value| Sexpr::from_value(&value)) {
+                environment.insert(name, sexpr);
+            }
+        }
+        CrashReport {
+            error: error.to_string(),
+            location,
+            backtrace: crate::callstack::snapshot(),
+            recent_forms: recent_forms.to_vec(),
+            environment,
+        }
+    }
+
+    pub fn write_to(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("CrashReport always serializes");
+        std::fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::value::Value;
+
+    #[test]
+    fn captures_the_error_message_and_location() {
+        let env = Environment::new_global();
+        let report = CrashReport::capture(&ShellError::Eval("boom".into()), "1:1".into(), &[], &env);
+        assert!(report.error.contains("boom"));
+        assert_eq!(report.location, "1:1");
+    }
+
+    #[test]
+    fn environment_snapshot_includes_data_values_and_skips_the_rest() {
+        let env = Environment::new_global();
+        env.define("x", Value::Int(1));
+        env.define("f", Value::Builtin("f", |_, _| Ok(Value::Nil)));
+        let report = CrashReport::capture(&ShellError::Eval("boom".into()), "1:1".into(), &[], &env);
+        assert_eq!(report.environment.get("x"), Some(&Sexpr::Int(1)));
+        assert!(!report.environment.contains_key("f"));
+    }
+
+    #[test]
+    fn recent_forms_are_carried_through_unchanged() {
+        let env = Environment::new_global();
+        let forms = vec!["(+ 1 2)".to_string(), "(bad-call)".to_string()];
+        let report = CrashReport::capture(&ShellError::Eval("boom".into()), "1:1".into(), &forms, &env);
+        assert_eq!(report.recent_forms, forms);
+    }
+
+    #[test]
+    fn writes_valid_json_to_disk() {
+        let env = Environment::new_global();
+        let report = CrashReport::capture(&ShellError::Eval("boom".into()), "1:1".into(), &[], &env);
+        let path = std::env::temp_dir().join("cracked_shell_crash_report_test.json");
+        report.write_to(&path).unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&contents).is_ok());
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,176 @@
+//! `(describe v)`'s implementation: a one-value debug report -- type,
+//! length/arity, sharing (`Rc` strong count), and a rough memory estimate
+//! -- for chasing sharing and mutation bugs in scripts, as opposed to
+//! [`crate::inspect`]'s navigable drill-down into a value's *contents*.
+use crate::value::{FutureState, Value};
+use std::rc::Rc;
+
+/// Renders `value`'s debug report as a multi-line string, one `key:
+/// value` pair per line. Every variant gets a `type` line; the rest are
+/// included only when they apply (e.g. `length` for a list, `arity` for a
+/// lambda or builtin, `captured env` only for a lambda).
+pub fn describe(value: &Value) -> String {
+    let mut lines = vec![format!("type: {}", value.type_name())];
+
+    if let Some(length) = length_of(value) {
+        lines.push(format!("length: {length}"));
+    }
+    if let Some(arity) = arity_of(value) {
+        lines.push(format!("arity: {arity}"));
+    }
+    if let Value::Symbol(name) | Value::Keyword(name) = value {
+        let interned = crate::intern::intern(name);
+        lines.push(format!("interned-symbol id: {:p}", Rc::as_ptr(&interned) as *const u8));
+        lines.push(format!("interned-symbol refcount: {}", Rc::strong_count(&interned)));
+    }
+    if let Some(count) = rc_strong_count(value) {
+        lines.push(format!("rc strong count: {count}"));
+    }
+    if let Value::Lambda(lambda) = value {
+        lines.push(format!("captured env: {}", describe_env(&lambda.env)));
+    }
+    lines.push(format!("approximate size: {} bytes", size_estimate(value)));
+
+    lines.join("\n")
+}
+
+fn length_of(value: &Value) -> Option<usize> {
+    match value {
+        Value::Str(s) => Some(s.chars().count()),
+        Value::List(items) | Value::DottedList(items, _) => Some(items.len()),
+        Value::Plist(p) => Some(p.len()),
+        Value::Vector(items) => Some(items.len()),
+        _ => None,
+    }
+}
+
+fn arity_of(value: &Value) -> Option<String> {
+    match value {
+        Value::Lambda(lambda) => Some(match &lambda.rest {
+            Some(_) => format!("{} parameter(s) + rest", lambda.params.len()),
+            None => format!("{} parameter(s)", lambda.params.len()),
+        }),
+        Value::Builtin(name, _) => crate::builtins::doc_for(name).map(|(arity, _)| arity.to_string()),
+        _ => None,
+    }
+}
+
+/// The strong count of the `Rc` backing `value`, for every variant that's
+/// actually `Rc`-shared -- `None` for an inline scalar like `Value::Int`,
+/// which has no sharing to report.
+fn rc_strong_count(value: &Value) -> Option<usize> {
+    match value {
+        Value::List(items) => Some(Rc::strong_count(items)),
+        Value::DottedList(items, _) => Some(Rc::strong_count(items)),
+        Value::Lambda(lambda) => Some(Rc::strong_count(lambda)),
+        Value::Future(future) => Some(Rc::strong_count(future)),
+        Value::Memo(memo) => Some(Rc::strong_count(memo)),
+        Value::Vector(items) => Some(Rc::strong_count(items)),
+        Value::Error(error) => Some(Rc::strong_count(error)),
+        Value::Native(_, f) => Some(Rc::strong_count(f)),
+        _ => None,
+    }
+}
+
+/// A one-line summary of a closure's captured environment: how many
+/// frames it can see (itself plus every enclosing scope) and how many
+/// names are bound directly in the innermost one.
+fn describe_env(env: &crate::env::Environment) -> String {
+    let frame_count = env.frames().count();
+    let local_count = env.local_names().len();
+    format!("{frame_count} frame(s), {local_count} binding(s) in the innermost frame")
+}
+
+/// A rough, non-authoritative byte estimate: the `Value` enum's own
+/// stack footprint plus whatever heap allocation its variant owns.
+/// Shared (`Rc`) data is counted once per `Value` that points to it, not
+/// divided by the number of sharers -- this is "how much memory would
+/// this value pull in if it were the only reference left," not a precise
+/// attribution of shared cost.
+fn size_estimate(value: &Value) -> usize {
+    let heap = match value {
+        Value::Str(s) => s.capacity(),
+        Value::Symbol(s) | Value::Keyword(s) => s.capacity(),
+        Value::List(items) => items.capacity() * std::mem::size_of::<Value>(),
+        Value::DottedList(items, _) => items.capacity() * std::mem::size_of::<Value>(),
+        Value::Vector(items) => items.capacity() * std::mem::size_of::<f64>(),
+        Value::Lambda(lambda) => {
+            lambda.params.iter().map(|p| p.capacity()).sum::<usize>()
+                + lambda.rest.as_ref().map_or(0, String::capacity)
+                + lambda.body.len() * std::mem::size_of::<Value>()
+        }
+        Value::Future(future) => match &*future.borrow() {
+            FutureState::Pending(_) => 0,
+            FutureState::Done(Ok(s)) | FutureState::Done(Err(s)) => s.capacity(),
+        },
+        _ => 0,
+    };
+    std::mem::size_of::<Value>() + heap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::Environment;
+    use std::rc::Rc;
+
+    #[test]
+    fn reports_the_type_of_a_scalar() {
+        assert!(describe(&Value::Int(5)).contains("type: int"));
+    }
+
+    #[test]
+    fn reports_the_length_of_a_list() {
+        let report = describe(&Value::list(vec![Value::Int(1), Value::Int(2)]));
+        assert!(report.contains("length: 2"));
+    }
+
+    #[test]
+    fn reports_the_arity_of_a_lambda() {
+        let env = Environment::new_global();
+        let lambda = Value::Lambda(Rc::new(crate::value::Lambda {
+            params: vec!["a".into(), "b".into()],
+            rest: None,
+            body: vec![],
+            env: env.clone(),
+        }));
+        assert!(describe(&lambda).contains("arity: 2 parameter(s)"));
+    }
+
+    #[test]
+    fn reports_the_captured_environment_of_a_lambda() {
+        let parent = Environment::new_global();
+        parent.define("x", Value::Int(1));
+        let inner = Environment::child(&parent);
+        inner.define("y", Value::Int(2));
+        let lambda = Value::Lambda(Rc::new(crate::value::Lambda {
+            params: vec![],
+            rest: None,
+            body: vec![],
+            env: inner,
+        }));
+        let report = describe(&lambda);
+        assert!(report.contains("2 frame(s), 1 binding(s) in the innermost frame"));
+    }
+
+    #[test]
+    fn reports_the_rc_strong_count_of_a_shared_list() {
+        let shared = Value::list(vec![Value::Int(1)]);
+        let other = shared.clone();
+        let report = describe(&shared);
+        assert!(report.contains("rc strong count: 2"));
+        drop(other);
+    }
+
+    #[test]
+    fn a_symbol_reports_an_interned_id_and_refcount() {
+        let report = describe(&Value::Symbol("cracked_shell_describe_test_symbol".into()));
+        assert!(report.contains("interned-symbol id: 0x"));
+        assert!(report.contains("interned-symbol refcount: "));
+    }
+
+    #[test]
+    fn every_value_reports_an_approximate_size() {
+        assert!(describe(&Value::Str("hello".into())).contains("approximate size: "));
+    }
+}
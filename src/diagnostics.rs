@@ -0,0 +1,213 @@
+use crate::span::{Position, Span};
+use serde::Serialize;
+
+/// An LSP `Position`: zero-based line and character offsets, unlike
+/// [`crate::span::Position`]'s one-based `line`/`col` -- editors and CI
+/// tooling consuming [`JsonDiagnostic`] expect the LSP convention, so the
+/// conversion happens once here rather than leaking 1-based columns into
+/// every consumer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct JsonPosition {
+    pub line: usize,
+    pub character: usize,
+}
+
+impl From<Position> for JsonPosition {
+    fn from(p: Position) -> Self {
+        JsonPosition {
+            line: p.line.saturating_sub(1),
+            character: p.col.saturating_sub(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct JsonRange {
+    pub start: JsonPosition,
+    pub end: JsonPosition,
+}
+
+impl From<Span> for JsonRange {
+    fn from(span: Span) -> Self {
+        JsonRange {
+            start: span.start.into(),
+            end: span.end.into(),
+        }
+    }
+}
+
+/// An LSP `DiagnosticSeverity`, restricted to the two levels this crate
+/// actually produces: a parse failure is an `Error` (the file couldn't be
+/// read as Cracked Shell source at all), every [`crate::lint::Diagnostic`]
+/// is a `Warning` (a lint finding never stops a script from running).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single diagnostic in the shape editors and CI expect: an LSP-style
+/// `range`, a `severity`, a `code` naming what kind of finding this is,
+/// and the `file` it came from (LSP diagnostics are scoped to one file by
+/// their transport, a JSON request/response pair; this crate reports
+/// diagnostics for one file at a time over stdout instead, so `file` is
+/// carried on each diagnostic rather than left implicit).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JsonDiagnostic {
+    pub file: String,
+    pub range: JsonRange,
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+}
+
+/// Lexes, parses, and lints `source`, reporting every finding as a
+/// [`JsonDiagnostic`]. A lexer or parser failure is reported as a single
+/// `Error`-severity diagnostic whose `code` is the failing
+/// [`crate::error::ParseError`]'s own stable code (e.g.
+/// `"unterminated-list"`), falling back to `"parse-error"` for the
+/// (currently unreachable, since [`crate::lint::lint`] only ever fails by
+/// parsing) case of some other [`crate::error::ShellError`] variant. Since
+/// `ShellError` carries no span of its own (see
+/// [`crate::repl::eval_source_and_print`] for the same limitation
+/// elsewhere), it's reported at the very start of the file rather than at
+/// the offending token. A successful parse is linted with
+/// [`crate::lint::lint`], whose findings -- all warnings, since none of
+/// them stop a script from running -- carry the span of whichever
+/// top-level form they were found in.
+pub fn json_diagnostics(file: &str, source: &str) -> Vec<JsonDiagnostic> {
+    match crate::lint::lint(source) {
+        Ok(diagnostics) => diagnostics
+            .into_iter()
+            .map(|d| JsonDiagnostic {
+                file: file.to_string(),
+                range: d.span.into(),
+                severity: Severity::Warning,
+                code: d.rule.to_string(),
+                message: d.message,
+            })
+            .collect(),
+        Err(e) => {
+            let code = match &e {
+                crate::error::ShellError::Parse(err) => err.code,
+                _ => "parse-error",
+            };
+            let start = Position { line: 1, col: 1 };
+            vec![JsonDiagnostic {
+                file: file.to_string(),
+                range: Span { start, end: start }.into(),
+                severity: Severity::Error,
+                code: code.to_string(),
+                message: e.to_string(),
+            }]
+        }
+    }
+}
+
+/// Renders the source line(s) `span` covers, gutter-prefixed with their
+/// line numbers, each followed by a caret line underlining the columns
+/// the span covers on that line -- rustc/miette/ariadne-style. A span
+/// spanning multiple lines gets one caret line per source line, since
+/// each line has its own starting column; the first and last lines are
+/// only underlined from/to the span's actual start/end column, while any
+/// lines in between are underlined in full.
+pub fn render_snippet(source: &str, span: &Span, colorize: bool) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut out = String::new();
+    for line_no in span.start.line..=span.end.line {
+        let Some(text) = lines.get(line_no - 1) else {
+            continue;
+        };
+        let gutter = line_no.to_string();
+        out.push_str(&format!("{gutter} | {text}\n"));
+
+        let start_col = if line_no == span.start.line { span.start.col } else { 1 };
+        let end_col = if line_no == span.end.line {
+            span.end.col
+        } else {
+            text.chars().count() + 1
+        };
+        let underline_len = end_col.saturating_sub(start_col).max(1);
+        let padding = " ".repeat(gutter.len() + 3 + start_col.saturating_sub(1));
+        let caret = format!("{padding}{}", "^".repeat(underline_len));
+        out.push_str(&crate::color::render_error(&caret, colorize));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span::Position;
+
+    fn span(start_line: usize, start_col: usize, end_line: usize, end_col: usize) -> Span {
+        Span {
+            start: Position { line: start_line, col: start_col },
+            end: Position { line: end_line, col: end_col },
+        }
+    }
+
+    #[test]
+    fn underlines_a_single_line_span() {
+        let rendered = render_snippet("(+ 1 bogus)", &span(1, 6, 1, 11), false);
+        assert_eq!(rendered, "1 | (+ 1 bogus)\n         ^^^^^\n");
+    }
+
+    #[test]
+    fn underlines_every_line_a_multiline_span_covers() {
+        let source = "(define (f)\n  (bogus))";
+        let rendered = render_snippet(source, &span(1, 1, 2, 10), false);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[0], "1 | (define (f)");
+        assert!(lines[1].trim_start().starts_with('^'));
+        assert_eq!(lines[2], "2 |   (bogus))");
+        assert!(lines[3].trim_start().starts_with('^'));
+    }
+
+    #[test]
+    fn wraps_the_caret_line_in_red_when_colorized() {
+        let rendered = render_snippet("(+ 1 bogus)", &span(1, 6, 1, 11), true);
+        assert!(rendered.contains("\x1b[31m"));
+    }
+
+    #[test]
+    fn position_converts_from_one_based_to_zero_based() {
+        let json: JsonPosition = Position { line: 1, col: 1 }.into();
+        assert_eq!(json, JsonPosition { line: 0, character: 0 });
+    }
+
+    #[test]
+    fn a_lint_finding_becomes_a_warning_diagnostic() {
+        let diagnostics = json_diagnostics("script.lisp", "(if 1)");
+        let bad_arity = diagnostics.iter().find(|d| d.code == "bad-arity").unwrap();
+        assert_eq!(bad_arity.severity, Severity::Warning);
+        assert_eq!(bad_arity.file, "script.lisp");
+    }
+
+    #[test]
+    fn a_parse_failure_becomes_a_single_error_diagnostic() {
+        let diagnostics = json_diagnostics("script.lisp", "(unterminated");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].code, "unterminated-list");
+    }
+
+    #[test]
+    fn a_parse_failure_uses_the_underlying_parse_errors_code() {
+        let diagnostics = json_diagnostics("script.lisp", "\"unterminated");
+        assert_eq!(diagnostics[0].code, "unterminated-string");
+    }
+
+    #[test]
+    fn serializes_to_the_expected_json_shape() {
+        let diagnostics = json_diagnostics("script.lisp", "(if 1)");
+        let bad_arity = diagnostics.iter().find(|d| d.code == "bad-arity").unwrap();
+        let json = serde_json::to_value(bad_arity).unwrap();
+        assert_eq!(json["file"], "script.lisp");
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["code"], "bad-arity");
+        assert_eq!(json["range"]["start"]["line"], 0);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+// Per-thread table of dynamically (fluidly) scoped variables, kept
+// separate from the lexical `Environment` chain: shell-ish settings like
+// the current directory or an output port are naturally scoped to "for
+// the duration of this call", not to the lexical block they were
+// mentioned in.
+//
+// Each variable is a stack: `defvar` establishes the bottom (its default
+// value), and `fluid-let` pushes a temporary override that's popped back
+// off once its body finishes, restoring whatever was visible before.
+thread_local! {
+    static DYNAMIC: RefCell<HashMap<String, Vec<Value>>> = RefCell::new(HashMap::new());
+}
+
+/// `(defvar name value)`: establishes `name` as a dynamic variable with
+/// `value` as its default. Calling this again on an already-defined name
+/// resets the default without disturbing any `fluid-let` currently
+/// shadowing it.
+pub fn defvar(name: impl Into<String>, value: Value) {
+    DYNAMIC.with(|vars| {
+        let mut vars = vars.borrow_mut();
+        let stack = vars.entry(name.into()).or_default();
+        if stack.is_empty() {
+            stack.push(value);
+        } else {
+            stack[0] = value;
+        }
+    });
+}
+
+/// The innermost value for `name`: the most recent `fluid-let` override,
+/// or its `defvar` default if nothing is currently shadowing it.
+pub fn get(name: &str) -> Option<Value> {
+    DYNAMIC.with(|vars| vars.borrow().get(name).and_then(|stack| stack.last().cloned()))
+}
+
+pub fn is_defined(name: &str) -> bool {
+    DYNAMIC.with(|vars| vars.borrow().contains_key(name))
+}
+
+/// Every name currently established with `defvar`, in no particular
+/// order. Used for "did you mean?" suggestions on an undefined symbol.
+pub fn names() -> Vec<String> {
+    DYNAMIC.with(|vars| vars.borrow().keys().cloned().collect())
+}
+
+/// Pushes a temporary override for `name`, used by `fluid-let` on entry.
+pub fn push(name: &str, value: Value) {
+    DYNAMIC.with(|vars| {
+        vars.borrow_mut().entry(name.to_string()).or_default().push(value);
+    });
+}
+
+/// Pops the most recent override for `name`, used by `fluid-let` on exit.
+pub fn pop(name: &str) {
+    DYNAMIC.with(|vars| {
+        if let Some(stack) = vars.borrow_mut().get_mut(name) {
+            stack.pop();
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defvar_establishes_a_default() {
+        defvar("cracked_shell_dynamic_test_default", Value::Int(1));
+        assert!(matches!(
+            get("cracked_shell_dynamic_test_default"),
+            Some(Value::Int(1))
+        ));
+    }
+
+    #[test]
+    fn push_then_pop_restores_the_prior_value() {
+        defvar("cracked_shell_dynamic_test_push_pop", Value::Int(1));
+        push("cracked_shell_dynamic_test_push_pop", Value::Int(2));
+        assert!(matches!(
+            get("cracked_shell_dynamic_test_push_pop"),
+            Some(Value::Int(2))
+        ));
+        pop("cracked_shell_dynamic_test_push_pop");
+        assert!(matches!(
+            get("cracked_shell_dynamic_test_push_pop"),
+            Some(Value::Int(1))
+        ));
+    }
+
+    #[test]
+    fn undefined_variable_is_none() {
+        assert!(get("cracked_shell_dynamic_test_missing").is_none());
+        assert!(!is_defined("cracked_shell_dynamic_test_missing"));
+    }
+
+    #[test]
+    fn names_lists_every_established_variable() {
+        defvar("cracked_shell_dynamic_test_names", Value::Int(1));
+        assert!(names().contains(&"cracked_shell_dynamic_test_names".to_string()));
+    }
+}
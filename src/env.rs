@@ -0,0 +1,608 @@
+use crate::ast::{Bindings, Sexpr};
+use crate::error::ShellError;
+use crate::value::Value;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+/// A single binding's value plus whether `set!`/`define` may replace it.
+/// `define-constant` is the only thing that produces an immutable one.
+struct Binding {
+    value: Value,
+    mutable: bool,
+}
+
+struct Frame {
+    vars: HashMap<String, Binding>,
+    parent: Option<Environment>,
+}
+
+thread_local! {
+    /// Bumped by every `Environment` mutation anywhere (`define`, `set!`,
+    /// `clear`) on this thread. [`Environment::get_cached`]'s inline cache
+    /// compares a stored generation against the current one to tell
+    /// "still valid" from "something changed since, don't trust this".
+    static GENERATION: Cell<u64> = const { Cell::new(0) };
+
+    /// Per-call-site cache for [`Environment::get_cached`], keyed by a
+    /// stable address identifying one particular symbol reference in the
+    /// parsed source (see that method's doc comment).
+    static LOOKUP_CACHE: RefCell<HashMap<usize, CacheEntry>> = RefCell::new(HashMap::new());
+}
+
+/// One [`Environment::get_cached`] cache entry: "as of `generation`, the
+/// call site this is keyed by resolved `name` in `frame`".
+///
+/// `frame` is a `Weak` reference, not a strong one, on purpose: if the
+/// frame it points at was never anything but a single call's short-lived
+/// local scope, nothing outside this cache keeps it alive once that call
+/// returns, so the `Weak` stops upgrading and the entry quietly goes
+/// stale instead of pinning a dead frame in memory forever (or, worse,
+/// outliving the call and later looking right at a glance but actually
+/// answering for the wrong invocation).
+struct CacheEntry {
+    name: String,
+    frame: Weak<RefCell<Frame>>,
+    generation: u64,
+}
+
+fn bump_generation() {
+    GENERATION.with(|g| g.set(g.get() + 1));
+}
+
+fn generation() -> u64 {
+    GENERATION.with(|g| g.get())
+}
+
+/// Lexical environment frame.
+///
+/// A cheap-to-clone handle onto a shared, mutable frame rather than the
+/// frame itself: cloning an `Environment` hands out another reference to
+/// the same bindings instead of snapshotting them. That's what lets a
+/// `lambda` capture the environment it was defined in -- so closures
+/// actually close over their surrounding bindings instead of the
+/// environment the caller happens to be evaluating in -- and lets
+/// `define`/`set!` mutate the frame a caller expects rather than a
+/// disconnected copy of it.
+#[derive(Clone)]
+pub struct Environment(Rc<RefCell<Frame>>);
+
+impl Environment {
+    pub fn new_global() -> Environment {
+        Environment(Rc::new(RefCell::new(Frame {
+            vars: HashMap::new(),
+            parent: None,
+        })))
+    }
+
+    /// A new, empty frame whose lookups fall back to `parent`.
+    pub fn child(parent: &Environment) -> Environment {
+        Environment(Rc::new(RefCell::new(Frame {
+            vars: HashMap::new(),
+            parent: Some(parent.clone()),
+        })))
+    }
+
+    /// Defines `name` as a plain, mutable binding, overwriting whatever was
+    /// there -- including a constant, since this is the primitive used for
+    /// internal bookkeeping (installing builtins, binding lambda params)
+    /// where shadowing is always intended. User-facing `(define ...)` goes
+    /// through [`Environment::define_checked`] instead, which respects
+    /// constants.
+    pub fn define(&self, name: impl Into<String>, value: Value) {
+        self.0.borrow_mut().vars.insert(
+            name.into(),
+            Binding {
+                value,
+                mutable: true,
+            },
+        );
+        bump_generation();
+    }
+
+    /// Like [`Environment::define`], but refuses to redefine a name that
+    /// was bound with [`Environment::define_constant`] in this same frame.
+    pub fn define_checked(&self, name: &str, value: Value) -> Result<(), ShellError> {
+        self.insert_checked(name, value, true)
+    }
+
+    /// Defines `name` as a constant: later `set!` or `define` on it (in
+    /// this frame) is an error instead of silently replacing it.
+    pub fn define_constant(&self, name: &str, value: Value) -> Result<(), ShellError> {
+        self.insert_checked(name, value, false)
+    }
+
+    fn insert_checked(&self, name: &str, value: Value, mutable: bool) -> Result<(), ShellError> {
+        let mut frame = self.0.borrow_mut();
+        if let Some(existing) = frame.vars.get(name) {
+            if !existing.mutable {
+                return Err(ShellError::Immutable(name.to_string()));
+            }
+        }
+        frame.vars.insert(name.to_string(), Binding { value, mutable });
+        drop(frame);
+        bump_generation();
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<Value> {
+        let frame = self.0.borrow();
+        if let Some(binding) = frame.vars.get(name) {
+            return Some(binding.value.clone());
+        }
+        frame.parent.as_ref().and_then(|p| p.get(name))
+    }
+
+    /// Mutates the binding for `name` in the nearest frame (this one or
+    /// an ancestor) that already has one, leaving every other frame
+    /// untouched. Unlike [`Environment::define`], this never creates a
+    /// new binding: assigning to a name nothing has declared yet is an
+    /// error, not a silent shadowing define in the local frame. Assigning
+    /// to a name bound by [`Environment::define_constant`] is also an
+    /// error.
+    pub fn set(&self, name: &str, value: Value) -> Result<(), ShellError> {
+        let next = {
+            let mut frame = self.0.borrow_mut();
+            if let Some(existing) = frame.vars.get(name) {
+                if !existing.mutable {
+                    return Err(ShellError::Immutable(name.to_string()));
+                }
+                frame.vars.insert(
+                    name.to_string(),
+                    Binding {
+                        value,
+                        mutable: true,
+                    },
+                );
+                drop(frame);
+                bump_generation();
+                return Ok(());
+            }
+            frame.parent.clone()
+        };
+        match next {
+            Some(parent) => parent.set(name, value),
+            None => Err(ShellError::Undefined(name.to_string())),
+        }
+    }
+
+    /// Looks `name` up the way [`Environment::get`] does, but first
+    /// checks a per-call-site inline cache: `call_site` should be a
+    /// stable address identifying one particular symbol reference in the
+    /// parsed source (e.g. `expr as *const Value as usize` for the
+    /// `Value::Symbol` being evaluated) so that repeated evaluations of
+    /// the *same* reference -- the common case for a global or a
+    /// recursive function's own name inside a loop -- can skip straight
+    /// to the frame that answered it last time instead of re-hashing
+    /// `name` in every intervening frame on the way there.
+    ///
+    /// A cache hit still walks from `self` up to the remembered frame,
+    /// comparing frame identity (a cheap pointer check) rather than
+    /// searching by name at each step, and only reads the binding once
+    /// it reaches that frame. This is what keeps a polymorphic call site
+    /// correct -- the same lexical reference inside a closure's body can
+    /// resolve through a *different* captured frame on each call if the
+    /// closure itself is re-created per call (e.g. `(lambda (x) (+ x n))`
+    /// returned afresh, with a different `n`, every time an outer
+    /// function runs) -- trusting a remembered frame without confirming
+    /// it's still one of `self`'s ancestors would silently answer with
+    /// some other call's value. If the walk never reaches the remembered
+    /// frame, or a [`Environment::define`]/`set!`/`clear` anywhere bumped
+    /// the generation counter since the entry was cached, this falls
+    /// back to an ordinary walk and re-caches whatever it finds.
+    pub fn get_cached(&self, name: &str, call_site: usize) -> Option<Value> {
+        if let Some(value) = Self::cache_hit(self, name, call_site) {
+            return Some(value);
+        }
+
+        let mut frame = self.clone();
+        loop {
+            let (found, parent) = {
+                let f = frame.0.borrow();
+                (f.vars.get(name).map(|b| b.value.clone()), f.parent.clone())
+            };
+            if let Some(value) = found {
+                LOOKUP_CACHE.with(|cache| {
+                    cache.borrow_mut().insert(
+                        call_site,
+                        CacheEntry {
+                            name: name.to_string(),
+                            frame: Rc::downgrade(&frame.0),
+                            generation: generation(),
+                        },
+                    );
+                });
+                return Some(value);
+            }
+            match parent {
+                Some(p) => frame = p,
+                None => return None,
+            }
+        }
+    }
+
+    fn cache_hit(start: &Environment, name: &str, call_site: usize) -> Option<Value> {
+        let target = LOOKUP_CACHE.with(|cache| {
+            let cache = cache.borrow();
+            let entry = cache.get(&call_site)?;
+            if entry.generation != generation() || entry.name != name {
+                return None;
+            }
+            entry.frame.upgrade()
+        })?;
+
+        let mut frame = start.clone();
+        loop {
+            if Rc::ptr_eq(&frame.0, &target) {
+                return frame.0.borrow().vars.get(name).map(|b| b.value.clone());
+            }
+            let parent = frame.0.borrow().parent.clone();
+            match parent {
+                Some(p) => frame = p,
+                None => return None,
+            }
+        }
+    }
+
+    /// Names bound directly in this frame, not including parent frames.
+    pub fn local_names(&self) -> Vec<String> {
+        self.0.borrow().vars.keys().cloned().collect()
+    }
+
+    /// Renders every binding in this frame as a `(define name value)\n`
+    /// line, skipping `Builtin`s (which have no source form of their own --
+    /// they're installed by name, not defined). Used by `save-session` to
+    /// dump a reloadable script, and by `async`/`parallel` to snapshot the
+    /// caller's top-level bindings into a background worker's otherwise
+    /// bare environment.
+    pub fn snapshot_defines(&self) -> String {
+        let mut names = self.local_names();
+        names.sort();
+        let mut out = String::new();
+        for name in names {
+            if let Some(value) = self.get(&name) {
+                if matches!(value, Value::Builtin(..)) {
+                    continue;
+                }
+                out.push_str(&format!("(define {name} {value})\n"));
+            }
+        }
+        out
+    }
+
+    /// Iterates this frame and each ancestor in turn, starting with `self`
+    /// and ending at the outermost (global) frame.
+    pub fn frames(&self) -> Frames {
+        Frames(Some(self.clone()))
+    }
+
+    /// Looks up each of `names` (through the normal lookup chain, not
+    /// just this frame) and collects them into a [`Bindings`] table fit
+    /// for handing to another evaluator's [`Environment::import`] or
+    /// serializing. Errors on the first name that isn't bound, or that
+    /// holds a `Builtin`/`Lambda`/`Future` with no data representation.
+    pub fn export(&self, names: &[&str]) -> Result<Bindings, ShellError> {
+        let mut bindings = Bindings::new();
+        for &name in names {
+            let value = self
+                .get(name)
+                .ok_or_else(|| ShellError::Undefined(name.to_string()))?;
+            let sexpr = Sexpr::from_value(&value).ok_or_else(|| {
+                ShellError::Eval(format!("{name} holds a non-data value and cannot be exported"))
+            })?;
+            bindings.insert(name.to_string(), sexpr);
+        }
+        Ok(bindings)
+    }
+
+    /// Defines every entry of `bindings` in this frame, the inverse of
+    /// [`Environment::export`]. Used to pre-seed a freshly created
+    /// evaluator with host-provided values before running untrusted or
+    /// embedded code against it.
+    pub fn import(&self, bindings: &Bindings) {
+        for (name, sexpr) in bindings {
+            self.define(name.clone(), sexpr.to_value());
+        }
+    }
+
+    /// Breaks any reference cycle rooted at this frame by dropping every
+    /// binding it holds directly. A lambda's captured environment is a
+    /// strong [`Environment`] (so closures keep working after the scope
+    /// that created them returns), which means a lambda stored back into
+    /// the very frame it captured -- the ordinary `(define (f ...) ...)`
+    /// recursive function -- makes that frame keep itself alive forever:
+    /// nothing ever drops its `Rc` to zero. Call this once a frame (in
+    /// practice, the global one on `,reset`) is known to be done with, so
+    /// a long-running session doesn't leak a frame's worth of bindings
+    /// every time a recursive function was ever defined in it.
+    pub fn clear(&self) {
+        self.0.borrow_mut().vars.clear();
+        bump_generation();
+    }
+
+    /// Looks up `name` across this frame and its ancestors, returning how
+    /// many frames out it was found -- `0` meaning this frame -- alongside
+    /// its value. Used by debuggers and completion, which care where a
+    /// name resolved from, unlike [`Environment::get`].
+    pub fn resolve(&self, name: &str) -> Option<(usize, Value)> {
+        self.frames().enumerate().find_map(|(depth, frame)| {
+            frame
+                .0
+                .borrow()
+                .vars
+                .get(name)
+                .map(|binding| (depth, binding.value.clone()))
+        })
+    }
+}
+
+/// Iterator returned by [`Environment::frames`].
+pub struct Frames(Option<Environment>);
+
+impl Iterator for Frames {
+    type Item = Environment;
+
+    fn next(&mut self) -> Option<Environment> {
+        let current = self.0.take()?;
+        self.0 = current.0.borrow().parent.clone();
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_lookups_fall_back_to_parent() {
+        let parent = Environment::new_global();
+        parent.define("x", Value::Int(1));
+        let child = Environment::child(&parent);
+        assert!(matches!(child.get("x"), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn define_in_child_does_not_leak_into_parent() {
+        let parent = Environment::new_global();
+        let child = Environment::child(&parent);
+        child.define("y", Value::Int(2));
+        assert!(parent.get("y").is_none());
+    }
+
+    #[test]
+    fn set_mutates_the_frame_that_owns_the_binding() {
+        let parent = Environment::new_global();
+        parent.define("x", Value::Int(1));
+        let child = Environment::child(&parent);
+        child.set("x", Value::Int(2)).unwrap();
+        assert!(matches!(parent.get("x"), Some(Value::Int(2))));
+        assert!(child.local_names().is_empty());
+    }
+
+    #[test]
+    fn set_on_an_undeclared_name_is_an_error() {
+        let env = Environment::new_global();
+        assert!(env.set("never-defined", Value::Int(1)).is_err());
+    }
+
+    #[test]
+    fn cloned_environments_share_the_same_frame() {
+        let env = Environment::new_global();
+        let handle = env.clone();
+        handle.define("shared", Value::Int(7));
+        assert!(matches!(env.get("shared"), Some(Value::Int(7))));
+    }
+
+    /// A global `(define x 10)` must still be visible to whatever gets
+    /// evaluated against the same `Environment` afterward -- one top-level
+    /// form at a time, the way the REPL and file evaluation both work.
+    /// `Environment` already wraps a shared `Rc<RefCell<Frame>>`, so this
+    /// has nothing to do with `eval`/`define` specifically; it's pinned
+    /// here as a regression test for the global frame itself.
+    #[test]
+    fn a_global_define_is_visible_to_later_top_level_evaluations() {
+        let env = Environment::new_global();
+        crate::eval::eval(&crate::parser::Parser::parse("(define x 10)").unwrap(), &env).unwrap();
+        let result = crate::eval::eval(&crate::parser::Parser::parse("x").unwrap(), &env).unwrap();
+        assert!(matches!(result, Value::Int(10)));
+    }
+
+    #[test]
+    fn frames_walks_from_self_to_the_global_frame() {
+        let global = Environment::new_global();
+        let middle = Environment::child(&global);
+        let inner = Environment::child(&middle);
+        assert_eq!(inner.frames().count(), 3);
+    }
+
+    #[test]
+    fn resolve_reports_the_depth_a_name_was_found_at() {
+        let global = Environment::new_global();
+        global.define("x", Value::Int(1));
+        let inner = Environment::child(&global);
+        inner.define("y", Value::Int(2));
+
+        assert!(matches!(inner.resolve("y"), Some((0, Value::Int(2)))));
+        assert!(matches!(inner.resolve("x"), Some((1, Value::Int(1)))));
+        assert!(inner.resolve("never-defined").is_none());
+    }
+
+    #[test]
+    fn set_on_a_constant_is_an_error() {
+        let env = Environment::new_global();
+        env.define_constant("answer", Value::Int(3)).unwrap();
+        assert!(env.set("answer", Value::Int(0)).is_err());
+        assert!(matches!(env.get("answer"), Some(Value::Int(3))));
+    }
+
+    #[test]
+    fn define_checked_on_a_constant_is_an_error() {
+        let env = Environment::new_global();
+        env.define_constant("answer", Value::Int(3)).unwrap();
+        assert!(env.define_checked("answer", Value::Int(0)).is_err());
+    }
+
+    #[test]
+    fn define_bypasses_constant_protection() {
+        let env = Environment::new_global();
+        env.define_constant("answer", Value::Int(3)).unwrap();
+        env.define("answer", Value::Int(0));
+        assert!(matches!(env.get("answer"), Some(Value::Int(0))));
+    }
+
+    #[test]
+    fn a_self_capturing_lambda_leaks_until_the_frame_is_cleared() {
+        let env = Environment::new_global();
+        let watcher = Rc::downgrade(&env.0);
+        // A recursive top-level define closes over `env` and is then
+        // stored back inside it -- `env` now keeps itself alive via that
+        // binding, so dropping every external handle isn't enough.
+        env.define(
+            "f",
+            Value::Lambda(std::rc::Rc::new(crate::value::Lambda {
+                params: vec![],
+                rest: None,
+                body: vec![],
+                env: env.clone(),
+            })),
+        );
+        drop(env);
+        assert!(watcher.upgrade().is_some(), "frame should still be alive via its own cycle");
+    }
+
+    #[test]
+    fn export_collects_named_bindings_into_a_data_table() {
+        let env = Environment::new_global();
+        env.define("x", Value::Int(1));
+        env.define("y", Value::Str("hi".into()));
+        let bindings = env.export(&["x", "y"]).unwrap();
+        assert_eq!(bindings.get("x"), Some(&crate::ast::Sexpr::Int(1)));
+        assert_eq!(bindings.get("y"), Some(&crate::ast::Sexpr::Str("hi".into())));
+    }
+
+    #[test]
+    fn export_rejects_an_unbound_name() {
+        let env = Environment::new_global();
+        assert!(env.export(&["missing"]).is_err());
+    }
+
+    #[test]
+    fn export_rejects_a_non_data_value() {
+        let env = Environment::new_global();
+        env.define("f", Value::Builtin("f", |_, _| Ok(Value::Nil)));
+        assert!(env.export(&["f"]).is_err());
+    }
+
+    #[test]
+    fn import_round_trips_an_exported_table_into_a_fresh_environment() {
+        let source = Environment::new_global();
+        source.define("x", Value::Int(42));
+        let bindings = source.export(&["x"]).unwrap();
+
+        let target = Environment::new_global();
+        target.import(&bindings);
+        assert!(matches!(target.get("x"), Some(Value::Int(42))));
+    }
+
+    #[test]
+    fn clearing_the_frame_breaks_the_cycle_and_frees_it() {
+        let env = Environment::new_global();
+        let watcher = Rc::downgrade(&env.0);
+        env.define(
+            "f",
+            Value::Lambda(std::rc::Rc::new(crate::value::Lambda {
+                params: vec![],
+                rest: None,
+                body: vec![],
+                env: env.clone(),
+            })),
+        );
+        env.clear();
+        drop(env);
+        assert!(watcher.upgrade().is_none(), "clear() should have broken the cycle");
+    }
+
+    #[test]
+    fn get_cached_finds_a_binding_on_a_cold_call_site() {
+        let env = Environment::new_global();
+        env.define("x", Value::Int(1));
+        assert!(matches!(env.get_cached("x", 0xdead), Some(Value::Int(1))));
+    }
+
+    #[test]
+    fn get_cached_reuses_the_remembered_frame_on_a_warm_call_site() {
+        let global = Environment::new_global();
+        global.define("plus-like", Value::Int(7));
+        let inner = Environment::child(&Environment::child(&global));
+
+        assert!(matches!(inner.get_cached("plus-like", 0xbeef), Some(Value::Int(7))));
+        // A second call from the same call site (same cache key) should
+        // answer the same way, having gone through the cache this time.
+        assert!(matches!(inner.get_cached("plus-like", 0xbeef), Some(Value::Int(7))));
+    }
+
+    #[test]
+    fn get_cached_invalidates_after_a_define_anywhere() {
+        let global = Environment::new_global();
+        global.define("x", Value::Int(1));
+        assert!(matches!(global.get_cached("x", 0xc0ffee), Some(Value::Int(1))));
+
+        global.define("x", Value::Int(2));
+        assert!(matches!(global.get_cached("x", 0xc0ffee), Some(Value::Int(2))));
+    }
+
+    #[test]
+    fn get_cached_invalidates_after_a_set() {
+        let global = Environment::new_global();
+        global.define("x", Value::Int(1));
+        assert!(matches!(global.get_cached("x", 0x1234), Some(Value::Int(1))));
+
+        global.set("x", Value::Int(9)).unwrap();
+        assert!(matches!(global.get_cached("x", 0x1234), Some(Value::Int(9))));
+    }
+
+    #[test]
+    fn get_cached_does_not_leak_a_value_from_an_unrelated_frame_at_the_same_call_site() {
+        // Simulates a closure's free variable: the same lexical reference
+        // (same call site) resolving through a *different* captured frame
+        // depending on which closure is invoked. A naive cache keyed only
+        // on call site + name would answer the second lookup with the
+        // first closure's value.
+        let call_site = 0x5eed;
+
+        let first_closure_env = Environment::new_global();
+        first_closure_env.define("n", Value::Int(1));
+        assert!(matches!(
+            first_closure_env.get_cached("n", call_site),
+            Some(Value::Int(1))
+        ));
+
+        let second_closure_env = Environment::new_global();
+        second_closure_env.define("n", Value::Int(2));
+        assert!(matches!(
+            second_closure_env.get_cached("n", call_site),
+            Some(Value::Int(2))
+        ));
+    }
+
+    #[test]
+    fn get_cached_falls_back_once_the_cached_frame_is_dropped() {
+        let call_site = 0xfeed;
+        {
+            let short_lived = Environment::new_global();
+            short_lived.define("n", Value::Int(1));
+            assert!(matches!(short_lived.get_cached("n", call_site), Some(Value::Int(1))));
+        }
+        // `short_lived` is gone now; a lookup from an unrelated frame at
+        // the same call site must not resurrect it.
+        let other = Environment::new_global();
+        other.define("n", Value::Int(2));
+        assert!(matches!(other.get_cached("n", call_site), Some(Value::Int(2))));
+    }
+
+    #[test]
+    fn get_cached_misses_cleanly_for_an_unbound_name() {
+        let env = Environment::new_global();
+        assert!(env.get_cached("never-defined", 0x9999).is_none());
+    }
+}
@@ -0,0 +1,146 @@
+use std::fmt;
+
+/// A lex or parse failure with a stable, matchable `code` (e.g.
+/// `"unterminated-string"`, `"unexpected-token"`) alongside its
+/// human-readable `message` -- mirroring how [`crate::lint::Diagnostic`]
+/// pairs a `rule` name with a message. `expected`/`found` are filled in
+/// for the "expected X, found Y" family of errors (mismatched or missing
+/// tokens); they're `None` for errors with no single expected/found pair,
+/// such as an unterminated literal.
+///
+/// Tests, IDE integrations, and the REPL's incomplete-input detection
+/// (see [`crate::paste`]) can match on `code` instead of parsing prose out
+/// of `message`, which is free to reword without breaking anything that
+/// depends on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub code: &'static str,
+    pub message: String,
+    pub expected: Option<String>,
+    pub found: Option<String>,
+}
+
+impl ParseError {
+    pub fn new(code: &'static str, message: impl Into<String>) -> Self {
+        ParseError { code, message: message.into(), expected: None, found: None }
+    }
+
+    /// Like [`ParseError::new`], but also records what token was expected
+    /// and what was found instead, for the "expected X, found Y" family of
+    /// errors.
+    pub fn expected_found(
+        code: &'static str,
+        message: impl Into<String>,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            code,
+            message: message.into(),
+            expected: Some(expected.into()),
+            found: Some(found.into()),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// An error produced while lexing, parsing, or evaluating Cracked Shell source.
+#[derive(Debug, Clone)]
+pub enum ShellError {
+    /// Boxed so a [`ParseError`]'s extra `expected`/`found` fields don't
+    /// grow every `Result<_, ShellError>` on the call stack -- the parser
+    /// recurses once per nesting level, so `ShellError`'s size multiplies
+    /// straight into stack usage for deeply nested input.
+    Parse(Box<ParseError>),
+    Eval(String),
+    Undefined(String),
+    Arity(String),
+    Io(String),
+    /// Attempted to `define` or `set!` a name bound with `define-constant`.
+    Immutable(String),
+}
+
+impl fmt::Display for ShellError {
+    /// Every variant prints as `"{prefix}: {detail}"`, where `prefix`
+    /// comes from [`crate::catalog`] rather than being hardcoded -- so an
+    /// embedder can call [`crate::catalog::set_override`] to translate or
+    /// reword it (`"error.eval"` -> `"erreur d'évaluation"`, say) without
+    /// forking this crate. The `{detail}` half (the interpolated message,
+    /// symbol name, or status) isn't catalog-routed; see [`crate::catalog`]
+    /// for why that's out of scope here.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShellError::Parse(err) => write!(f, "{}: {err}", crate::catalog::message("error.parse")),
+            ShellError::Eval(msg) => write!(f, "{}: {msg}", crate::catalog::message("error.eval")),
+            ShellError::Undefined(name) => write!(f, "{}: {name}", crate::catalog::message("error.undefined")),
+            ShellError::Arity(msg) => write!(f, "{}: {msg}", crate::catalog::message("error.arity")),
+            ShellError::Io(msg) => write!(f, "{}: {msg}", crate::catalog::message("error.io")),
+            ShellError::Immutable(name) => write!(f, "{}: {name}", crate::catalog::message("error.immutable")),
+        }
+    }
+}
+
+impl std::error::Error for ShellError {}
+
+impl From<std::io::Error> for ShellError {
+    fn from(e: std::io::Error) -> Self {
+        ShellError::Io(e.to_string())
+    }
+}
+
+impl From<ParseError> for ShellError {
+    fn from(e: ParseError) -> Self {
+        ShellError::Parse(Box::new(e))
+    }
+}
+
+/// Lets [`crate::serde_value`]'s `Serializer`/`Deserializer` report a
+/// mismatch (wrong shape, unsupported type) as an ordinary `ShellError`
+/// instead of a separate error type just for that one conversion.
+impl serde::ser::Error for ShellError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ShellError::Eval(msg.to_string())
+    }
+}
+
+impl serde::de::Error for ShellError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        ShellError::Eval(msg.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_leaves_expected_and_found_unset() {
+        let err = ParseError::new("unterminated-string", "unterminated string");
+        assert_eq!(err.expected, None);
+        assert_eq!(err.found, None);
+    }
+
+    #[test]
+    fn expected_found_fills_in_both_fields() {
+        let err = ParseError::expected_found("unexpected-token", "unexpected ')'", "an expression", "')'");
+        assert_eq!(err.expected.as_deref(), Some("an expression"));
+        assert_eq!(err.found.as_deref(), Some("')'"));
+    }
+
+    #[test]
+    fn converting_to_shell_error_preserves_the_code() {
+        let err: ShellError = ParseError::new("unterminated-list", "unterminated list").into();
+        assert!(matches!(err, ShellError::Parse(e) if e.code == "unterminated-list"));
+    }
+
+    #[test]
+    fn display_shows_only_the_message() {
+        let err = ParseError::new("unterminated-string", "unterminated string");
+        assert_eq!(err.to_string(), "unterminated string");
+    }
+}
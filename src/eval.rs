@@ -0,0 +1,1517 @@
+use crate::env::Environment;
+use crate::error::{ParseError, ShellError};
+use crate::parser::Parser;
+use crate::value::{Lambda, Value};
+use std::cell::Cell;
+use std::rc::Rc;
+
+thread_local! {
+    static FUEL: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Bounds how many more times [`eval`] may run before it starts returning
+/// `ShellError::Eval("out of fuel")` instead of recursing further --
+/// `None` (the default) means unlimited, exactly how `eval` behaves for
+/// ordinary scripts. Meant for property tests over fuzzer-generated
+/// expressions (self-referential `lambda`s, runaway mutual recursion)
+/// that would otherwise hang the test process instead of failing fast.
+pub fn set_fuel(limit: Option<usize>) {
+    FUEL.with(|f| f.set(limit));
+}
+
+fn consume_fuel() -> Result<(), ShellError> {
+    FUEL.with(|f| match f.get() {
+        None => Ok(()),
+        Some(0) => Err(ShellError::Eval("out of fuel".into())),
+        Some(n) => {
+            f.set(Some(n - 1));
+            Ok(())
+        }
+    })
+}
+
+pub fn eval(expr: &Value, env: &Environment) -> Result<Value, ShellError> {
+    consume_fuel()?;
+    match expr {
+        Value::Symbol(name) => env
+            .get_cached(name, expr as *const Value as usize)
+            .or_else(|| crate::dynamic::get(name))
+            .or_else(|| crate::builtins::resolve_lazy(name, env))
+            .ok_or_else(|| undefined_symbol_error(name, env)),
+        Value::List(items) => eval_list(items, env),
+        other => Ok(other.clone()),
+    }
+}
+
+/// Builds a [`ShellError::Parse`] with the stable `"bad-syntax"` code for a
+/// special form whose shape (not its arity, which [`ShellError::Arity`]
+/// already covers) is wrong -- a `define` signature that isn't a symbol or
+/// `(name params...)`, a binding that isn't `(name expr)`, and so on.
+fn bad_syntax(message: impl Into<String>) -> ShellError {
+    ParseError::new("bad-syntax", message).into()
+}
+
+/// Builds the [`ShellError::Undefined`] for a failed symbol lookup,
+/// appending a "did you mean?" suggestion when some in-scope name --
+/// walking every lexical frame, every dynamic variable, and every
+/// registered builtin -- is a close typo away from `name`.
+fn undefined_symbol_error(name: &str, env: &Environment) -> ShellError {
+    let mut candidates: Vec<String> = Vec::new();
+    for frame in env.frames() {
+        candidates.extend(frame.local_names());
+    }
+    candidates.extend(crate::dynamic::names());
+    candidates.extend(crate::builtins::all_names().into_iter().map(str::to_string));
+
+    let message = match crate::suggest::suggest(name, candidates.iter().map(String::as_str)) {
+        Some(hint) => format!("{name} (did you mean {hint}?)"),
+        None => name.to_string(),
+    };
+    ShellError::Undefined(message)
+}
+
+fn eval_list(items: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    if items.is_empty() {
+        return Ok(Value::Nil);
+    }
+
+    #[cfg(feature = "tracing")]
+    let _span = crate::trace::eval_span(&items[0]).entered();
+
+    if let Value::Symbol(name) = &items[0] {
+        macro_rules! special_form {
+            ($label:expr, $body:expr) => {{
+                crate::profile::record_special_form($label);
+                return $body;
+            }};
+        }
+        match name.as_str() {
+            "quote" => special_form!(
+                "quote",
+                items
+                    .get(1)
+                    .cloned()
+                    .ok_or_else(|| ShellError::Arity("quote expects 1 argument".into()))
+            ),
+            "if" => special_form!("if", eval_if(&items[1..], env)),
+            "cond" => special_form!("cond", eval_cond(&items[1..], env)),
+            "and" => special_form!("and", eval_and(&items[1..], env)),
+            "or" => special_form!("or", eval_or(&items[1..], env)),
+            "define" => special_form!("define", eval_define(&items[1..], env)),
+            "define-constant" => special_form!("define-constant", eval_define_constant(&items[1..], env)),
+            "defvar" => special_form!("defvar", eval_defvar(&items[1..], env)),
+            "fluid-let" => special_form!("fluid-let", eval_fluid_let(&items[1..], env)),
+            "with-env" => special_form!("with-env", eval_with_env(&items[1..], env)),
+            "set!" => special_form!("set!", eval_set(&items[1..], env)),
+            "lambda" => special_form!("lambda", eval_lambda(&items[1..], env)),
+            "let" => special_form!("let", eval_let(&items[1..], env)),
+            "while" => special_form!("while", eval_while(&items[1..], env)),
+            "do" => special_form!("do", eval_do(&items[1..], env)),
+            "begin" => special_form!("begin", eval_begin(&items[1..], env)),
+            "async" => special_form!("async", eval_async(&items[1..], env)),
+            "parallel" => special_form!("parallel", eval_parallel(&items[1..], env)),
+            "with-raw-mode" => special_form!("with-raw-mode", eval_with_raw_mode(&items[1..], env)),
+            "catch" => special_form!("catch", eval_catch(&items[1..], env)),
+            "with-context" => special_form!("with-context", eval_with_context(&items[1..], env)),
+            _ => {}
+        }
+    }
+
+    let call_name = match &items[0] {
+        Value::Symbol(name) => Some(name.as_str()),
+        _ => None,
+    };
+
+    let func = eval(&items[0], env)?;
+    let mut args = Vec::with_capacity(items.len().saturating_sub(1));
+    for arg_expr in &items[1..] {
+        args.push(eval(arg_expr, env)?);
+    }
+
+    match &func {
+        Value::Builtin(name, _) | Value::Native(name, _) => crate::profile::record_builtin_call(name),
+        Value::Lambda(_) => {
+            let name = call_name.unwrap_or(crate::profile::ANONYMOUS_FUNCTION);
+            crate::callstack::push(name);
+            let result = if crate::profile::is_enabled() {
+                let started = std::time::Instant::now();
+                let result = apply(&func, args, env);
+                crate::profile::record_function_call(name, started.elapsed());
+                result
+            } else {
+                apply(&func, args, env)
+            };
+            if result.is_ok() {
+                crate::callstack::pop();
+            }
+            return result;
+        }
+        _ => {}
+    }
+    apply(&func, args, env)
+}
+
+fn eval_if(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let cond = args
+        .first()
+        .ok_or_else(|| ShellError::Arity("if expects a condition".into()))?;
+    let then_branch = args
+        .get(1)
+        .ok_or_else(|| ShellError::Arity("if expects a then-branch".into()))?;
+
+    if eval(cond, env)?.is_truthy() {
+        eval(then_branch, env)
+    } else {
+        match args.get(2) {
+            Some(else_branch) => eval(else_branch, env),
+            None => Ok(Value::Nil),
+        }
+    }
+}
+
+/// `(cond (test expr...) ... (else expr...))`: evaluates each clause's
+/// test in order and runs the body of the first one that's truthy,
+/// returning its last expression's value (or the test's own value if the
+/// clause has no body). `else` in test position always matches. A clause
+/// of the form `(test => proc)` evaluates `proc` and applies it to the
+/// test's value instead of running a body -- the one place this shell's
+/// `cond` follows Scheme's arrow form. Falls through to `nil` if nothing
+/// matches and there's no `else`, the same as a bodyless `if`.
+fn eval_cond(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    for clause in args {
+        let items = match clause {
+            Value::List(items) if !items.is_empty() => items,
+            _ => return Err(bad_syntax("cond clause must be a non-empty list")),
+        };
+
+        let is_else = matches!(&items[0], Value::Symbol(s) if s == "else");
+        let test_value = if is_else { Value::Bool(true) } else { eval(&items[0], env)? };
+        if !test_value.is_truthy() {
+            continue;
+        }
+
+        if let [_, Value::Symbol(arrow), proc_expr] = items.as_slice() {
+            if arrow == "=>" {
+                let proc = eval(proc_expr, env)?;
+                return apply(&proc, vec![test_value], env);
+            }
+        }
+
+        if items.len() == 1 {
+            return Ok(test_value);
+        }
+        let mut result = Value::Nil;
+        for expr in &items[1..] {
+            result = eval(expr, env)?;
+        }
+        return Ok(result);
+    }
+    Ok(Value::Nil)
+}
+
+/// `(and expr...)`: evaluates each expression in order, stopping and
+/// returning the first one that isn't truthy without evaluating the
+/// rest. Returns the last expression's value if every one of them is
+/// truthy, or `#t` if there are no expressions at all -- the identity
+/// for `and`.
+fn eval_and(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let mut result = Value::Bool(true);
+    for expr in args {
+        result = eval(expr, env)?;
+        if !result.is_truthy() {
+            return Ok(result);
+        }
+    }
+    Ok(result)
+}
+
+/// `(or expr...)`: evaluates each expression in order, stopping and
+/// returning the first one that's truthy without evaluating the rest.
+/// Returns the last expression's value if none of them are truthy, or
+/// `#f` if there are no expressions at all.
+fn eval_or(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let mut result = Value::Bool(false);
+    for expr in args {
+        result = eval(expr, env)?;
+        if result.is_truthy() {
+            return Ok(result);
+        }
+    }
+    Ok(result)
+}
+
+fn eval_define(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    match args.first() {
+        Some(Value::Symbol(name)) => {
+            let value_expr = args
+                .get(1)
+                .ok_or_else(|| ShellError::Arity("define expects a value".into()))?;
+            let value = eval(value_expr, env)?;
+            env.define_checked(name, value)?;
+            Ok(Value::Symbol(name.clone()))
+        }
+        Some(Value::List(sig)) => {
+            let name = match sig.first() {
+                Some(Value::Symbol(n)) => n.clone(),
+                _ => return Err(bad_syntax("bad define signature")),
+            };
+            let params = parse_params(&sig[1..])?;
+            let lambda = Value::Lambda(Rc::new(Lambda {
+                params,
+                rest: None,
+                body: args[1..].to_vec(),
+                env: env.clone(),
+            }));
+            env.define_checked(&name, lambda)?;
+            Ok(Value::Symbol(name))
+        }
+        Some(Value::DottedList(sig, rest)) => {
+            let name = match sig.first() {
+                Some(Value::Symbol(n)) => n.clone(),
+                _ => return Err(bad_syntax("bad define signature")),
+            };
+            let rest = match rest.as_ref() {
+                Value::Symbol(s) => s.clone(),
+                _ => return Err(bad_syntax("rest parameter must be a symbol")),
+            };
+            let params = parse_params(&sig[1..])?;
+            let lambda = Value::Lambda(Rc::new(Lambda {
+                params,
+                rest: Some(rest),
+                body: args[1..].to_vec(),
+                env: env.clone(),
+            }));
+            env.define_checked(&name, lambda)?;
+            Ok(Value::Symbol(name))
+        }
+        _ => Err(bad_syntax("define expects a symbol or (name params...)")),
+    }
+}
+
+/// `(define-constant name expr)`: like `define`, but later `set!` or
+/// `define` on `name` in this frame is an error instead of silently
+/// replacing it.
+fn eval_define_constant(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let name = match args.first() {
+        Some(Value::Symbol(name)) => name,
+        _ => return Err(bad_syntax("define-constant expects a symbol")),
+    };
+    let value_expr = args
+        .get(1)
+        .ok_or_else(|| ShellError::Arity("define-constant expects a value".into()))?;
+    let value = eval(value_expr, env)?;
+    env.define_constant(name, value)?;
+    Ok(Value::Symbol(name.clone()))
+}
+
+/// `(defvar name expr)`: establishes `name` as a dynamically (fluidly)
+/// scoped variable with `expr`'s value as its default, separate from the
+/// lexical `Environment` chain.
+fn eval_defvar(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let name = match args.first() {
+        Some(Value::Symbol(name)) => name,
+        _ => return Err(bad_syntax("defvar expects a symbol")),
+    };
+    let value_expr = args
+        .get(1)
+        .ok_or_else(|| ShellError::Arity("defvar expects a value".into()))?;
+    let value = eval(value_expr, env)?;
+    crate::dynamic::defvar(name.clone(), value);
+    Ok(Value::Symbol(name.clone()))
+}
+
+/// `(fluid-let ((name expr)...) body...)`: temporarily overrides each
+/// `name` -- which must already be a `defvar`-declared dynamic variable --
+/// for the dynamic extent of `body`, restoring its prior value (even if
+/// `body` errors) once evaluation leaves this form.
+fn eval_fluid_let(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let bindings = match args.first() {
+        Some(Value::List(b)) => b,
+        _ => return Err(bad_syntax("fluid-let expects a binding list")),
+    };
+
+    let mut pushed = Vec::with_capacity(bindings.len());
+    let setup = (|| {
+        for binding in bindings.iter() {
+            let pair = match binding {
+                Value::List(p) if p.len() == 2 => p,
+                _ => return Err(bad_syntax("fluid-let binding must be (name expr)")),
+            };
+            let name = match &pair[0] {
+                Value::Symbol(s) => s.clone(),
+                _ => {
+                    return Err(bad_syntax("fluid-let binding name must be a symbol"))
+                }
+            };
+            if !crate::dynamic::is_defined(&name) {
+                return Err(ShellError::Undefined(name));
+            }
+            let value = eval(&pair[1], env)?;
+            crate::dynamic::push(&name, value);
+            pushed.push(name);
+        }
+        Ok(())
+    })();
+    if let Err(e) = setup {
+        for name in pushed.iter().rev() {
+            crate::dynamic::pop(name);
+        }
+        return Err(e);
+    }
+
+    let result = (|| {
+        let mut result = Value::Nil;
+        for expr in &args[1..] {
+            result = eval(expr, env)?;
+        }
+        Ok(result)
+    })();
+
+    for name in pushed.iter().rev() {
+        crate::dynamic::pop(name);
+    }
+    result
+}
+
+/// `(with-env (("RUST_LOG" "debug")) body...)`: temporarily overlays OS
+/// environment variables for the dynamic extent of `body`, restoring
+/// each one's prior value (or unsetting it, if it wasn't set before)
+/// afterwards -- even if `body` errors. Since a spawned child process
+/// inherits the current process's environment by default, this is what
+/// scopes a variable to just the commands `proc/run` runs inside `body`
+/// rather than leaking it for the rest of the session.
+///
+/// The OS environment is process-wide, not per-thread, so overlapping
+/// `with-env` calls from `async`/`parallel` worker threads can race each
+/// other's overrides; this is fine for the REPL's normal single-threaded
+/// use but isn't a substitute for per-command environment scoping.
+fn eval_with_env(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let bindings = match args.first() {
+        Some(Value::List(b)) => b,
+        _ => return Err(bad_syntax("with-env expects a binding list")),
+    };
+
+    let mut overridden = Vec::with_capacity(bindings.len());
+    let setup = (|| {
+        for binding in bindings.iter() {
+            let pair = match binding {
+                Value::List(p) if p.len() == 2 => p,
+                _ => return Err(bad_syntax("with-env binding must be (name expr)")),
+            };
+            let name = match &pair[0] {
+                Value::Str(s) => s.clone(),
+                _ => return Err(bad_syntax("with-env binding name must be a string")),
+            };
+            let value = match eval(&pair[1], env)? {
+                Value::Str(s) => s,
+                other => {
+                    return Err(ShellError::Eval(format!(
+                        "with-env binding value must be a string, got {}",
+                        other.type_name()
+                    )))
+                }
+            };
+            let previous = std::env::var(&name).ok();
+            std::env::set_var(&name, value);
+            overridden.push((name, previous));
+        }
+        Ok(())
+    })();
+    if let Err(e) = setup {
+        restore_env(&overridden);
+        return Err(e);
+    }
+
+    let result = (|| {
+        let mut result = Value::Nil;
+        for expr in &args[1..] {
+            result = eval(expr, env)?;
+        }
+        Ok(result)
+    })();
+
+    restore_env(&overridden);
+    result
+}
+
+/// Restores each `(name, previous)` pair `with-env` recorded, in reverse
+/// order, unsetting `name` entirely if it had no prior value.
+fn restore_env(overridden: &[(String, Option<String>)]) {
+    for (name, previous) in overridden.iter().rev() {
+        match previous {
+            Some(value) => std::env::set_var(name, value),
+            None => std::env::remove_var(name),
+        }
+    }
+}
+
+/// `(set! name expr)`: mutates an existing binding for `name` in the
+/// nearest frame (this one or an ancestor) that already has one.
+///
+/// Unlike `define`, assigning to an undeclared name is an error rather
+/// than silently creating a new local binding.
+fn eval_set(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let name = match args.first() {
+        Some(Value::Symbol(name)) => name,
+        _ => return Err(bad_syntax("set! expects a symbol")),
+    };
+    let value_expr = args
+        .get(1)
+        .ok_or_else(|| ShellError::Arity("set! expects a value".into()))?;
+    let value = eval(value_expr, env)?;
+    env.set(name, value)?;
+    Ok(Value::Symbol(name.clone()))
+}
+
+fn eval_lambda(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let spec = args
+        .first()
+        .ok_or_else(|| bad_syntax("lambda expects a parameter list"))?;
+    let (params, rest) = parse_param_spec(spec)?;
+    let body = args[1..].to_vec();
+    if body.is_empty() {
+        return Err(ShellError::Arity("lambda expects a body".into()));
+    }
+    Ok(Value::Lambda(Rc::new(Lambda {
+        params,
+        rest,
+        body,
+        env: env.clone(),
+    })))
+}
+
+fn parse_params(items: &[Value]) -> Result<Vec<String>, ShellError> {
+    items
+        .iter()
+        .map(|v| match v {
+            Value::Symbol(s) => Ok(s.clone()),
+            _ => Err(bad_syntax("parameter names must be symbols")),
+        })
+        .collect()
+}
+
+/// Parses a lambda/`define` parameter spec into its fixed names and an
+/// optional rest name: `(x y)` is fixed-arity with no rest, `(x . rest)`
+/// is `x` plus a rest that collects everything past it, and a bare
+/// `args` symbol (no parens at all) is an all-rest parameter list --
+/// every argument lands in `args`, including zero of them.
+fn parse_param_spec(spec: &Value) -> Result<(Vec<String>, Option<String>), ShellError> {
+    match spec {
+        Value::Symbol(s) => Ok((Vec::new(), Some(s.clone()))),
+        Value::List(items) => Ok((parse_params(items)?, None)),
+        Value::DottedList(items, tail) => {
+            let rest = match tail.as_ref() {
+                Value::Symbol(s) => s.clone(),
+                _ => return Err(bad_syntax("rest parameter must be a symbol")),
+            };
+            Ok((parse_params(items)?, Some(rest)))
+        }
+        _ => Err(bad_syntax("lambda expects a parameter list")),
+    }
+}
+
+fn eval_let(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let bindings = match args.first() {
+        Some(Value::List(b)) => b,
+        _ => return Err(bad_syntax("let expects a binding list")),
+    };
+
+    let local = Environment::child(env);
+    for binding in bindings.iter() {
+        let pair = match binding {
+            Value::List(p) if p.len() == 2 => p,
+            _ => return Err(bad_syntax("let binding must be (name expr)")),
+        };
+        let name = match &pair[0] {
+            Value::Symbol(s) => s.clone(),
+            _ => return Err(bad_syntax("let binding name must be a symbol")),
+        };
+        let value = eval(&pair[1], env)?;
+        local.define(name, value);
+    }
+
+    let mut result = Value::Nil;
+    for expr in &args[1..] {
+        result = eval(expr, &local)?;
+    }
+    Ok(result)
+}
+
+/// `(begin e1 e2 ... en)`: evaluates each expression in order in the
+/// current scope and returns the last one's value. `lambda` and `let`
+/// bodies already sequence multiple expressions this way implicitly;
+/// `begin` is for the places that don't, like an `if` branch, which
+/// otherwise only takes a single expression (`(if cond (begin e1 e2) e3)`).
+fn eval_begin(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    if args.is_empty() {
+        return Err(ShellError::Arity("begin expects at least 1 expression".into()));
+    }
+    let mut result = Value::Nil;
+    for expr in args {
+        result = eval(expr, env)?;
+    }
+    Ok(result)
+}
+
+/// `(while cond body...)`: evaluates `cond`, and while it's truthy,
+/// evaluates `body` in sequence and repeats. Returns the value of the
+/// last `body` expression evaluated on the final truthy iteration, or
+/// `nil` if `cond` was never truthy. Each iteration re-evaluates `cond`
+/// and `body` against the same `env` -- there's no per-iteration scope,
+/// the way `let` or a lambda body gets one.
+fn eval_while(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let cond = args
+        .first()
+        .ok_or_else(|| ShellError::Arity("while expects a condition".into()))?;
+    let body = &args[1..];
+
+    let mut result = Value::Nil;
+    while eval(cond, env)?.is_truthy() {
+        for expr in body {
+            result = eval(expr, env)?;
+        }
+    }
+    Ok(result)
+}
+
+/// `(do ((var init step)...) (test result...) body...)`: binds each
+/// `var` to its `init` in a fresh child scope, then repeats: if `test`
+/// is truthy, evaluates `result` in sequence and returns its last value
+/// (or `nil` with no `result` expressions); otherwise evaluates `body`
+/// in sequence for effect, rebinds each `var` to its `step` (or leaves
+/// it as-is if the clause omits `step`), and loops. All `step`
+/// expressions are evaluated before any `var` is rebound, so they see
+/// each other's old values, matching Scheme's `do`.
+fn eval_do(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let specs = match args.first() {
+        Some(Value::List(specs)) => specs,
+        _ => return Err(bad_syntax("do expects a list of variable specs")),
+    };
+    let test_clause = match args.get(1) {
+        Some(Value::List(items)) if !items.is_empty() => items,
+        _ => return Err(bad_syntax("do expects a (test result...) clause")),
+    };
+    let body = &args[2..];
+
+    struct DoVar {
+        name: String,
+        step: Option<Value>,
+    }
+
+    let local = Environment::child(env);
+    let mut vars = Vec::with_capacity(specs.len());
+    for spec in specs.iter() {
+        let parts = match spec {
+            Value::List(p) if p.len() == 2 || p.len() == 3 => p,
+            _ => return Err(bad_syntax("do variable spec must be (name init) or (name init step)")),
+        };
+        let name = match &parts[0] {
+            Value::Symbol(s) => s.clone(),
+            _ => return Err(bad_syntax("do variable name must be a symbol")),
+        };
+        let init = eval(&parts[1], env)?;
+        local.define(name.clone(), init);
+        vars.push(DoVar { name, step: parts.get(2).cloned() });
+    }
+
+    loop {
+        if eval(&test_clause[0], &local)?.is_truthy() {
+            let mut result = Value::Nil;
+            for expr in &test_clause[1..] {
+                result = eval(expr, &local)?;
+            }
+            return Ok(result);
+        }
+
+        for expr in body {
+            eval(expr, &local)?;
+        }
+
+        let mut next_values = Vec::with_capacity(vars.len());
+        for var in &vars {
+            next_values.push(match &var.step {
+                Some(step) => Some(eval(step, &local)?),
+                None => None,
+            });
+        }
+        for (var, next) in vars.iter().zip(next_values) {
+            if let Some(value) = next {
+                local.set(&var.name, value)?;
+            }
+        }
+    }
+}
+
+/// `(with-raw-mode body...)`: evaluates `body` in sequence.
+///
+/// There is no real raw-mode terminal control wired in yet (the REPL's
+/// input loop reads plain lines), so this currently just runs `body`
+/// as-is; it exists so scripts can be written against the eventual API
+/// without changing once raw mode lands.
+fn eval_with_raw_mode(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let mut result = Value::Nil;
+    for expr in args {
+        result = eval(expr, env)?;
+    }
+    Ok(result)
+}
+
+/// `(catch expr)`: evaluates `expr`, returning its value on success. On
+/// failure, instead of propagating the error, returns a `Value::Error`
+/// built from it -- the one way to turn a [`ShellError`] into a value a
+/// script can branch on, since nothing else in this interpreter catches
+/// an error partway through evaluation.
+///
+/// The stack of in-progress calls ([`crate::callstack`]) is restored to
+/// how it looked before `expr` ran: the error has been handled here, so
+/// its frames shouldn't linger for a later, unrelated failure's backtrace
+/// to pick up.
+fn eval_catch(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let expr = args
+        .first()
+        .ok_or_else(|| ShellError::Arity("catch expects 1 argument".into()))?;
+    let depth = crate::callstack::depth();
+    match eval(expr, env) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let location = crate::callstack::snapshot().into_iter().next();
+            crate::callstack::truncate(depth);
+            Ok(Value::error(error_kind_name(&e).to_string(), e.to_string(), location, Vec::new()))
+        }
+    }
+}
+
+/// The bare kind name a caught [`ShellError`] reports through
+/// `(error-kind e)`, as a string rather than a `'static &str` since
+/// [`Value::Error`] stores kinds from `make-error` the same way, and those
+/// aren't known until runtime.
+fn error_kind_name(e: &ShellError) -> &'static str {
+    match e {
+        ShellError::Parse(_) => "parse",
+        ShellError::Eval(_) => "eval",
+        ShellError::Undefined(_) => "undefined",
+        ShellError::Arity(_) => "arity",
+        ShellError::Io(_) => "io",
+        ShellError::Immutable(_) => "immutable",
+    }
+}
+
+/// `(with-context "while parsing config" body...)`: evaluates `body` in
+/// sequence like `with-raw-mode`, but on failure re-wraps the propagating
+/// error's message with `context`, anyhow-style, rather than letting it
+/// through unchanged. Nesting `with-context` calls chains the messages --
+/// each layer's wrap includes the inner error's already-wrapped message --
+/// so the final report reads as a "caused by" stack from outermost context
+/// down to the original failure, without losing which [`ShellError`]
+/// variant it originally was.
+fn eval_with_context(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let (context, body) = match args.split_first() {
+        Some((Value::Str(context), body)) if !body.is_empty() => (context, body),
+        _ => {
+            return Err(ShellError::Arity(
+                "with-context expects a string label and at least one body expression".into(),
+            ))
+        }
+    };
+    let mut result = Value::Nil;
+    for expr in body {
+        result = eval(expr, env).map_err(|e| wrap_context(context, e))?;
+    }
+    Ok(result)
+}
+
+/// Prepends `context` to `e`'s message, keeping `e`'s variant so its
+/// `ShellError::Display` prefix (`"eval error: "`, `"parse error: "`, ...)
+/// still reflects what actually went wrong at the root of the chain.
+fn wrap_context(context: &str, e: ShellError) -> ShellError {
+    let message = format!("{context}\ncaused by: {e}");
+    match e {
+        ShellError::Parse(err) => ShellError::Parse(Box::new(ParseError { message, ..*err })),
+        ShellError::Eval(_) => ShellError::Eval(message),
+        ShellError::Undefined(_) => ShellError::Undefined(message),
+        ShellError::Arity(_) => ShellError::Arity(message),
+        ShellError::Io(_) => ShellError::Io(message),
+        ShellError::Immutable(_) => ShellError::Immutable(message),
+    }
+}
+
+/// Spawns `source` to evaluate on a fresh background thread against a
+/// fresh environment pre-seeded with `prelude`, returning a `Value::Future`
+/// handle immediately.
+///
+/// `Value` and `Environment` are built on `Rc`, so neither is `Send` and
+/// neither can be moved across a thread boundary directly. The crossing
+/// mechanism every background evaluator in this crate (`async`,
+/// `parallel`, and any future job subsystem) should go through instead of
+/// inventing its own: render the not-yet-evaluated expression back to
+/// source text -- a `String` is `Send` -- build a brand-new `Environment`
+/// on the worker thread, replay `prelude` (the caller's top-level
+/// bindings, rendered by [`Environment::snapshot_defines`] the same way
+/// `save-session` does) into it, and evaluate `source` there from
+/// scratch. The result crosses back the same way, as rendered text that
+/// [`eval_await`] re-parses on the other side.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_isolated(prelude: String, source: String) -> Value {
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let worker_env = Environment::new_global();
+            crate::builtins::install(&worker_env);
+            for form in Parser::parse_all(&prelude)? {
+                eval(&form, &worker_env)?;
+            }
+            Parser::parse(&source).and_then(|form| eval(&form, &worker_env))
+        }))
+        .unwrap_or_else(|payload| Err(ShellError::Eval(format!("panicked: {}", panic_message(&*payload)))))
+        .map(|v| v.to_string())
+        .map_err(|e| e.to_string());
+        tx.send(result).ok();
+    });
+
+    Value::Future(std::rc::Rc::new(std::cell::RefCell::new(
+        crate::value::FutureState::Pending(rx),
+    )))
+}
+
+/// wasm32-unknown-unknown has no threads to spawn onto, so `async` and
+/// `parallel` run `source` to completion right here instead and hand
+/// back an already-[`FutureState::Done`] handle -- `await` still works
+/// the same way on the result, just without the concurrency.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_isolated(prelude: String, source: String) -> Value {
+    let worker_env = Environment::new_global();
+    crate::builtins::install(&worker_env);
+    let result = Parser::parse_all(&prelude)
+        .and_then(|forms| {
+            for form in forms {
+                eval(&form, &worker_env)?;
+            }
+            Parser::parse(&source).and_then(|form| eval(&form, &worker_env))
+        })
+        .map(|v| v.to_string())
+        .map_err(|e| e.to_string());
+
+    Value::Future(std::rc::Rc::new(std::cell::RefCell::new(
+        crate::value::FutureState::Done(result),
+    )))
+}
+
+/// `(async expr)`: evaluates `expr` on a background thread and returns a
+/// `Value::Future` handle immediately. The worker sees the same top-level
+/// bindings the caller has (see [`spawn_isolated`]), not just builtins.
+fn eval_async(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    let expr = args
+        .first()
+        .ok_or_else(|| ShellError::Arity("async expects 1 expression".into()))?;
+    Ok(spawn_isolated(env.snapshot_defines(), expr.to_string()))
+}
+
+/// `(parallel expr...)`: like `(async expr)`, but spawns every expression
+/// on its own background thread at once and returns the list of their
+/// `Future` handles in argument order, so callers can `await` each as it
+/// finishes instead of running them one at a time.
+fn eval_parallel(args: &[Value], env: &Environment) -> Result<Value, ShellError> {
+    if args.is_empty() {
+        return Err(ShellError::Arity("parallel expects at least 1 expression".into()));
+    }
+    let prelude = env.snapshot_defines();
+    Ok(Value::list(
+        args.iter()
+            .map(|expr| spawn_isolated(prelude.clone(), expr.to_string()))
+            .collect(),
+    ))
+}
+
+/// `(await handle)`: blocks until the future resolves, returning its
+/// value (parsed back from the rendered string) or raising its error.
+pub fn eval_await(handle: &Value) -> Result<Value, ShellError> {
+    let state_cell = match handle {
+        Value::Future(state) => state,
+        other => {
+            return Err(ShellError::Eval(format!(
+                "await expects a future, got {}",
+                other.type_name()
+            )))
+        }
+    };
+
+    let resolved = {
+        let mut state = state_cell.borrow_mut();
+        if let crate::value::FutureState::Pending(rx) = &*state {
+            let result = rx
+                .recv()
+                .unwrap_or_else(|_| Err("background thread panicked".into()));
+            *state = crate::value::FutureState::Done(result);
+        }
+        match &*state {
+            crate::value::FutureState::Done(result) => result.clone(),
+            crate::value::FutureState::Pending(_) => unreachable!(),
+        }
+    };
+
+    match resolved {
+        Ok(text) => Parser::parse(&text),
+        Err(e) => Err(ShellError::Eval(e)),
+    }
+}
+
+/// Extracts a human-readable message from a [`std::panic::catch_unwind`]
+/// payload. `panic!("...")` and `panic!("{}", ...)` payloads downcast to
+/// `&str` or `String`; anything else (a custom payload from some
+/// dependency) has no reliable text, so it falls back to a generic label
+/// rather than guessing.
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+pub fn apply(func: &Value, args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+    match func {
+        Value::Builtin(name, f) => {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(args, env))).unwrap_or_else(
+                |payload| {
+                    Err(ShellError::Eval(format!(
+                        "internal error: {name} panicked: {}",
+                        panic_message(&*payload)
+                    )))
+                },
+            )
+        }
+        Value::Native(name, f) => {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(args, env))).unwrap_or_else(
+                |payload| {
+                    Err(ShellError::Eval(format!(
+                        "internal error: {name} panicked: {}",
+                        panic_message(&*payload)
+                    )))
+                },
+            )
+        }
+        Value::Memo(memo) => memo.borrow_mut().call(args, env),
+        Value::Lambda(lambda) => {
+            if lambda.rest.is_none() && lambda.params.len() != args.len() {
+                return Err(ShellError::Arity(format!(
+                    "expected {} argument(s), got {}",
+                    lambda.params.len(),
+                    args.len()
+                )));
+            }
+            if lambda.rest.is_some() && args.len() < lambda.params.len() {
+                return Err(ShellError::Arity(format!(
+                    "expected at least {} argument(s), got {}",
+                    lambda.params.len(),
+                    args.len()
+                )));
+            }
+            let call_env = Environment::child(&lambda.env);
+            let mut args = args.into_iter();
+            for param in &lambda.params {
+                call_env.define(param.clone(), args.next().expect("arity checked above"));
+            }
+            if let Some(rest) = &lambda.rest {
+                call_env.define(rest.clone(), Value::list(args.collect()));
+            }
+            let mut result = Value::Nil;
+            for expr in &lambda.body {
+                result = eval(expr, &call_env)?;
+            }
+            Ok(result)
+        }
+        other => Err(ShellError::Eval(format!(
+            "value of type {} is not callable",
+            other.type_name()
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtins;
+    use crate::parser::Parser;
+
+    fn eval_str(src: &str, env: &Environment) -> Value {
+        eval(&Parser::parse(src).unwrap(), env).unwrap()
+    }
+
+    fn fresh_env() -> Environment {
+        let env = Environment::new_global();
+        builtins::install(&env);
+        env
+    }
+
+    #[test]
+    fn evaluates_arithmetic() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(+ 1 2 3)", &env), Value::Int(6)));
+    }
+
+    #[test]
+    fn define_and_lookup() {
+        let env = fresh_env();
+        eval_str("(define x 10)", &env);
+        assert!(matches!(eval_str("x", &env), Value::Int(10)));
+    }
+
+    #[test]
+    fn lambda_application() {
+        let env = fresh_env();
+        eval_str("(define (square x) (* x x))", &env);
+        assert!(matches!(eval_str("(square 5)", &env), Value::Int(25)));
+    }
+
+    #[test]
+    fn an_anonymous_lambda_applies_directly() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("((lambda (x) (+ x 1)) 41)", &env), Value::Int(42)));
+    }
+
+    /// A lambda returned from inside a `let` keeps seeing that `let`'s
+    /// binding afterward -- [`crate::value::Lambda::env`] captures the
+    /// frame by reference, not a snapshot of its bindings at creation time.
+    #[test]
+    fn a_lambda_closes_over_a_let_binding() {
+        let env = fresh_env();
+        eval_str("(define make-adder (lambda (n) (lambda (x) (+ x n))))", &env);
+        let add5 = eval_str("(let ((n 5)) (make-adder n))", &env);
+        env.define("add5", add5);
+        assert!(matches!(eval_str("(add5 10)", &env), Value::Int(15)));
+    }
+
+    #[test]
+    fn a_bare_symbol_param_list_collects_every_argument_into_a_list() {
+        let env = fresh_env();
+        eval_str("(define (wrapper . args) args)", &env);
+        assert!(crate::value::values_equal(&eval_str("(wrapper 1 2 3)", &env), &eval_str("(list 1 2 3)", &env)));
+        assert!(crate::value::values_equal(&eval_str("(wrapper)", &env), &eval_str("(list)", &env)));
+    }
+
+    #[test]
+    fn a_dotted_param_list_binds_the_fixed_names_and_collects_the_rest() {
+        let env = fresh_env();
+        eval_str("(define (wrapper first . rest) (list first rest))", &env);
+        let result = eval_str("(wrapper 1 2 3)", &env);
+        assert!(crate::value::values_equal(&result, &eval_str("(list 1 (list 2 3))", &env)));
+    }
+
+    #[test]
+    fn a_rest_parameter_is_empty_when_no_extra_arguments_are_passed() {
+        let env = fresh_env();
+        eval_str("(define (wrapper first . rest) rest)", &env);
+        assert!(crate::value::values_equal(&eval_str("(wrapper 1)", &env), &eval_str("(list)", &env)));
+    }
+
+    #[test]
+    fn a_rest_parameter_lambda_still_enforces_its_minimum_arity() {
+        let env = fresh_env();
+        eval_str("(define (wrapper first . rest) rest)", &env);
+        assert!(eval(&Parser::parse("(wrapper)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn anonymous_variadic_lambda_literal_form_works_too() {
+        let env = fresh_env();
+        assert!(crate::value::values_equal(&eval_str("((lambda args args) 1 2)", &env), &eval_str("(list 1 2)", &env)));
+    }
+
+    #[test]
+    fn async_await_round_trips_a_value() {
+        let env = fresh_env();
+        let handle = eval_str("(async (+ 1 2))", &env);
+        let resolved = eval_await(&handle).unwrap();
+        assert!(matches!(resolved, Value::Int(3)));
+    }
+
+    #[test]
+    fn parallel_runs_every_expression_and_returns_their_futures_in_order() {
+        let env = fresh_env();
+        let handles = eval_str("(parallel (+ 1 2) (* 3 4))", &env);
+        let handles = match handles {
+            Value::List(items) => items,
+            other => panic!("expected a list of futures, got {other}"),
+        };
+        assert_eq!(handles.len(), 2);
+        assert!(matches!(eval_await(&handles[0]).unwrap(), Value::Int(3)));
+        assert!(matches!(eval_await(&handles[1]).unwrap(), Value::Int(12)));
+    }
+
+    #[test]
+    fn async_sees_the_callers_top_level_defines() {
+        let env = fresh_env();
+        eval_str("(define (greet) \"hi\")", &env);
+        let handle = eval_str("(async (greet))", &env);
+        let resolved = eval_await(&handle).unwrap();
+        assert!(matches!(resolved, Value::Str(ref s) if s == "hi"));
+    }
+
+    #[test]
+    fn parallel_sees_the_callers_top_level_defines() {
+        let env = fresh_env();
+        eval_str("(define scale 10)", &env);
+        let handles = eval_str("(parallel (* scale 2) (* scale 3))", &env);
+        let handles = match handles {
+            Value::List(items) => items,
+            other => panic!("expected a list of futures, got {other}"),
+        };
+        assert!(matches!(eval_await(&handles[0]).unwrap(), Value::Int(20)));
+        assert!(matches!(eval_await(&handles[1]).unwrap(), Value::Int(30)));
+    }
+
+    fn panicking_builtin(_args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+        panic!("kaboom");
+    }
+
+    #[test]
+    fn a_panicking_builtin_surfaces_as_an_eval_error_with_the_panic_message() {
+        let env = fresh_env();
+        let err = apply(&Value::Builtin("boom", panicking_builtin), vec![], &env).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("boom"));
+        assert!(message.contains("kaboom"));
+    }
+
+    #[test]
+    fn panic_message_reads_a_string_literal_payload() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new("kaboom");
+        assert_eq!(panic_message(&*payload), "kaboom");
+    }
+
+    #[test]
+    fn panic_message_reads_a_formatted_string_payload() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new(format!("kaboom {}", 1));
+        assert_eq!(panic_message(&*payload), "kaboom 1");
+    }
+
+    #[test]
+    fn panic_message_falls_back_for_an_unrecognized_payload() {
+        let payload: Box<dyn std::any::Any + Send> = Box::new(42i32);
+        assert_eq!(panic_message(&*payload), "unknown panic");
+    }
+
+    #[test]
+    fn keywords_are_self_evaluating() {
+        let env = fresh_env();
+        match eval_str(":foo", &env) {
+            Value::Keyword(s) => assert_eq!(s, "foo"),
+            other => panic!("expected keyword, got {other}"),
+        }
+    }
+
+    #[test]
+    fn if_branches() {
+        let env = fresh_env();
+        match eval_str("(if (< 1 2) 'yes 'no)", &env) {
+            Value::Symbol(s) => assert_eq!(s, "yes"),
+            other => panic!("expected symbol, got {other}"),
+        }
+    }
+
+    #[test]
+    fn lambdas_close_over_their_defining_scope() {
+        let env = fresh_env();
+        eval_str(
+            "(define (make-counter) (define n 0) (lambda () (set! n (+ n 1)) n))",
+            &env,
+        );
+        eval_str("(define c (make-counter))", &env);
+        assert!(matches!(eval_str("(c)", &env), Value::Int(1)));
+        assert!(matches!(eval_str("(c)", &env), Value::Int(2)));
+        assert!(matches!(eval_str("(c)", &env), Value::Int(3)));
+    }
+
+    #[test]
+    fn set_mutates_an_outer_binding_from_a_nested_let() {
+        let env = fresh_env();
+        eval_str("(define total 0)", &env);
+        eval_str("(let ((x 5)) (set! total (+ total x)))", &env);
+        assert!(matches!(eval_str("total", &env), Value::Int(5)));
+    }
+
+    #[test]
+    fn set_on_an_undefined_name_is_an_error() {
+        let env = fresh_env();
+        assert!(eval(&Parser::parse("(set! never-defined 1)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn begin_evaluates_every_expression_and_returns_the_last() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(begin (define x 1) (set! x (+ x 1)) x)", &env),
+            Value::Int(2)
+        ));
+    }
+
+    #[test]
+    fn begin_lets_an_if_branch_run_more_than_one_expression() {
+        let env = fresh_env();
+        eval_str("(define total 0)", &env);
+        eval_str("(if #t (begin (set! total 1) (set! total (+ total 1))) (set! total -1))", &env);
+        assert!(matches!(eval_str("total", &env), Value::Int(2)));
+    }
+
+    #[test]
+    fn begin_with_no_expressions_is_an_error() {
+        let env = fresh_env();
+        assert!(eval(&Parser::parse("(begin)").unwrap(), &env).is_err());
+    }
+
+    #[test]
+    fn cond_picks_the_first_truthy_clause() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(cond (#f 1) (#t 2) (#t 3))", &env),
+            Value::Int(2)
+        ));
+    }
+
+    #[test]
+    fn cond_falls_back_to_the_else_clause() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(cond (#f 1) (else 2))", &env), Value::Int(2)));
+    }
+
+    #[test]
+    fn cond_with_no_matching_clause_and_no_else_is_nil() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(cond (#f 1) (#f 2))", &env), Value::Nil));
+    }
+
+    #[test]
+    fn cond_clause_runs_every_expression_in_its_body() {
+        let env = fresh_env();
+        eval_str("(define total 0)", &env);
+        eval_str("(cond (#t (set! total 1) (set! total (+ total 1))))", &env);
+        assert!(matches!(eval_str("total", &env), Value::Int(2)));
+    }
+
+    #[test]
+    fn cond_arrow_clause_applies_proc_to_the_test_value() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(cond ((+ 1 1) => (lambda (n) (* n 10))))", &env),
+            Value::Int(20)
+        ));
+    }
+
+    #[test]
+    fn and_returns_the_last_value_when_everything_is_truthy() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(and 1 2 3)", &env), Value::Int(3)));
+    }
+
+    #[test]
+    fn and_short_circuits_on_the_first_falsy_value() {
+        let env = fresh_env();
+        eval_str("(define touched #f)", &env);
+        eval_str("(and #f (set! touched #t))", &env);
+        assert!(matches!(eval_str("touched", &env), Value::Bool(false)));
+    }
+
+    #[test]
+    fn and_with_no_expressions_is_true() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(and)", &env), Value::Bool(true)));
+    }
+
+    #[test]
+    fn or_returns_the_first_truthy_value() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(or #f 2 3)", &env), Value::Int(2)));
+    }
+
+    #[test]
+    fn or_short_circuits_once_it_finds_a_truthy_value() {
+        let env = fresh_env();
+        eval_str("(define touched #f)", &env);
+        eval_str("(or #t (set! touched #t))", &env);
+        assert!(matches!(eval_str("touched", &env), Value::Bool(false)));
+    }
+
+    #[test]
+    fn or_with_no_expressions_is_false() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(or)", &env), Value::Bool(false)));
+    }
+
+    #[test]
+    fn while_runs_the_body_until_the_condition_goes_falsy() {
+        let env = fresh_env();
+        eval_str("(define i 0)", &env);
+        eval_str("(while (< i 3) (set! i (+ i 1)))", &env);
+        assert!(matches!(eval_str("i", &env), Value::Int(3)));
+    }
+
+    #[test]
+    fn while_returns_the_last_body_value_or_nil_if_never_run() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(while #f 1)", &env), Value::Nil));
+        eval_str("(define i 0)", &env);
+        assert!(matches!(eval_str("(while (< i 1) 1 (set! i (+ i 1)) 2)", &env), Value::Int(2)));
+    }
+
+    #[test]
+    fn do_loops_until_the_test_is_truthy_and_returns_the_result() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(do ((i 0 (+ i 1)) (sum 0 (+ sum i))) ((= i 5) sum))", &env),
+            Value::Int(10)
+        ));
+    }
+
+    #[test]
+    fn do_evaluates_every_step_before_rebinding_any_variable() {
+        let env = fresh_env();
+        assert!(matches!(
+            eval_str("(do ((a 0 b) (b 1 (+ a b))) ((= a 3) a))", &env),
+            Value::Int(3)
+        ));
+    }
+
+    #[test]
+    fn do_runs_its_body_for_effect_on_every_iteration() {
+        let env = fresh_env();
+        eval_str("(define total 0)", &env);
+        eval_str("(do ((i 0 (+ i 1))) ((= i 3)) (set! total (+ total i)))", &env);
+        assert!(matches!(eval_str("total", &env), Value::Int(3)));
+    }
+
+    #[test]
+    fn bindings_sees_the_local_frame_and_global_bindings_sees_the_outermost() {
+        let env = fresh_env();
+        eval_str("(define top 1)", &env);
+        match eval_str("(let ((x 2)) (bindings))", &env) {
+            Value::List(items) => {
+                assert!(items.iter().any(|v| matches!(v, Value::Symbol(s) if s == "x")));
+                assert!(!items.iter().any(|v| matches!(v, Value::Symbol(s) if s == "top")));
+            }
+            other => panic!("expected list, got {other}"),
+        }
+        match eval_str("(let ((x 2)) (global-bindings))", &env) {
+            Value::List(items) => {
+                assert!(items.iter().any(|v| matches!(v, Value::Symbol(s) if s == "top")));
+                assert!(!items.iter().any(|v| matches!(v, Value::Symbol(s) if s == "x")));
+            }
+            other => panic!("expected list, got {other}"),
+        }
+    }
+
+    #[test]
+    fn define_constant_resists_set_and_redefine() {
+        let env = fresh_env();
+        eval_str("(define-constant pi 3)", &env);
+        assert!(matches!(eval_str("pi", &env), Value::Int(3)));
+        assert!(eval(&Parser::parse("(set! pi 4)").unwrap(), &env).is_err());
+        assert!(eval(&Parser::parse("(define pi 4)").unwrap(), &env).is_err());
+        assert!(matches!(eval_str("pi", &env), Value::Int(3)));
+    }
+
+    #[test]
+    fn fluid_let_temporarily_overrides_a_dynamic_variable() {
+        let env = fresh_env();
+        eval_str("(defvar *verbose* #f)", &env);
+        assert!(matches!(eval_str("*verbose*", &env), Value::Bool(false)));
+        let inside = eval_str("(fluid-let ((*verbose* #t)) *verbose*)", &env);
+        assert!(matches!(inside, Value::Bool(true)));
+        assert!(matches!(eval_str("*verbose*", &env), Value::Bool(false)));
+    }
+
+    #[test]
+    fn fluid_let_restores_on_error_inside_body() {
+        let env = fresh_env();
+        eval_str("(defvar *verbose* #f)", &env);
+        let _ = eval(
+            &Parser::parse("(fluid-let ((*verbose* #t)) (undefined-fn))").unwrap(),
+            &env,
+        );
+        assert!(matches!(eval_str("*verbose*", &env), Value::Bool(false)));
+    }
+
+    #[test]
+    fn fluid_let_on_an_undeclared_dynamic_variable_is_an_error() {
+        let env = fresh_env();
+        assert!(eval(
+            &Parser::parse("(fluid-let ((*never-declared* #t)) #t)").unwrap(),
+            &env,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn with_env_overlays_and_restores_an_os_variable() {
+        let env = fresh_env();
+        std::env::remove_var("CRACKED_SHELL_WITH_ENV_TEST");
+        let result = eval_str(
+            "(with-env ((\"CRACKED_SHELL_WITH_ENV_TEST\" \"overlaid\")) (get-option 'unused))",
+            &env,
+        );
+        assert!(matches!(result, Value::Nil));
+        assert_eq!(
+            std::env::var("CRACKED_SHELL_WITH_ENV_TEST"),
+            Err(std::env::VarError::NotPresent)
+        );
+    }
+
+    #[test]
+    fn with_env_restores_the_prior_value_on_error() {
+        let env = fresh_env();
+        std::env::set_var("CRACKED_SHELL_WITH_ENV_RESTORE_TEST", "original");
+        let result = eval(
+            &Parser::parse(
+                "(with-env ((\"CRACKED_SHELL_WITH_ENV_RESTORE_TEST\" \"overlaid\")) undefined-name)",
+            )
+            .unwrap(),
+            &env,
+        );
+        assert!(result.is_err());
+        assert_eq!(
+            std::env::var("CRACKED_SHELL_WITH_ENV_RESTORE_TEST").unwrap(),
+            "original"
+        );
+        std::env::remove_var("CRACKED_SHELL_WITH_ENV_RESTORE_TEST");
+    }
+
+    #[test]
+    fn catch_passes_through_a_successful_result() {
+        let env = fresh_env();
+        assert_eq!(eval_str("(catch (+ 1 2))", &env).to_string(), "3");
+    }
+
+    #[test]
+    fn catch_turns_a_failure_into_an_error_value() {
+        let env = fresh_env();
+        let result = eval_str("(catch undefined-name)", &env);
+        assert!(matches!(result, Value::Error(_)));
+        assert_eq!(
+            eval_str("(error-kind (catch undefined-name))", &env).to_string(),
+            ":undefined"
+        );
+    }
+
+    #[test]
+    fn with_context_passes_through_a_successful_result() {
+        let env = fresh_env();
+        assert_eq!(eval_str("(with-context \"loading\" (+ 1 2))", &env).to_string(), "3");
+    }
+
+    #[test]
+    fn with_context_prepends_its_label_on_failure() {
+        let env = fresh_env();
+        let err = eval(
+            &Parser::parse("(with-context \"while parsing config\" undefined-name)").unwrap(),
+            &env,
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("while parsing config"));
+        assert!(message.contains("caused by"));
+        assert!(message.contains("undefined-name"));
+    }
+
+    #[test]
+    fn nested_with_context_chains_every_layer() {
+        let env = fresh_env();
+        let err = eval(
+            &Parser::parse(
+                "(with-context \"outer\" (with-context \"inner\" undefined-name))",
+            )
+            .unwrap(),
+            &env,
+        )
+        .unwrap_err();
+        let message = err.to_string();
+        let outer_at = message.find("outer").unwrap();
+        let inner_at = message.find("inner").unwrap();
+        assert!(outer_at < inner_at, "expected outer context before inner: {message}");
+    }
+
+    #[test]
+    fn catch_restores_the_call_stack_the_error_left_behind() {
+        let env = fresh_env();
+        for form in Parser::parse_all("(define (inner) (undefined-fn)) (define (outer) (inner))").unwrap() {
+            eval(&form, &env).unwrap();
+        }
+        crate::callstack::clear();
+        eval_str("(catch (outer))", &env);
+        assert!(crate::callstack::snapshot().is_empty());
+    }
+
+    #[test]
+    fn fuel_limit_stops_infinite_recursion() {
+        // `eval`/`apply` aren't tail-call optimized, so driving this to
+        // "out of fuel" nests ~1_000 Rust stack frames -- more than the
+        // default test-thread stack allows in a debug build. Run it on a
+        // thread with a stack large enough to actually reach the fuel
+        // check instead of overflowing first.
+        std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(|| {
+                let env = fresh_env();
+                eval_str("(define (loop) (loop))", &env);
+                set_fuel(Some(1_000));
+                let result = eval(&Parser::parse("(loop)").unwrap(), &env);
+                set_fuel(None);
+                assert!(matches!(result, Err(ShellError::Eval(ref msg)) if msg == "out of fuel"));
+            })
+            .unwrap()
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn fuel_does_not_interfere_when_unset() {
+        let env = fresh_env();
+        assert!(matches!(eval_str("(+ 1 2)", &env), Value::Int(3)));
+    }
+}
+
+/// Property tests over generated expression trees, as a safety net
+/// alongside the fixed-input tests above: `eval` should never panic, and
+/// a fuel limit should always turn "would recurse forever" into a clean
+/// error instead of a hang.
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::builtins;
+    use proptest::prelude::*;
+
+    fn fresh_env() -> Environment {
+        let env = Environment::new_global();
+        builtins::install(&env);
+        env
+    }
+
+    /// Small arithmetic/`if` expressions over int and bool leaves --
+    /// bounded to depth 5, so every one of these terminates on its own;
+    /// the point of the fuel limit here is to confirm it doesn't get in
+    /// the way of forms that would finish anyway.
+    fn expr() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![any::<i64>().prop_map(Value::Int), any::<bool>().prop_map(Value::Bool),];
+        leaf.prop_recursive(5, 64, 3, |inner| {
+            prop_oneof![
+                (inner.clone(), inner.clone())
+                    .prop_map(|(a, b)| Value::list(vec![Value::Symbol("+".into()), a, b])),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(a, b)| Value::list(vec![Value::Symbol("-".into()), a, b])),
+                (inner.clone(), inner.clone(), inner)
+                    .prop_map(|(c, t, e)| Value::list(vec![Value::Symbol("if".into()), c, t, e])),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn evaluation_never_panics_and_respects_a_fuel_limit(form in expr()) {
+            let env = fresh_env();
+            set_fuel(Some(5_000));
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| eval(&form, &env)));
+            set_fuel(None);
+            prop_assert!(result.is_ok());
+        }
+    }
+}
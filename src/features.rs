@@ -0,0 +1,67 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+/// Every flag `(use-feature 'name)` accepts, paired with a one-line note
+/// describing what it changes -- this is how a breaking change gets
+/// staged: the old behavior stays the default, the new behavior only
+/// runs once a script (or a human at the REPL) opts in by name, and this
+/// list is the single place that records what each flag does and what it
+/// will eventually replace.
+const KNOWN: &[(&str, &str)] =
+    &[("strict-arity", "(= n...) errors on a non-numeric argument instead of treating it as unequal")];
+
+thread_local! {
+    static ENABLED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+/// Opts into `name`. Errors if it isn't one of [`KNOWN`] -- a typo in a
+/// feature name should be loud, not a silent no-op that leaves a script
+/// running on behavior it thinks it opted out of.
+pub fn enable(name: &str) -> Result<(), String> {
+    let (flag, _) = KNOWN
+        .iter()
+        .find(|(flag, _)| *flag == name)
+        .ok_or_else(|| format!("unknown feature: {name}"))?;
+    ENABLED.with(|enabled| enabled.borrow_mut().insert(flag));
+    Ok(())
+}
+
+pub fn is_enabled(name: &str) -> bool {
+    ENABLED.with(|enabled| enabled.borrow().contains(name))
+}
+
+/// Every known feature name paired with its description, sorted by name.
+pub fn names() -> Vec<(&'static str, &'static str)> {
+    let mut names = KNOWN.to_vec();
+    names.sort_by_key(|(name, _)| *name);
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_feature_is_an_error() {
+        assert!(enable("cracked_shell_features_test_missing").is_err());
+    }
+
+    #[test]
+    fn enabling_a_known_feature_makes_it_report_enabled() {
+        enable("strict-arity").unwrap();
+        assert!(is_enabled("strict-arity"));
+    }
+
+    #[test]
+    fn an_unenabled_feature_reports_disabled() {
+        assert!(!is_enabled("cracked_shell_features_test_never_enabled"));
+    }
+
+    #[test]
+    fn names_are_sorted() {
+        let names: Vec<&str> = names().into_iter().map(|(name, _)| name).collect();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+    }
+}
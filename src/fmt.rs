@@ -0,0 +1,241 @@
+use crate::error::{ParseError, ShellError};
+use crate::lexer::{Lexer, Token, TokenInfo, TokenOrTrivia, Trivia};
+
+/// The column width [`format_code`] tries to keep a form within before
+/// breaking it onto multiple indented lines, matching [`crate::pretty`]'s
+/// default.
+const WIDTH: usize = crate::pretty::DEFAULT_WIDTH;
+
+/// A piece of source re-derived from the token stream: either semantic
+/// (an atom, a prefixed form, a list) or trivia worth keeping (a comment).
+/// Whitespace trivia is dropped; [`format_code`] re-derives spacing itself
+/// rather than preserving the original layout.
+enum Node {
+    Atom(String),
+    Prefixed(&'static str, Box<Node>),
+    List(Vec<Node>),
+    LineComment(String),
+    BlockComment(String),
+}
+
+enum Item {
+    Node(Node),
+    Close,
+}
+
+/// Parses `source` and re-emits it with canonical indentation, keeping
+/// comments in place. Re-derives spacing and line breaks from each form's
+/// nesting depth and width rather than preserving the original layout, so
+/// it's idempotent but doesn't preserve blank lines between forms.
+pub fn format_code(source: &str) -> Result<String, ShellError> {
+    let mut tokens = Lexer::token_stream(source, true);
+    let forms = parse_top_level(&mut tokens)?;
+
+    let mut out = String::new();
+    for node in &forms {
+        render_node(node, 0, &mut out);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn parse_top_level<I>(tokens: &mut I) -> Result<Vec<Node>, ShellError>
+where
+    I: Iterator<Item = Result<TokenInfo, ShellError>>,
+{
+    let mut items = Vec::new();
+    loop {
+        match parse_item(tokens)? {
+            Some(Item::Node(node)) => items.push(node),
+            Some(Item::Close) => {
+                return Err(ParseError::expected_found("unexpected-token", "unexpected ')'", "an expression", "')'")
+                    .into())
+            }
+            None => return Ok(items),
+        }
+    }
+}
+
+fn parse_list<I>(tokens: &mut I) -> Result<Vec<Node>, ShellError>
+where
+    I: Iterator<Item = Result<TokenInfo, ShellError>>,
+{
+    let mut items = Vec::new();
+    loop {
+        match parse_item(tokens)? {
+            Some(Item::Node(node)) => items.push(node),
+            Some(Item::Close) => return Ok(items),
+            None => {
+                return Err(
+                    ParseError::expected_found("unterminated-list", "unterminated list", "')'", "end of input")
+                        .into(),
+                )
+            }
+        }
+    }
+}
+
+fn parse_item<I>(tokens: &mut I) -> Result<Option<Item>, ShellError>
+where
+    I: Iterator<Item = Result<TokenInfo, ShellError>>,
+{
+    loop {
+        let info = match tokens.next() {
+            Some(info) => info?,
+            None => return Ok(None),
+        };
+        let node = match info.item {
+            TokenOrTrivia::Trivia(Trivia::Whitespace) => continue,
+            TokenOrTrivia::Trivia(Trivia::LineComment(text)) => Node::LineComment(text),
+            TokenOrTrivia::Trivia(Trivia::BlockComment(text)) => Node::BlockComment(text),
+            TokenOrTrivia::Token(Token::RParen) => return Ok(Some(Item::Close)),
+            TokenOrTrivia::Token(Token::LParen) => Node::List(parse_list(tokens)?),
+            TokenOrTrivia::Token(Token::Quote) => parse_prefixed("'", tokens)?,
+            TokenOrTrivia::Token(Token::Backtick) => parse_prefixed("`", tokens)?,
+            TokenOrTrivia::Token(Token::Comma) => parse_prefixed(",", tokens)?,
+            TokenOrTrivia::Token(Token::CommaAt) => parse_prefixed(",@", tokens)?,
+            TokenOrTrivia::Token(Token::DatumComment) => parse_prefixed("#;", tokens)?,
+            TokenOrTrivia::Token(other) => Node::Atom(render_token(&other)),
+        };
+        return Ok(Some(Item::Node(node)));
+    }
+}
+
+fn parse_prefixed<I>(prefix: &'static str, tokens: &mut I) -> Result<Node, ShellError>
+where
+    I: Iterator<Item = Result<TokenInfo, ShellError>>,
+{
+    match parse_item(tokens)? {
+        Some(Item::Node(inner)) => Ok(Node::Prefixed(prefix, Box::new(inner))),
+        _ => Err(ParseError::expected_found(
+            "unexpected-eof",
+            format!("expected an expression after '{prefix}'"),
+            "an expression",
+            "end of input",
+        )
+        .into()),
+    }
+}
+
+fn render_token(tok: &Token) -> String {
+    match tok {
+        Token::Symbol(s) => s.clone(),
+        Token::Keyword(s) => format!(":{s}"),
+        Token::Int(i) => i.to_string(),
+        Token::Float(n) => n.to_string(),
+        Token::Str(s) => format!("\"{}\"", escape_string(s)),
+        Token::Bool(b) => if *b { "#t" } else { "#f" }.to_string(),
+        Token::Char(c) => render_char(*c),
+        other => unreachable!("structural token reached render_token: {other:?}"),
+    }
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+fn render_char(c: char) -> String {
+    match c {
+        ' ' => "#\\space".into(),
+        '\n' => "#\\newline".into(),
+        '\t' => "#\\tab".into(),
+        other => format!("#\\{other}"),
+    }
+}
+
+/// Renders `node` on one line if it (and everything inside it) fits
+/// within [`WIDTH`] and contains no comments; comments always force their
+/// enclosing list onto multiple lines.
+fn render_flat(node: &Node) -> Option<String> {
+    match node {
+        Node::Atom(s) => Some(s.clone()),
+        Node::Prefixed(prefix, inner) => render_flat(inner).map(|s| format!("{prefix}{s}")),
+        Node::List(items) => {
+            let mut parts = Vec::with_capacity(items.len());
+            for item in items {
+                parts.push(render_flat(item)?);
+            }
+            Some(format!("({})", parts.join(" ")))
+        }
+        Node::LineComment(_) | Node::BlockComment(_) => None,
+    }
+}
+
+fn render_node(node: &Node, indent: usize, out: &mut String) {
+    if let Some(flat) = render_flat(node) {
+        if flat.len() + indent * 2 <= WIDTH {
+            out.push_str(&flat);
+            return;
+        }
+    }
+
+    match node {
+        Node::List(items) if items.is_empty() => out.push_str("()"),
+        Node::List(items) => {
+            out.push('(');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                    out.push_str(&"  ".repeat(indent + 1));
+                }
+                render_node(item, indent + 1, out);
+            }
+            // A line comment runs to the end of its line, so the closing
+            // paren after one has to start a new line.
+            if matches!(items.last(), Some(Node::LineComment(_))) {
+                out.push('\n');
+                out.push_str(&"  ".repeat(indent));
+            }
+            out.push(')');
+        }
+        Node::Prefixed(prefix, inner) => {
+            out.push_str(prefix);
+            render_node(inner, indent, out);
+        }
+        Node::LineComment(text) => out.push_str(text.trim_end()),
+        Node::BlockComment(text) => out.push_str(text),
+        Node::Atom(s) => out.push_str(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reformats_cramped_code_with_canonical_spacing() {
+        let out = format_code("(define   (square x)(*  x x))").unwrap();
+        assert_eq!(out, "(define (square x) (* x x))\n");
+    }
+
+    #[test]
+    fn preserves_comments() {
+        let out = format_code("(+ 1 ; add one\n 2)").unwrap();
+        assert!(out.contains("; add one"));
+    }
+
+    #[test]
+    fn wraps_long_forms_onto_multiple_indented_lines() {
+        let items = (0..40).map(|i| i.to_string()).collect::<Vec<_>>().join(" ");
+        let out = format_code(&format!("(list {items})")).unwrap();
+        assert!(out.contains('\n'));
+        assert!(out.contains("  1"));
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let once = format_code("(define (square x) (* x x))").unwrap();
+        let twice = format_code(&once).unwrap();
+        assert_eq!(once, twice);
+    }
+}
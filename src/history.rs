@@ -0,0 +1,65 @@
+/// Line history used for fish-style autosuggestion and the `,history`
+/// meta command.
+#[derive(Default)]
+pub struct History {
+    entries: Vec<String>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History::default()
+    }
+
+    pub fn push(&mut self, line: &str) {
+        let line = line.trim();
+        if !line.is_empty() && self.entries.last().map(String::as_str) != Some(line) {
+            self.entries.push(line.to_string());
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Returns the most recent history entry starting with `prefix`, the
+    /// inline suggestion shown dimmed as the user types it.
+    pub fn suggest(&self, prefix: &str) -> Option<&str> {
+        if prefix.is_empty() {
+            return None;
+        }
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| entry.starts_with(prefix) && entry.as_str() != prefix)
+            .map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_most_recent_match() {
+        let mut h = History::new();
+        h.push("(define x 1)");
+        h.push("(display x)");
+        h.push("(define y 2)");
+        assert_eq!(h.suggest("(define"), Some("(define y 2)"));
+    }
+
+    #[test]
+    fn skips_duplicate_consecutive_entries() {
+        let mut h = History::new();
+        h.push("(+ 1 1)");
+        h.push("(+ 1 1)");
+        assert_eq!(h.entries().len(), 1);
+    }
+
+    #[test]
+    fn no_suggestion_for_empty_prefix() {
+        let mut h = History::new();
+        h.push("(+ 1 1)");
+        assert_eq!(h.suggest(""), None);
+    }
+}
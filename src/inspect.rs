@@ -0,0 +1,86 @@
+use crate::value::Value;
+use std::io::{self, Write};
+
+/// Runs an interactive, navigable view of a nested value: list children by
+/// index, `<n>` to drill into child `n`, `u` to go up a level, `q` to quit.
+pub fn inspect(root: &Value) {
+    let mut path: Vec<usize> = Vec::new();
+    loop {
+        let current = resolve(root, &path);
+        print_frame(current, &path);
+
+        print!("inspect> ");
+        io::stdout().flush().ok();
+        let mut input = String::new();
+        if io::stdin().read_line(&mut input).unwrap_or(0) == 0 {
+            break;
+        }
+        match input.trim() {
+            "q" => break,
+            "u" => {
+                path.pop();
+            }
+            index_str => {
+                if let Ok(index) = index_str.parse::<usize>() {
+                    if child_count(current) > index {
+                        path.push(index);
+                    } else {
+                        println!("no such index: {index}");
+                    }
+                } else {
+                    println!("commands: <n> to descend, u to go up, q to quit");
+                }
+            }
+        }
+    }
+}
+
+fn resolve<'a>(root: &'a Value, path: &[usize]) -> &'a Value {
+    let mut current = root;
+    for &index in path {
+        if let Value::List(items) = current {
+            if let Some(item) = items.get(index) {
+                current = item;
+            }
+        }
+    }
+    current
+}
+
+fn child_count(value: &Value) -> usize {
+    match value {
+        Value::List(items) => items.len(),
+        _ => 0,
+    }
+}
+
+fn print_frame(value: &Value, path: &[usize]) {
+    println!("path: /{}", path.iter().map(|i| i.to_string()).collect::<Vec<_>>().join("/"));
+    match value {
+        Value::List(items) => {
+            for (i, item) in items.iter().enumerate() {
+                println!("  [{i}] {item}");
+            }
+        }
+        other => println!("  {other}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_nested_path() {
+        let root = Value::list(vec![
+            Value::Int(1),
+            Value::list(vec![Value::Int(2), Value::Int(3)]),
+        ]);
+        assert!(matches!(resolve(&root, &[1, 1]), Value::Int(3)));
+    }
+
+    #[test]
+    fn child_count_for_scalar_is_zero() {
+        assert_eq!(child_count(&Value::Int(1)), 0);
+    }
+}
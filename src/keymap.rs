@@ -0,0 +1,57 @@
+use crate::value::Value;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Line-editing style, configurable from the rc file.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EditMode {
+    Emacs,
+    Vi,
+}
+
+/// Keybinding configuration: the active editing mode plus custom bindings
+/// from a key name (e.g. `"F5"`) to a Lisp expression to evaluate.
+///
+/// This is kept per thread rather than per-`Environment`, since it describes
+/// how the terminal reads input rather than a Lisp-level binding.
+#[derive(Default)]
+pub struct KeyBindings {
+    pub mode: Option<EditMode>,
+    pub bindings: HashMap<String, Value>,
+}
+
+thread_local! {
+    static KEYMAP: RefCell<KeyBindings> = RefCell::new(KeyBindings::default());
+}
+
+pub fn set_mode(mode: EditMode) {
+    KEYMAP.with(|k| k.borrow_mut().mode = Some(mode));
+}
+
+pub fn mode() -> EditMode {
+    KEYMAP.with(|k| k.borrow().mode.unwrap_or(EditMode::Emacs))
+}
+
+pub fn bind(key: impl Into<String>, action: Value) {
+    KEYMAP.with(|k| k.borrow_mut().bindings.insert(key.into(), action));
+}
+
+pub fn binding(key: &str) -> Option<Value> {
+    KEYMAP.with(|k| k.borrow().bindings.get(key).cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_to_emacs_mode() {
+        assert_eq!(mode(), EditMode::Emacs);
+    }
+
+    #[test]
+    fn records_custom_bindings() {
+        bind("F5-test", Value::Int(1));
+        assert!(matches!(binding("F5-test"), Some(Value::Int(1))));
+    }
+}
@@ -0,0 +1,186 @@
+//! `cracked learn`: a guided, checkpointed tutorial that runs in the
+//! terminal the same way the REPL does -- it prints a prompt, reads a
+//! line, evaluates it, and won't move on to the next step until the
+//! value it gets back is the one the step expects.
+//!
+//! The lessons are [`lessons`]'s [`LESSONS_SRC`], written as ordinary
+//! s-expression data and parsed with [`crate::parser::Parser`] rather
+//! than built up as Rust structs -- editing or adding a lesson is editing
+//! a `(lesson ...)` form, not recompiling control flow.
+//!
+//! The ticket asked for a lesson on pipelines and one on job control;
+//! this interpreter has neither. There's no `|` operator (see
+//! `src/translate.rs`, which comments out shell pipelines it can't
+//! translate rather than inventing one), and no background-job table --
+//! no `jobs`/`bg`/`fg`. The closest things that *do* exist are covered
+//! instead: chaining `proc/run` calls by hand in place of pipelining
+//! them, and the `async`/`parallel` special forms in place of job
+//! control. "Checkpointed" here means each step must be answered
+//! correctly before the next one unlocks, not that progress survives
+//! quitting partway through and running `cracked learn` again -- there's
+//! no saved-progress file the way `persist-define` has one, and one
+//! wasn't worth adding for a tutorial a person works through in one
+//! sitting.
+
+use crate::env::Environment;
+use crate::eval::eval;
+use crate::parser::Parser;
+use crate::value::Value;
+use std::io::{self, BufRead, Write};
+
+/// One step within a [`Lesson`]: what to ask the learner to type, and the
+/// exact printed form (as [`crate::value::Value`]'s `Display` renders it)
+/// their answer must evaluate to before the tutorial moves on.
+pub struct Step {
+    pub prompt: String,
+    pub expect: String,
+}
+
+/// A named, introduced group of [`Step`]s, evaluated one after another
+/// against a single [`Environment`] shared by the whole lesson -- so a
+/// step that does `(define x 5)` is still in scope for the step after it.
+pub struct Lesson {
+    pub title: String,
+    pub intro: String,
+    pub steps: Vec<Step>,
+}
+
+const LESSONS_SRC: &str = r#"
+(
+  (lesson
+    "S-expressions"
+    "Everything here is a parenthesized list: the first element names what to call, the rest are its arguments."
+    (step "Evaluate (+ 1 2)" "3")
+    (step "Evaluate (list 1 2 3)" "(1 2 3)")
+    (step "Evaluate (define x 5)" "x")
+    (step "Evaluate x" "5"))
+  (lesson
+    "Running external commands"
+    "There's no shell pipe operator in this dialect -- proc/run runs one external command to completion and hands back its captured stdout as a string, for you to pass along yourself."
+    (step "Evaluate (proc/run \"echo\" \"hi\")" "\"hi\\n\""))
+  (lesson
+    "Concurrency"
+    "There's no background-job table here -- no jobs, bg, or fg -- concurrent work uses the async and parallel special forms instead. Both return immediately with one or more futures; await blocks on one until it resolves."
+    (step "Evaluate (await (async (+ 1 1)))" "2")
+    (step
+      "Evaluate (let ((jobs (parallel (+ 1 1) (+ 2 2)))) (list (await (car jobs)) (await (car (cdr jobs)))))"
+      "(2 4)"))
+)
+"#;
+
+/// Parses [`LESSONS_SRC`] into [`Lesson`]s. Panics on malformed data --
+/// this is the crate's own embedded tutorial, not user input, so a
+/// mistake here is a bug to fix in `LESSONS_SRC`, not something to
+/// recover from at runtime.
+fn lessons() -> Vec<Lesson> {
+    let parsed = Parser::parse(LESSONS_SRC).expect("lesson data parses");
+    let Value::List(lessons) = parsed else {
+        panic!("lesson data must be a list of (lesson ...) forms");
+    };
+    lessons.iter().map(parse_lesson).collect()
+}
+
+fn parse_lesson(form: &Value) -> Lesson {
+    let Value::List(items) = form else {
+        panic!("each lesson must be a (lesson ...) form");
+    };
+    let [head, title, intro, steps @ ..] = items.as_slice() else {
+        panic!("lesson form is missing its title or introduction");
+    };
+    assert!(matches!(head, Value::Symbol(s) if s == "lesson"), "expected a lesson form");
+    Lesson {
+        title: string_of(title),
+        intro: string_of(intro),
+        steps: steps.iter().map(parse_step).collect(),
+    }
+}
+
+fn parse_step(form: &Value) -> Step {
+    let Value::List(items) = form else {
+        panic!("each step must be a (step ...) form");
+    };
+    let [head, prompt, expect] = items.as_slice() else {
+        panic!("step form needs a prompt and an expected value");
+    };
+    assert!(matches!(head, Value::Symbol(s) if s == "step"), "expected a step form");
+    Step { prompt: string_of(prompt), expect: string_of(expect) }
+}
+
+fn string_of(value: &Value) -> String {
+    match value {
+        Value::Str(s) => s.clone(),
+        other => panic!("expected a string in lesson data, got {other}"),
+    }
+}
+
+/// Runs the tutorial against stdin/stdout: prints each lesson's
+/// introduction, then walks its steps, re-reading a line and re-evaluating
+/// it until the learner's answer matches the step's expected value.
+/// Typing `,quit` or closing stdin stops the tutorial early.
+pub fn run() {
+    let env = Environment::new_global();
+    crate::builtins::install(&env);
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    for lesson in lessons() {
+        println!("== {} ==\n{}\n", lesson.title, lesson.intro);
+        for step in lesson.steps {
+            loop {
+                print!("{}\nlearn> ", step.prompt);
+                io::stdout().flush().ok();
+                let Some(Ok(line)) = lines.next() else {
+                    println!("\nstopped partway through -- run `cracked learn` again to start over.");
+                    return;
+                };
+                if line.trim() == ",quit" {
+                    println!("stopped partway through -- run `cracked learn` again to start over.");
+                    return;
+                }
+                match Parser::parse(&line).and_then(|form| eval(&form, &env)) {
+                    Ok(value) if value.to_string() == step.expect => {
+                        println!("correct!\n");
+                        break;
+                    }
+                    Ok(value) => println!("got {value}, expected {} -- try again (or ,quit to stop)\n", step.expect),
+                    Err(e) => println!("{e} -- try again (or ,quit to stop)\n"),
+                }
+            }
+        }
+        println!("-- lesson complete --\n");
+    }
+    println!("tutorial complete!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lessons_parse_from_the_embedded_data() {
+        let lessons = lessons();
+        assert!(lessons.iter().any(|l| l.title == "S-expressions"));
+        assert!(lessons.iter().any(|l| l.title == "Concurrency"));
+    }
+
+    #[test]
+    fn every_steps_expected_answer_is_reachable_by_evaluating_its_own_prompt() {
+        // Each prompt names the exact expression to type, so evaluating it
+        // verbatim in a shared lesson environment should reproduce the
+        // step's expected value -- this is the same check `run` performs
+        // interactively, just without a human at the keyboard.
+        for lesson in lessons() {
+            let env = Environment::new_global();
+            crate::builtins::install(&env);
+            for step in lesson.steps {
+                let expr = step
+                    .prompt
+                    .rsplit_once("Evaluate ")
+                    .map(|(_, rest)| rest)
+                    .unwrap_or(&step.prompt);
+                let value = eval(&Parser::parse(expr).unwrap(), &env).unwrap();
+                assert_eq!(value.to_string(), step.expect, "lesson {:?}", lesson.title);
+            }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+/// Where a symbol resolves to when checked against the immediately
+/// enclosing lambda's parameter list, without touching a live
+/// `Environment`.
+///
+/// `Local` is the fast path a hot loop wants: the name is one of the
+/// lambda's own parameters, so it sits at a known index in the call's
+/// argument slice rather than somewhere up a chain of hashmaps. Everything
+/// else -- free variables, globals, anything a `define` or `defvar`
+/// introduces after the fact -- is `Dynamic`, and still has to go through
+/// [`crate::env::Environment::get`] at eval time, since those aren't
+/// knowable from a parameter list alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Address {
+    Local(usize),
+    Dynamic,
+}
+
+/// The compile-time resolution pass: checks `name` against `params`. This
+/// is meant to run once per lambda (e.g. while linting it, or ahead of a
+/// call) rather than once per reference, since a parameter list doesn't
+/// change between invocations -- what it avoids is `Environment::get`
+/// redoing the same hashing and frame-chain walk on every single mention
+/// of the same parameter inside a hot loop.
+///
+/// Only the immediately enclosing lambda is considered; a name belonging
+/// to an *outer* lambda's parameters still reports `Dynamic` here, since
+/// resolving through enclosing closures needs the frame chain the
+/// interpreter builds at call time regardless -- the win this targets is
+/// specifically the innermost, hottest frame, e.g. `n` and `acc` in
+/// `(define (sum n acc) (if (= n 0) acc (sum (- n 1) (+ acc n))))`.
+pub fn resolve(params: &[String], name: &str) -> Address {
+    match params.iter().position(|p| p == name) {
+        Some(index) => Address::Local(index),
+        None => Address::Dynamic,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::Environment;
+    use crate::value::Value;
+    use std::time::Instant;
+
+    #[test]
+    fn resolves_a_parameter_to_its_index() {
+        let params = vec!["n".to_string(), "acc".to_string()];
+        assert_eq!(resolve(&params, "acc"), Address::Local(1));
+    }
+
+    #[test]
+    fn free_variables_fall_back_to_dynamic() {
+        let params = vec!["n".to_string()];
+        assert_eq!(resolve(&params, "global-thing"), Address::Dynamic);
+    }
+
+    /// Not a pass/fail timing assertion -- wall-clock comparisons are
+    /// flaky on shared CI hardware -- just a demonstration, printed for a
+    /// human to read, of the gap this pass is meant to close: indexing a
+    /// parameter slice directly versus walking `Environment::get`'s frame
+    /// chain for the same name, repeated enough times that per-call
+    /// overhead dominates. Run with
+    /// `cargo test --release -- --ignored --nocapture` to see numbers.
+    #[test]
+    #[ignore]
+    fn local_addressing_beats_environment_lookup_in_a_hot_loop() {
+        const ITERATIONS: usize = 1_000_000;
+        let params = vec!["n".to_string(), "acc".to_string()];
+        let slots = [Value::Int(10), Value::Int(0)];
+
+        let start = Instant::now();
+        let mut total = 0i64;
+        for _ in 0..ITERATIONS {
+            if let Address::Local(index) = resolve(&params, "acc") {
+                if let Value::Int(n) = slots[index] {
+                    total += n;
+                }
+            }
+        }
+        let local_elapsed = start.elapsed();
+
+        let env = Environment::new_global();
+        env.define("n", Value::Int(10));
+        env.define("acc", Value::Int(0));
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            if let Some(Value::Int(n)) = env.get("acc") {
+                total += n;
+            }
+        }
+        let env_elapsed = start.elapsed();
+
+        eprintln!(
+            "local addressing: {local_elapsed:?}, environment lookup: {env_elapsed:?} (total={total})"
+        );
+    }
+}
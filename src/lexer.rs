@@ -0,0 +1,1007 @@
+use crate::error::{ParseError, ShellError};
+use crate::span::{Position, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    LParen,
+    RParen,
+    Quote,
+    /// `` ` `` quasiquote prefix.
+    Backtick,
+    /// `,` unquote prefix.
+    Comma,
+    /// `,@` unquote-splicing prefix.
+    CommaAt,
+    /// `#;` datum comment: the parser discards the expression that follows.
+    DatumComment,
+    /// A `:foo` keyword token; evaluates to itself rather than a lookup.
+    Keyword(String),
+    Symbol(String),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Char(char),
+}
+
+impl Token {
+    /// A coarse syntax-highlighting category for this token, independent
+    /// of its exact payload. Used by [`TokenStream`] so external tooling
+    /// (editors, formatters) doesn't have to match on every `Token`
+    /// variant itself.
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            Token::LParen | Token::RParen => TokenCategory::Paren,
+            Token::Quote | Token::Backtick | Token::Comma | Token::CommaAt => TokenCategory::Quote,
+            Token::DatumComment => TokenCategory::Comment,
+            Token::Keyword(_) => TokenCategory::Keyword,
+            Token::Symbol(_) => TokenCategory::Symbol,
+            Token::Int(_) | Token::Float(_) => TokenCategory::Number,
+            Token::Str(_) => TokenCategory::String,
+            Token::Bool(_) => TokenCategory::Bool,
+            Token::Char(_) => TokenCategory::Char,
+        }
+    }
+}
+
+/// Coarse syntax-highlighting category for a [`Token`] or [`Trivia`] item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenCategory {
+    Paren,
+    Quote,
+    Keyword,
+    Symbol,
+    Number,
+    String,
+    Bool,
+    Char,
+    Comment,
+    Whitespace,
+}
+
+/// A run of source text with no semantic meaning to the parser: a
+/// whitespace run or a comment. Only reported by [`TokenStream`] when
+/// asked to include trivia; the default tokenizers discard it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Trivia {
+    Whitespace,
+    LineComment(String),
+    BlockComment(String),
+}
+
+impl Trivia {
+    fn category(&self) -> TokenCategory {
+        match self {
+            Trivia::Whitespace => TokenCategory::Whitespace,
+            Trivia::LineComment(_) | Trivia::BlockComment(_) => TokenCategory::Comment,
+        }
+    }
+}
+
+/// A `Token` or a `Trivia` item, as produced by [`TokenStream`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenOrTrivia {
+    Token(Token),
+    Trivia(Trivia),
+}
+
+/// One item from [`TokenStream`]: a token (or, in trivia mode, a comment
+/// or whitespace run) paired with its source span and syntax category.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenInfo {
+    pub item: TokenOrTrivia,
+    pub span: Span,
+    pub category: TokenCategory,
+}
+
+/// Turns Cracked Shell source text into a flat token stream.
+///
+/// This is the whole-input tokenizer; it requires the source to be
+/// available up front as a string.
+pub struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    col: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Lexer {
+            chars: source.chars().peekable(),
+            line: 1,
+            col: 1,
+        }
+    }
+
+    pub fn tokenize(source: &str) -> Result<Vec<Token>, ShellError> {
+        Ok(Lexer::tokenize_with_spans(source)?
+            .into_iter()
+            .map(|(tok, _)| tok)
+            .collect())
+    }
+
+    /// Tokenizes `source`, pairing each token with the source span it was
+    /// read from.
+    pub fn tokenize_with_spans(source: &str) -> Result<Vec<(Token, Span)>, ShellError> {
+        let mut lexer = Lexer::new(source);
+        let mut tokens = Vec::new();
+        loop {
+            lexer.skip_whitespace_and_comments();
+            let start = lexer.position();
+            match lexer.next_token()? {
+                Some(tok) => tokens.push((
+                    tok,
+                    Span {
+                        start,
+                        end: lexer.position(),
+                    },
+                )),
+                None => break,
+            }
+        }
+        Ok(tokens)
+    }
+
+    /// An iterator-based token stream for external tooling (syntax
+    /// highlighters, formatters, editors), where each item carries its
+    /// source span and a coarse [`TokenCategory`]. With `include_trivia`,
+    /// comments and whitespace runs are reported as items too instead of
+    /// being silently discarded.
+    pub fn token_stream(source: &'a str, include_trivia: bool) -> TokenStream<'a> {
+        TokenStream::new(source, include_trivia)
+    }
+
+    fn position(&self) -> Position {
+        Position {
+            line: self.line,
+            col: self.col,
+        }
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        loop {
+            while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+                self.advance();
+            }
+            if self.chars.peek() == Some(&';') {
+                while let Some(&c) = self.chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    self.advance();
+                }
+                continue;
+            }
+            if self.chars.peek() == Some(&'#') && self.chars.clone().nth(1) == Some('|') {
+                self.advance();
+                self.advance();
+                self.skip_block_comment();
+                continue;
+            }
+            break;
+        }
+    }
+
+    /// Skips a `#| ... |#` block comment, which may nest.
+    fn skip_block_comment(&mut self) {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.advance() {
+                Some('#') if self.chars.peek() == Some(&'|') => {
+                    self.advance();
+                    depth += 1;
+                }
+                Some('|') if self.chars.peek() == Some(&'#') => {
+                    self.advance();
+                    depth -= 1;
+                }
+                Some(_) => {}
+                None => break,
+            }
+        }
+    }
+
+    /// Consumes and returns a single trivia item (one whitespace run or
+    /// one comment) starting at the current position, or `None` if the
+    /// next character starts neither. Unlike
+    /// [`Lexer::skip_whitespace_and_comments`], this stops after one item
+    /// so a caller can report each one with its own span.
+    fn next_trivia(&mut self) -> Option<Trivia> {
+        if matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+            while matches!(self.chars.peek(), Some(c) if c.is_whitespace()) {
+                self.advance();
+            }
+            return Some(Trivia::Whitespace);
+        }
+
+        if self.chars.peek() == Some(&';') {
+            let mut text = String::new();
+            while let Some(&c) = self.chars.peek() {
+                if c == '\n' {
+                    break;
+                }
+                text.push(c);
+                self.advance();
+            }
+            return Some(Trivia::LineComment(text));
+        }
+
+        if self.chars.peek() == Some(&'#') && self.chars.clone().nth(1) == Some('|') {
+            let mut text = String::new();
+            text.push(self.advance().unwrap());
+            text.push(self.advance().unwrap());
+            self.read_block_comment_text(&mut text);
+            return Some(Trivia::BlockComment(text));
+        }
+
+        None
+    }
+
+    /// Like [`Lexer::skip_block_comment`], but appends every consumed
+    /// character (including the closing `|#`) to `text` instead of
+    /// discarding them, so trivia mode can report the comment's contents.
+    fn read_block_comment_text(&mut self, text: &mut String) {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.advance() {
+                Some('#') if self.chars.peek() == Some(&'|') => {
+                    text.push('#');
+                    text.push(self.advance().unwrap());
+                    depth += 1;
+                }
+                Some('|') if self.chars.peek() == Some(&'#') => {
+                    text.push('|');
+                    text.push(self.advance().unwrap());
+                    depth -= 1;
+                }
+                Some(c) => text.push(c),
+                None => break,
+            }
+        }
+    }
+
+    /// This assumes [`Lexer::skip_whitespace_and_comments`] has already
+    /// been called, so span-tracking callers can take the pre-token
+    /// position as the token's start.
+    fn next_token(&mut self) -> Result<Option<Token>, ShellError> {
+        let c = match self.chars.peek() {
+            Some(&c) => c,
+            None => return Ok(None),
+        };
+
+        match c {
+            '(' | '[' => {
+                self.advance();
+                Ok(Some(Token::LParen))
+            }
+            ')' | ']' => {
+                self.advance();
+                Ok(Some(Token::RParen))
+            }
+            '\'' => {
+                self.advance();
+                Ok(Some(Token::Quote))
+            }
+            '`' => {
+                self.advance();
+                Ok(Some(Token::Backtick))
+            }
+            ',' => {
+                self.advance();
+                if self.chars.peek() == Some(&'@') {
+                    self.advance();
+                    Ok(Some(Token::CommaAt))
+                } else {
+                    Ok(Some(Token::Comma))
+                }
+            }
+            ':' => {
+                self.advance();
+                Ok(Some(Token::Keyword(self.read_word(String::new()))))
+            }
+            '|' => self.read_piped_symbol().map(Some),
+            '"' => self.read_string().map(Some),
+            'r' if self.chars.clone().nth(1) == Some('"') => {
+                self.advance();
+                self.read_raw_string().map(Some)
+            }
+            '#' if self.chars.clone().nth(1) == Some(';') => {
+                self.advance();
+                self.advance();
+                Ok(Some(Token::DatumComment))
+            }
+            '#' if self.chars.clone().nth(1) == Some('\\') => {
+                self.advance();
+                self.advance();
+                self.read_char_literal().map(Some)
+            }
+            '#' if self.chars.clone().nth(1) == Some('<') && self.chars.clone().nth(2) == Some('<') => {
+                self.read_heredoc().map(Some)
+            }
+            _ if c == '-' || c == '+' || c == '.' || c.is_ascii_digit() => {
+                self.read_number_or_symbol()
+            }
+            _ => self.read_symbol().map(Some),
+        }
+    }
+
+    fn read_string(&mut self) -> Result<Token, ShellError> {
+        self.advance(); // opening quote
+        let mut s = String::new();
+        loop {
+            match self.advance() {
+                Some('"') => return Ok(Token::Str(s)),
+                Some('\\') => match self.advance() {
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some(other) => s.push(other),
+                    None => return Err(ParseError::new("unterminated-string", "unterminated string").into()),
+                },
+                Some(c) => s.push(c),
+                None => return Err(ParseError::new("unterminated-string", "unterminated string").into()),
+            }
+        }
+    }
+
+    /// Reads a `r"..."` raw string literal: backslashes have no special
+    /// meaning, so the text can span multiple lines and contain literal
+    /// backslashes without escaping.
+    fn read_raw_string(&mut self) -> Result<Token, ShellError> {
+        self.advance(); // opening quote
+        let mut s = String::new();
+        loop {
+            match self.advance() {
+                Some('"') => return Ok(Token::Str(s)),
+                Some(c) => s.push(c),
+                None => return Err(ParseError::new("unterminated-string", "unterminated raw string").into()),
+            }
+        }
+    }
+
+    /// Reads a `#<<END ... END` heredoc string literal: everything up to
+    /// (not including) a line that is exactly the terminator becomes the
+    /// string's contents, with no escape processing -- the whole point is
+    /// to paste in a block of text (SQL, a config file) without having to
+    /// escape quotes in it. `#<<~END` additionally strips the leading
+    /// whitespace shared by every content line, so the heredoc body can be
+    /// indented to match the surrounding code.
+    fn read_heredoc(&mut self) -> Result<Token, ShellError> {
+        self.advance(); // '#'
+        self.advance(); // '<'
+        self.advance(); // '<'
+        let strip_indent = self.chars.peek() == Some(&'~');
+        if strip_indent {
+            self.advance();
+        }
+
+        let mut terminator = String::new();
+        while matches!(self.chars.peek(), Some(c) if !c.is_whitespace()) {
+            terminator.push(self.advance().unwrap());
+        }
+        if terminator.is_empty() {
+            return Err(ParseError::new(
+                "unterminated-heredoc",
+                "heredoc is missing a terminator after #<<",
+            )
+            .into());
+        }
+
+        while matches!(self.chars.peek(), Some(' ') | Some('\t')) {
+            self.advance();
+        }
+        if self.advance() != Some('\n') {
+            return Err(ParseError::expected_found(
+                "unexpected-token",
+                format!("expected a newline after heredoc terminator '{terminator}'"),
+                "newline",
+                format!("terminator '{terminator}'"),
+            )
+            .into());
+        }
+
+        let mut lines: Vec<String> = Vec::new();
+        loop {
+            let mut line = String::new();
+            let terminated_by_eof = loop {
+                match self.advance() {
+                    Some('\n') => break false,
+                    Some(c) => line.push(c),
+                    None => break true,
+                }
+            };
+            let line_matches_terminator = if strip_indent {
+                line.trim() == terminator
+            } else {
+                line == terminator
+            };
+            if line_matches_terminator {
+                break;
+            }
+            if terminated_by_eof {
+                return Err(ParseError::new(
+                    "unterminated-heredoc",
+                    format!("unterminated heredoc: missing '{terminator}' terminator"),
+                )
+                .into());
+            }
+            lines.push(line);
+        }
+
+        if strip_indent {
+            let indent = lines
+                .iter()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| line.len() - line.trim_start().len())
+                .min()
+                .unwrap_or(0);
+            for line in &mut lines {
+                let strip = indent.min(line.len() - line.trim_start().len());
+                line.drain(..strip);
+            }
+        }
+
+        Ok(Token::Str(lines.join("\n")))
+    }
+
+    /// Reads a `#\a` char literal, or a named one like `#\space`.
+    fn read_char_literal(&mut self) -> Result<Token, ShellError> {
+        let first = self
+            .advance()
+            .ok_or_else(|| ShellError::from(ParseError::new("unterminated-char-literal", "unterminated char literal")))?;
+
+        if !first.is_alphabetic() {
+            return Ok(Token::Char(first));
+        }
+
+        let name = self.read_word(first.to_string());
+        if name.chars().count() == 1 {
+            return Ok(Token::Char(first));
+        }
+        match name.to_lowercase().as_str() {
+            "space" => Ok(Token::Char(' ')),
+            "newline" => Ok(Token::Char('\n')),
+            "tab" => Ok(Token::Char('\t')),
+            other => Err(ParseError::new("unknown-char-literal", format!("unknown char literal name: #\\{other}")).into()),
+        }
+    }
+
+    /// Scans a signed integer or float literal, falling back to a symbol
+    /// when the leading `-`/`+`/`.` turns out not to be followed by any
+    /// digits (e.g. `-`, `+list`, `...`).
+    ///
+    /// Handles leading dots (`.5`), trailing dots (`3.`), and signs on
+    /// either, and rejects multiple dots or a dangling exponent marker
+    /// with a message that names the source position, since those are
+    /// the cases that used to silently fall through to a confusing
+    /// "unexpected symbol" error further down the pipeline.
+    fn read_number_or_symbol(&mut self) -> Result<Option<Token>, ShellError> {
+        let start = self.position();
+        let mut buf = String::new();
+        let first = self.advance().unwrap();
+        buf.push(first);
+
+        if first == '0' {
+            if let Some(radix) = self.chars.peek().and_then(|c| radix_for_prefix(*c)) {
+                self.advance();
+                return self.read_radix_int(radix).map(Some);
+            }
+        }
+
+        let mut has_digit = first.is_ascii_digit();
+        let mut dot_count = usize::from(first == '.');
+
+        while matches!(self.chars.peek(), Some(c) if c.is_ascii_digit() || *c == '.') {
+            let c = self.advance().unwrap();
+            if c == '.' {
+                dot_count += 1;
+            } else {
+                has_digit = true;
+            }
+            buf.push(c);
+        }
+
+        if !has_digit {
+            return self.read_symbol_with_prefix(buf).map(Some);
+        }
+
+        if dot_count > 1 {
+            return Err(ParseError::new(
+                "invalid-number-literal",
+                format!("invalid number literal '{buf}' at {start}: too many decimal points"),
+            )
+            .into());
+        }
+
+        let mut is_float = dot_count == 1;
+        if matches!(self.chars.peek(), Some('e') | Some('E')) {
+            buf.push(self.advance().unwrap());
+            if matches!(self.chars.peek(), Some('+') | Some('-')) {
+                buf.push(self.advance().unwrap());
+            }
+            let mut exponent_digits = 0;
+            while matches!(self.chars.peek(), Some(c) if c.is_ascii_digit()) {
+                buf.push(self.advance().unwrap());
+                exponent_digits += 1;
+            }
+            if exponent_digits == 0 {
+                return Err(ParseError::new(
+                    "invalid-number-literal",
+                    format!("invalid number literal '{buf}' at {start}: expected digits after exponent marker"),
+                )
+                .into());
+            }
+            is_float = true;
+        }
+
+        if is_float {
+            buf.parse::<f64>()
+                .map(Token::Float)
+                .map(Some)
+                .map_err(|_| {
+                    ParseError::new("invalid-number-literal", format!("invalid number literal '{buf}' at {start}"))
+                        .into()
+                })
+        } else {
+            buf.parse::<i64>()
+                .map(Token::Int)
+                .map(Some)
+                .map_err(|_| {
+                    ParseError::new("invalid-number-literal", format!("invalid number literal '{buf}' at {start}"))
+                        .into()
+                })
+        }
+    }
+
+    /// Reads the digits of a `0x`/`0o`/`0b`-prefixed integer literal.
+    fn read_radix_int(&mut self, radix: u32) -> Result<Token, ShellError> {
+        let mut digits = String::new();
+        while matches!(self.chars.peek(), Some(c) if c.is_digit(radix)) {
+            digits.push(self.advance().unwrap());
+        }
+        i64::from_str_radix(&digits, radix).map(Token::Int).map_err(|_| {
+            ParseError::new("invalid-number-literal", format!("invalid radix-{radix} literal: {digits}")).into()
+        })
+    }
+
+    /// Reads characters into `buf` until whitespace or a delimiter.
+    fn read_word(&mut self, mut buf: String) -> String {
+        while let Some(&c) = self.chars.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' || c == '[' || c == ']' {
+                break;
+            }
+            buf.push(c);
+            self.advance();
+        }
+        buf
+    }
+
+    fn read_symbol_with_prefix(&mut self, buf: String) -> Result<Token, ShellError> {
+        Ok(symbol_or_bool(fold_symbol_case(self.read_word(buf))))
+    }
+
+    fn read_symbol(&mut self) -> Result<Token, ShellError> {
+        self.read_symbol_with_prefix(String::new())
+    }
+
+    /// Reads a `|weird symbol|` pipe-escaped symbol: everything between the
+    /// pipes is taken literally (spaces and parens included), with `\|` and
+    /// `\\` as the only recognized escapes. Exempt from the `fold-case`
+    /// option, same as a string literal would be -- the whole point of the
+    /// escaping is to preserve the name exactly as written.
+    fn read_piped_symbol(&mut self) -> Result<Token, ShellError> {
+        self.advance(); // opening pipe
+        let mut s = String::new();
+        loop {
+            match self.advance() {
+                Some('|') => return Ok(Token::Symbol(s)),
+                Some('\\') => match self.advance() {
+                    Some('|') => s.push('|'),
+                    Some('\\') => s.push('\\'),
+                    Some(other) => s.push(other),
+                    None => return Err(ParseError::new("unterminated-symbol", "unterminated |symbol|").into()),
+                },
+                Some(c) => s.push(c),
+                None => return Err(ParseError::new("unterminated-symbol", "unterminated |symbol|").into()),
+            }
+        }
+    }
+}
+
+/// Lowercases `s` when this thread's `fold-case` option (set via
+/// `(set-option 'fold-case #t)`) is truthy, for scripts that want
+/// case-insensitive bare symbols. `|piped|` symbols bypass this entirely.
+fn fold_symbol_case(s: String) -> String {
+    match crate::config::get("fold-case") {
+        Some(v) if v.is_truthy() => s.to_lowercase(),
+        _ => s,
+    }
+}
+
+impl Lexer<'_> {
+    /// Reads all of `reader` and tokenizes it. Exists alongside
+    /// [`Lexer::tokenize`] so callers with an `io::Read` (a file, a pipe)
+    /// don't have to buffer into a `String` themselves first.
+    pub fn tokenize_reader<R: std::io::Read>(mut reader: R) -> Result<Vec<Token>, ShellError> {
+        let mut source = String::new();
+        reader.read_to_string(&mut source)?;
+        Lexer::tokenize(&source)
+    }
+}
+
+/// Iterator returned by [`Lexer::token_stream`]; see there for details.
+pub struct TokenStream<'a> {
+    lexer: Lexer<'a>,
+    include_trivia: bool,
+    done: bool,
+}
+
+impl<'a> TokenStream<'a> {
+    fn new(source: &'a str, include_trivia: bool) -> Self {
+        TokenStream {
+            lexer: Lexer::new(source),
+            include_trivia,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for TokenStream<'_> {
+    type Item = Result<TokenInfo, ShellError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.include_trivia {
+            let start = self.lexer.position();
+            if let Some(trivia) = self.lexer.next_trivia() {
+                let category = trivia.category();
+                return Some(Ok(TokenInfo {
+                    item: TokenOrTrivia::Trivia(trivia),
+                    span: Span {
+                        start,
+                        end: self.lexer.position(),
+                    },
+                    category,
+                }));
+            }
+        } else {
+            self.lexer.skip_whitespace_and_comments();
+        }
+
+        let start = self.lexer.position();
+        match self.lexer.next_token() {
+            Ok(Some(tok)) => {
+                let category = tok.category();
+                Some(Ok(TokenInfo {
+                    item: TokenOrTrivia::Token(tok),
+                    span: Span {
+                        start,
+                        end: self.lexer.position(),
+                    },
+                    category,
+                }))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Feeds a lexer with source text arriving in chunks (e.g. one line at a
+/// time from a REPL), re-tokenizing the accumulated buffer on each call.
+///
+/// This isn't incremental in the sense of reusing prior work — every
+/// `drain_tokens` call re-lexes everything seen so far — but it lets a
+/// caller hand over input as it arrives instead of needing the whole
+/// script assembled up front, which is what the REPL's line-at-a-time
+/// input loop actually needs.
+#[derive(Default)]
+pub struct IncrementalLexer {
+    buffer: String,
+}
+
+impl IncrementalLexer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn feed(&mut self, chunk: &str) {
+        self.buffer.push_str(chunk);
+    }
+
+    /// Tokenizes everything fed so far.
+    pub fn drain_tokens(&self) -> Result<Vec<Token>, ShellError> {
+        Lexer::tokenize(&self.buffer)
+    }
+
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+}
+
+fn radix_for_prefix(c: char) -> Option<u32> {
+    match c {
+        'x' | 'X' => Some(16),
+        'o' | 'O' => Some(8),
+        'b' | 'B' => Some(2),
+        _ => None,
+    }
+}
+
+fn symbol_or_bool(s: String) -> Token {
+    match s.as_str() {
+        "#t" => Token::Bool(true),
+        "#f" => Token::Bool(false),
+        _ => Token::Symbol(s),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_block_comments() {
+        let tokens = Lexer::tokenize("(+ #| ignored |# 1 2)").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LParen,
+                Token::Symbol("+".into()),
+                Token::Int(1),
+                Token::Int(2),
+                Token::RParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn block_comments_nest() {
+        let tokens = Lexer::tokenize("#| outer #| inner |# still outer |# 1").unwrap();
+        assert_eq!(tokens, vec![Token::Int(1)]);
+    }
+
+    #[test]
+    fn raw_strings_do_not_process_escapes() {
+        let tokens = Lexer::tokenize(r#"r"a\nb""#).unwrap();
+        assert_eq!(tokens, vec![Token::Str("a\\nb".into())]);
+    }
+
+    #[test]
+    fn strings_can_span_multiple_lines() {
+        let tokens = Lexer::tokenize("\"line one\nline two\"").unwrap();
+        assert_eq!(tokens, vec![Token::Str("line one\nline two".into())]);
+    }
+
+    #[test]
+    fn reads_radix_prefixed_integers() {
+        assert_eq!(Lexer::tokenize("0x1F").unwrap(), vec![Token::Int(31)]);
+        assert_eq!(Lexer::tokenize("0o17").unwrap(), vec![Token::Int(15)]);
+        assert_eq!(Lexer::tokenize("0b101").unwrap(), vec![Token::Int(5)]);
+    }
+
+    #[test]
+    fn reads_scientific_notation_floats() {
+        assert_eq!(Lexer::tokenize("1.5e2").unwrap(), vec![Token::Float(150.0)]);
+        assert_eq!(Lexer::tokenize("2e-3").unwrap(), vec![Token::Float(0.002)]);
+    }
+
+    #[test]
+    fn square_brackets_tokenize_like_parens() {
+        let tokens = Lexer::tokenize("[+ 1 2]").unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::LParen,
+                Token::Symbol("+".into()),
+                Token::Int(1),
+                Token::Int(2),
+                Token::RParen,
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_keyword_tokens() {
+        assert_eq!(Lexer::tokenize(":foo").unwrap(), vec![Token::Keyword("foo".into())]);
+    }
+
+    #[test]
+    fn reads_signed_and_leading_dot_floats() {
+        assert_eq!(Lexer::tokenize("-.5").unwrap(), vec![Token::Float(-0.5)]);
+        assert_eq!(Lexer::tokenize(".5").unwrap(), vec![Token::Float(0.5)]);
+        assert_eq!(Lexer::tokenize("3.").unwrap(), vec![Token::Float(3.0)]);
+        assert_eq!(Lexer::tokenize("-3").unwrap(), vec![Token::Int(-3)]);
+    }
+
+    #[test]
+    fn rejects_multiple_decimal_points() {
+        let err = Lexer::tokenize("1.2.3").unwrap_err().to_string();
+        assert!(err.contains("too many decimal points"), "{err}");
+    }
+
+    #[test]
+    fn rejects_dangling_exponent_marker() {
+        let err = Lexer::tokenize("-3.err").unwrap_err().to_string();
+        assert!(err.contains("expected digits after exponent marker"), "{err}");
+    }
+
+    #[test]
+    fn bare_sign_and_dot_are_symbols() {
+        assert_eq!(Lexer::tokenize("-").unwrap(), vec![Token::Symbol("-".into())]);
+        assert_eq!(Lexer::tokenize("...").unwrap(), vec![Token::Symbol("...".into())]);
+    }
+
+    #[test]
+    fn reads_char_literals() {
+        assert_eq!(Lexer::tokenize(r"#\a").unwrap(), vec![Token::Char('a')]);
+        assert_eq!(Lexer::tokenize(r"#\space").unwrap(), vec![Token::Char(' ')]);
+        assert_eq!(Lexer::tokenize(r"#\newline").unwrap(), vec![Token::Char('\n')]);
+    }
+
+    #[test]
+    fn tokenizes_from_a_reader() {
+        let tokens = Lexer::tokenize_reader("(+ 1 2)".as_bytes()).unwrap();
+        assert_eq!(
+            tokens,
+            vec![Token::LParen, Token::Symbol("+".into()), Token::Int(1), Token::Int(2), Token::RParen]
+        );
+    }
+
+    #[test]
+    fn incremental_lexer_tokenizes_fed_chunks() {
+        let mut lexer = IncrementalLexer::new();
+        lexer.feed("(+ 1 ");
+        lexer.feed("2)");
+        assert_eq!(
+            lexer.drain_tokens().unwrap(),
+            vec![Token::LParen, Token::Symbol("+".into()), Token::Int(1), Token::Int(2), Token::RParen]
+        );
+    }
+
+    #[test]
+    fn tracks_line_and_column_spans() {
+        let tokens = Lexer::tokenize_with_spans("1\n  2").unwrap();
+        assert_eq!(tokens[0].1.start, Position { line: 1, col: 1 });
+        assert_eq!(tokens[1].1.start, Position { line: 2, col: 3 });
+    }
+
+    #[test]
+    fn token_stream_without_trivia_matches_tokenize() {
+        let items: Vec<Token> = Lexer::token_stream("(+ 1 2)", false)
+            .map(|r| match r.unwrap().item {
+                TokenOrTrivia::Token(tok) => tok,
+                TokenOrTrivia::Trivia(_) => panic!("did not ask for trivia"),
+            })
+            .collect();
+        assert_eq!(items, Lexer::tokenize("(+ 1 2)").unwrap());
+    }
+
+    #[test]
+    fn token_stream_with_trivia_reports_comments_and_whitespace() {
+        let items: Vec<TokenInfo> = Lexer::token_stream("(+ 1 ; comment\n 2)", true)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert!(items
+            .iter()
+            .any(|i| matches!(i.item, TokenOrTrivia::Trivia(Trivia::LineComment(_)))));
+        assert!(items
+            .iter()
+            .any(|i| i.category == TokenCategory::Whitespace));
+        assert_eq!(
+            items
+                .iter()
+                .filter(|i| matches!(i.item, TokenOrTrivia::Token(_)))
+                .count(),
+            5
+        );
+    }
+
+    #[test]
+    fn token_categories_are_reported() {
+        let items: Vec<TokenInfo> = Lexer::token_stream(":k 1 \"s\"", false)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            items.iter().map(|i| i.category).collect::<Vec<_>>(),
+            vec![TokenCategory::Keyword, TokenCategory::Number, TokenCategory::String]
+        );
+    }
+
+    #[test]
+    fn emits_datum_comment_token() {
+        let tokens = Lexer::tokenize("#;(ignored) 1").unwrap();
+        assert_eq!(tokens, vec![Token::DatumComment, Token::LParen, Token::Symbol("ignored".into()), Token::RParen, Token::Int(1)]);
+    }
+
+    #[test]
+    fn reads_heredoc_literal() {
+        let tokens = Lexer::tokenize("#<<END\nline one\nline two\nEND\n").unwrap();
+        assert_eq!(tokens, vec![Token::Str("line one\nline two".into())]);
+    }
+
+    #[test]
+    fn heredoc_with_tilde_strips_common_indentation() {
+        let tokens = Lexer::tokenize("#<<~END\n    select 1;\n    select 2;\n    END\n").unwrap();
+        assert_eq!(
+            tokens,
+            vec![Token::Str("select 1;\nselect 2;".into())]
+        );
+    }
+
+    #[test]
+    fn unterminated_heredoc_is_an_error() {
+        let err = Lexer::tokenize("#<<END\nno terminator here").unwrap_err();
+        assert!(matches!(err, ShellError::Parse(e) if e.code == "unterminated-heredoc"));
+    }
+
+    #[test]
+    fn reads_piped_symbol_with_spaces() {
+        let tokens = Lexer::tokenize("|weird symbol|").unwrap();
+        assert_eq!(tokens, vec![Token::Symbol("weird symbol".into())]);
+    }
+
+    #[test]
+    fn piped_symbol_supports_escapes() {
+        let tokens = Lexer::tokenize(r"|a \| b \\ c|").unwrap();
+        assert_eq!(tokens, vec![Token::Symbol(r"a | b \ c".into())]);
+    }
+
+    #[test]
+    fn fold_case_option_lowercases_bare_symbols_but_not_piped_ones() {
+        crate::config::set("fold-case".into(), crate::value::Value::Bool(true));
+        let tokens = Lexer::tokenize("FooBar |StillMixed|").unwrap();
+        crate::config::set("fold-case".into(), crate::value::Value::Bool(false));
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Symbol("foobar".into()),
+                Token::Symbol("StillMixed".into()),
+            ]
+        );
+    }
+}
+
+/// Property tests over arbitrary input, as a safety net alongside the
+/// fixed-input tests above: `tokenize` should never panic, only ever
+/// return `Ok` or a clean `Err`, no matter what bytes it's handed.
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn tokenizing_arbitrary_strings_never_panics(source in ".{0,200}") {
+            let _ = Lexer::tokenize(&source);
+        }
+
+        #[test]
+        fn tokenizing_arbitrary_bytes_never_panics(bytes in proptest::collection::vec(any::<u8>(), 0..200)) {
+            if let Ok(source) = String::from_utf8(bytes) {
+                let _ = Lexer::tokenize(&source);
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(feature = "arena-env")]
+pub mod arena;
+pub mod ast;
+pub mod brace;
+pub mod builtins;
+pub mod callstack;
+pub mod catalog;
+pub mod color;
+pub mod completions;
+pub mod config;
+pub mod convert;
+pub mod crash_report;
+pub mod describe;
+pub mod diagnostics;
+pub mod dynamic;
+pub mod env;
+pub mod error;
+pub mod eval;
+pub mod features;
+pub mod fmt;
+pub mod history;
+pub mod inspect;
+pub mod intern;
+pub mod keymap;
+#[cfg(feature = "repl")]
+pub mod learn;
+pub mod lexaddr;
+pub mod lexer;
+pub mod lint;
+pub mod memo;
+#[cfg(feature = "repl")]
+pub mod meta;
+pub mod native;
+pub mod notify;
+pub mod output;
+pub mod parse_cache;
+pub mod parser;
+pub mod paste;
+pub mod plist;
+pub mod plugin;
+pub mod pretty;
+pub mod profile;
+#[cfg(feature = "repl")]
+pub mod repl;
+pub mod rope;
+pub mod sandbox;
+pub mod serde_value;
+pub mod shell;
+pub mod shellwords;
+pub mod span;
+pub mod suggest;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+#[cfg(feature = "tracing")]
+pub mod trace;
+pub mod transcript;
+pub mod translate;
+pub mod value;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use env::Environment;
+pub use error::ShellError;
+pub use eval::eval;
+pub use parser::Parser;
+pub use shell::Shell;
+pub use value::Value;
@@ -0,0 +1,658 @@
+use crate::builtins;
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::lexaddr::{self, Address};
+use crate::parser::Parser;
+use crate::span::Span;
+use crate::value::Value;
+use std::collections::HashSet;
+
+/// A single static-analysis finding from [`lint`].
+///
+/// `span` is the span of the top-level form the finding came from; lint
+/// doesn't currently track spans for sub-expressions, so findings inside
+/// a large form all point at its start rather than the exact offending
+/// piece.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub rule: &'static str,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Diagnostic {
+    /// A single-line JSON rendering, so editors and other tooling can
+    /// consume lint output as JSON Lines instead of parsing prose.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"rule\":\"{}\",\"message\":{},\"span\":\"{}\"}}",
+            self.rule,
+            json_string(&self.message),
+            self.span,
+        )
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Walks every top-level form parsed from `source` and reports unused
+/// `let` bindings, wrong-arity special forms, always-truthy `if`
+/// conditions, and references to symbols nothing in the script defines.
+///
+/// Scoping is approximate: a name bound by `define`, a `lambda` parameter
+/// list, or a `let` binding is considered known for the rest of the
+/// script, not just its own body, which favors missing a real bug over
+/// flagging a false positive.
+///
+/// Shadowing is additionally reported when this thread's `warn-shadow`
+/// option (set via `(set-option 'warn-shadow #t)`) is truthy, since it is
+/// noisy enough by default -- recursive helpers routinely rebind their own
+/// name, and that is not a bug -- that most scripts don't want it on.
+pub fn lint(source: &str) -> Result<Vec<Diagnostic>, ShellError> {
+    let forms = Parser::parse_all_spanned(source)?;
+    let builtins = builtin_names();
+    let mut known = builtins.clone();
+    let ctx = LintContext {
+        warn_shadow: matches!(crate::config::get("warn-shadow"), Some(v) if v.is_truthy()),
+        builtins,
+    };
+    let mut diagnostics = Vec::new();
+    for form in &forms {
+        walk(&form.value, form.span, &mut known, &mut diagnostics, &ctx);
+    }
+    Ok(diagnostics)
+}
+
+fn builtin_names() -> HashSet<String> {
+    let env = Environment::new_global();
+    builtins::install(&env);
+    env.local_names().into_iter().collect()
+}
+
+/// Settings that stay constant for a whole [`lint`] run, threaded through
+/// `walk`'s recursion alongside the mutable `known` scope approximation.
+struct LintContext {
+    warn_shadow: bool,
+    builtins: HashSet<String>,
+}
+
+/// Records a `shadowed-binding` diagnostic for `name` if shadowing
+/// warnings are enabled and `name` is already known -- either a builtin or
+/// an earlier binding in this same approximate scope.
+fn check_shadow(name: &str, known: &HashSet<String>, span: Span, ctx: &LintContext, diagnostics: &mut Vec<Diagnostic>) {
+    if !ctx.warn_shadow || !known.contains(name) {
+        return;
+    }
+    let message = if ctx.builtins.contains(name) {
+        format!("binding '{name}' shadows the builtin '{name}'")
+    } else {
+        format!("binding '{name}' shadows an outer binding of the same name")
+    };
+    diagnostics.push(Diagnostic {
+        rule: "shadowed-binding",
+        message,
+        span,
+    });
+}
+
+fn walk(value: &Value, span: Span, known: &mut HashSet<String>, diagnostics: &mut Vec<Diagnostic>, ctx: &LintContext) {
+    let items = match value {
+        Value::Symbol(name) => {
+            if !known.contains(name) {
+                diagnostics.push(Diagnostic {
+                    rule: "undefined-symbol",
+                    message: format!("reference to undefined symbol: {name}"),
+                    span,
+                });
+            }
+            return;
+        }
+        Value::List(items) => items,
+        _ => return,
+    };
+
+    if items.is_empty() {
+        return;
+    }
+
+    if let Value::Symbol(head) = &items[0] {
+        match head.as_str() {
+            "quote" | "quasiquote" | "unquote" | "unquote-splicing" => return,
+            "defvar" => {
+                let args = &items[1..];
+                if args.len() != 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("defvar expects 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                if let Value::Symbol(name) = &args[0] {
+                    known.insert(name.clone());
+                }
+                walk(&args[1], span, known, diagnostics, ctx);
+                return;
+            }
+            "fluid-let" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("fluid-let expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                let bindings = match &args[0] {
+                    Value::List(b) => b,
+                    _ => return,
+                };
+                for binding in bindings.iter() {
+                    let pair = match binding {
+                        Value::List(p) if p.len() == 2 => p,
+                        _ => continue,
+                    };
+                    walk(&pair[1], span, known, diagnostics, ctx);
+                    if let Value::Symbol(name) = &pair[0] {
+                        if !known.contains(name) {
+                            diagnostics.push(Diagnostic {
+                                rule: "undefined-symbol",
+                                message: format!("reference to undefined symbol: {name}"),
+                                span,
+                            });
+                        }
+                    }
+                }
+                for expr in &args[1..] {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "with-env" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("with-env expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                if let Value::List(bindings) = &args[0] {
+                    for binding in bindings.iter() {
+                        if let Value::List(pair) = binding {
+                            if let Some(value_expr) = pair.get(1) {
+                                walk(value_expr, span, known, diagnostics, ctx);
+                            }
+                        }
+                    }
+                }
+                for expr in &args[1..] {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "if" => {
+                let args = &items[1..];
+                if !(2..=3).contains(&args.len()) {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("if expects 2 or 3 arguments, got {}", args.len()),
+                        span,
+                    });
+                }
+                if let Some(cond) = args.first() {
+                    if is_always_truthy(cond) {
+                        diagnostics.push(Diagnostic {
+                            rule: "always-true-if",
+                            message: "if condition is a literal that is always truthy".into(),
+                            span,
+                        });
+                    }
+                    walk(cond, span, known, diagnostics, ctx);
+                }
+                for branch in args.iter().skip(1) {
+                    walk(branch, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "define" | "define-constant" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("{head} expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                match &args[0] {
+                    Value::Symbol(name) => {
+                        check_shadow(name, known, span, ctx, diagnostics);
+                        known.insert(name.clone());
+                        walk(&args[1], span, known, diagnostics, ctx);
+                    }
+                    Value::List(sig) => {
+                        if let Some(Value::Symbol(name)) = sig.first() {
+                            check_shadow(name, known, span, ctx, diagnostics);
+                            known.insert(name.clone());
+                        }
+                        for param in sig.iter().skip(1) {
+                            if let Value::Symbol(p) = param {
+                                known.insert(p.clone());
+                            }
+                        }
+                        for body_expr in &args[1..] {
+                            walk(body_expr, span, known, diagnostics, ctx);
+                        }
+                    }
+                    _ => {}
+                }
+                return;
+            }
+            "lambda" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("lambda expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                let body = &args[1..];
+                let (fixed, rest): (&[Value], Option<&Value>) = match &args[0] {
+                    Value::List(params) => (params.as_slice(), None),
+                    Value::DottedList(params, tail) => (params.as_slice(), Some(tail.as_ref())),
+                    symbol @ Value::Symbol(_) => (&[], Some(symbol)),
+                    _ => (&[], None),
+                };
+                if !fixed.is_empty() || rest.is_some() {
+                    let mut names: Vec<String> = fixed
+                        .iter()
+                        .filter_map(|param| match param {
+                            Value::Symbol(p) => Some(p.clone()),
+                            _ => None,
+                        })
+                        .collect();
+                    if let Some(Value::Symbol(r)) = rest {
+                        names.push(r.clone());
+                    }
+                    for name in &names {
+                        // `resolve` always reports `Local` here -- `names`
+                        // is exactly this lambda's own parameter list --
+                        // but running every param through the same
+                        // compile-time resolution pass a call site would
+                        // use keeps this check honest about what "used"
+                        // means: a reference the interpreter could someday
+                        // address directly, not just a textual match.
+                        if matches!(lexaddr::resolve(&names, name), Address::Local(_))
+                            && !body.iter().any(|expr| contains_symbol(expr, name))
+                        {
+                            diagnostics.push(Diagnostic {
+                                rule: "unused-lambda-param",
+                                message: format!("lambda parameter '{name}' is never used in its body"),
+                                span,
+                            });
+                        }
+                        known.insert(name.clone());
+                    }
+                }
+                for body_expr in body {
+                    walk(body_expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "set!" => {
+                let args = &items[1..];
+                if args.len() != 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("set! expects 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                if let Value::Symbol(name) = &args[0] {
+                    if !known.contains(name) {
+                        diagnostics.push(Diagnostic {
+                            rule: "undefined-symbol",
+                            message: format!("reference to undefined symbol: {name}"),
+                            span,
+                        });
+                    }
+                }
+                walk(&args[1], span, known, diagnostics, ctx);
+                return;
+            }
+            "let" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("let expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                let bindings = match &args[0] {
+                    Value::List(b) => b,
+                    _ => return,
+                };
+                let body = &args[1..];
+                for binding in bindings.iter() {
+                    let pair = match binding {
+                        Value::List(p) if p.len() == 2 => p,
+                        _ => continue,
+                    };
+                    walk(&pair[1], span, known, diagnostics, ctx);
+                    if let Value::Symbol(name) = &pair[0] {
+                        if !body.iter().any(|expr| contains_symbol(expr, name)) {
+                            diagnostics.push(Diagnostic {
+                                rule: "unused-let-binding",
+                                message: format!("let binding '{name}' is never used in its body"),
+                                span,
+                            });
+                        }
+                        check_shadow(name, known, span, ctx, diagnostics);
+                        known.insert(name.clone());
+                    }
+                }
+                for expr in body {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "while" => {
+                let args = &items[1..];
+                if args.is_empty() {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: "while expects a condition".into(),
+                        span,
+                    });
+                    return;
+                }
+                for expr in args {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "do" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("do expects at least 2 arguments, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                if let Value::List(specs) = &args[0] {
+                    for spec in specs.iter() {
+                        let parts = match spec {
+                            Value::List(p) if p.len() == 2 || p.len() == 3 => p,
+                            _ => continue,
+                        };
+                        walk(&parts[1], span, known, diagnostics, ctx);
+                        if let Some(step) = parts.get(2) {
+                            walk(step, span, known, diagnostics, ctx);
+                        }
+                        if let Value::Symbol(name) = &parts[0] {
+                            known.insert(name.clone());
+                        }
+                    }
+                }
+                if let Value::List(test_clause) = &args[1] {
+                    for expr in test_clause.iter() {
+                        walk(expr, span, known, diagnostics, ctx);
+                    }
+                }
+                for expr in &args[2..] {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "begin" => {
+                let args = &items[1..];
+                if args.is_empty() {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: "begin expects at least 1 argument, got 0".into(),
+                        span,
+                    });
+                    return;
+                }
+                for expr in args {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "and" | "or" => {
+                for expr in &items[1..] {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "cond" => {
+                for clause in &items[1..] {
+                    let clause_items = match clause {
+                        Value::List(items) if !items.is_empty() => items,
+                        _ => continue,
+                    };
+                    let is_else = matches!(&clause_items[0], Value::Symbol(s) if s == "else");
+                    if !is_else {
+                        walk(&clause_items[0], span, known, diagnostics, ctx);
+                    }
+                    if let [_, Value::Symbol(arrow), proc_expr] = clause_items.as_slice() {
+                        if arrow == "=>" {
+                            walk(proc_expr, span, known, diagnostics, ctx);
+                            continue;
+                        }
+                    }
+                    for expr in &clause_items[1..] {
+                        walk(expr, span, known, diagnostics, ctx);
+                    }
+                }
+                return;
+            }
+            "catch" => {
+                let args = &items[1..];
+                if args.len() != 1 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("catch expects 1 argument, got {}", args.len()),
+                        span,
+                    });
+                }
+                for expr in args {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "with-context" => {
+                let args = &items[1..];
+                if args.len() < 2 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("with-context expects a label and at least 1 body expression, got {}", args.len()),
+                        span,
+                    });
+                    return;
+                }
+                for expr in &args[1..] {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "async" => {
+                let args = &items[1..];
+                if args.len() != 1 {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: format!("async expects 1 argument, got {}", args.len()),
+                        span,
+                    });
+                }
+                for expr in args {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            "parallel" => {
+                let args = &items[1..];
+                if args.is_empty() {
+                    diagnostics.push(Diagnostic {
+                        rule: "bad-arity",
+                        message: "parallel expects at least 1 argument, got 0".into(),
+                        span,
+                    });
+                }
+                for expr in args {
+                    walk(expr, span, known, diagnostics, ctx);
+                }
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    for item in items.iter() {
+        walk(item, span, known, diagnostics, ctx);
+    }
+}
+
+/// Conservative `if`-condition check: only literals that can never be
+/// `#f` at runtime (numbers, strings, `#t`, quoted lists...) count as
+/// "always truthy" -- a symbol, or any list headed by a symbol other than
+/// `quote` (a call, and also a `lambda` form), is not flagged.
+fn is_always_truthy(value: &Value) -> bool {
+    !matches!(value, Value::Bool(false) | Value::Symbol(_))
+        && !matches!(value, Value::List(items) if matches!(items.first(), Some(Value::Symbol(s)) if s != "quote"))
+}
+
+fn contains_symbol(value: &Value, name: &str) -> bool {
+    match value {
+        Value::Symbol(s) => s == name,
+        Value::List(items) => items.iter().any(|item| contains_symbol(item, name)),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_unused_let_binding() {
+        let diagnostics = lint("(let ((x 1) (y 2)) (+ y 1))").unwrap();
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.rule == "unused-let-binding" && d.message.contains("'x'")));
+        assert!(!diagnostics
+            .iter()
+            .any(|d| d.rule == "unused-let-binding" && d.message.contains("'y'")));
+    }
+
+    #[test]
+    fn flags_unused_lambda_param() {
+        let diagnostics = lint("(lambda (x y) (+ y 1))").unwrap();
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.rule == "unused-lambda-param" && d.message.contains("'x'")));
+        assert!(!diagnostics
+            .iter()
+            .any(|d| d.rule == "unused-lambda-param" && d.message.contains("'y'")));
+    }
+
+    #[test]
+    fn flags_bad_arity() {
+        let diagnostics = lint("(if 1)").unwrap();
+        assert!(diagnostics.iter().any(|d| d.rule == "bad-arity"));
+    }
+
+    #[test]
+    fn flags_always_true_if_condition() {
+        let diagnostics = lint("(if 42 1 2)").unwrap();
+        assert!(diagnostics.iter().any(|d| d.rule == "always-true-if"));
+    }
+
+    #[test]
+    fn flags_undefined_symbol() {
+        let diagnostics = lint("(+ unbound-var 1)").unwrap();
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.rule == "undefined-symbol" && d.message.contains("unbound-var")));
+    }
+
+    #[test]
+    fn does_not_flag_known_bindings() {
+        let diagnostics = lint("(define (square x) (* x x)) (square 3)").unwrap();
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn shadowing_a_builtin_is_silent_by_default() {
+        let diagnostics = lint("(define list 5)").unwrap();
+        assert!(!diagnostics.iter().any(|d| d.rule == "shadowed-binding"));
+    }
+
+    #[test]
+    fn warn_shadow_flags_a_define_that_shadows_a_builtin() {
+        crate::config::set("warn-shadow".into(), Value::Bool(true));
+        let diagnostics = lint("(define list 5)").unwrap();
+        crate::config::set("warn-shadow".into(), Value::Bool(false));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.rule == "shadowed-binding" && d.message.contains("builtin")));
+    }
+
+    #[test]
+    fn warn_shadow_flags_a_let_binding_that_shadows_an_outer_one() {
+        crate::config::set("warn-shadow".into(), Value::Bool(true));
+        let diagnostics = lint("(define x 1) (let ((x 2)) x)").unwrap();
+        crate::config::set("warn-shadow".into(), Value::Bool(false));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.rule == "shadowed-binding" && d.message.contains("'x'")));
+    }
+
+    #[test]
+    fn catch_is_not_flagged_as_an_undefined_symbol() {
+        let diagnostics = lint("(catch undefined-thing)").unwrap();
+        assert!(!diagnostics.iter().any(|d| d.message.contains("'catch'") || d.message.contains(": catch")));
+    }
+
+    #[test]
+    fn with_context_is_not_flagged_as_an_undefined_symbol() {
+        let diagnostics = lint("(with-context \"loading\" (define x 1))").unwrap();
+        assert!(!diagnostics.iter().any(|d| d.message.contains("with-context")));
+    }
+
+    #[test]
+    fn with_context_flags_missing_body() {
+        let diagnostics = lint("(with-context \"loading\")").unwrap();
+        assert!(diagnostics.iter().any(|d| d.rule == "bad-arity"));
+    }
+
+    #[test]
+    fn renders_diagnostics_as_json() {
+        let diagnostics = lint("(if 1)").unwrap();
+        let json = diagnostics[0].to_json();
+        assert!(json.contains("\"rule\":\"bad-arity\""));
+    }
+}
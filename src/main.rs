@@ -0,0 +1,239 @@
+use cracked_shell::repl::{self, ReplOptions};
+use std::io::{IsTerminal, Read};
+use std::path::PathBuf;
+
+#[cfg(feature = "tracing")]
+type TraceGuard = cracked_shell::trace::TraceGuard;
+#[cfg(not(feature = "tracing"))]
+type TraceGuard = ();
+
+#[cfg(feature = "tracing")]
+fn install_trace(path: &str) -> TraceGuard {
+    cracked_shell::trace::install(std::path::Path::new(path))
+}
+
+#[cfg(not(feature = "tracing"))]
+fn install_trace(_path: &str) -> TraceGuard {
+    eprintln!("--trace-json requires a build with --features tracing");
+    std::process::exit(1);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1).peekable();
+
+    // Held for the rest of `main` so its `Drop` flushes the trace file on
+    // exit, however the process leaves -- normally or via an early
+    // `std::process::exit` in one of the subcommands below.
+    // `TraceGuard` is `()` without the `tracing` feature, since
+    // `install_trace` always exits in that build -- the `unit_arg`/
+    // `let_unit_value` lints don't see that exit is unreachable, hence the
+    // blanket allow.
+    #[allow(clippy::unit_arg, clippy::let_unit_value)]
+    let _trace_guard: Option<TraceGuard> = if args.peek().map(String::as_str) == Some("--trace-json") {
+        args.next();
+        let path = args.next().unwrap_or_else(|| {
+            eprintln!("--trace-json requires an output file path");
+            std::process::exit(1);
+        });
+        Some(install_trace(&path))
+    } else {
+        None
+    };
+
+    if let Some(first) = args.next() {
+        if first == "fmt" {
+            run_fmt_subcommand(args);
+            return;
+        }
+        if first == "lint" {
+            run_lint_subcommand(args);
+            return;
+        }
+        if first == "translate" {
+            run_translate_subcommand(args);
+            return;
+        }
+        if first == "completions" {
+            run_completions_subcommand(args);
+            return;
+        }
+        if first == "learn" {
+            run_learn_subcommand();
+            return;
+        }
+        return run_repl(std::iter::once(first).chain(args));
+    }
+
+    run_repl(args);
+}
+
+/// `cracked lint FILE [--json]`: prints one JSON-Lines diagnostic per
+/// finding from [`cracked_shell::lint::lint`] and exits non-zero if any
+/// were found. `--json` switches to [`cracked_shell::diagnostics::json_diagnostics`]'s
+/// LSP-shaped output instead -- the same `file`/`range`/`severity`/`code`
+/// fields editors and CI already expect from other tools -- and also
+/// reports a lexer/parser failure as a diagnostic rather than a bare error
+/// line, since without `--json` a parse failure has no `Diagnostic` to
+/// print at all.
+fn run_lint_subcommand(mut args: impl Iterator<Item = String>) {
+    let mut path = None;
+    let mut json = false;
+    for arg in args.by_ref() {
+        match arg.as_str() {
+            "--json" => json = true,
+            _ => path = Some(arg),
+        }
+    }
+    let path = path.unwrap_or_else(|| {
+        eprintln!("lint requires a file path");
+        std::process::exit(1);
+    });
+    let source = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("error reading {path}: {e}");
+        std::process::exit(1);
+    });
+
+    if json {
+        let diagnostics = cracked_shell::diagnostics::json_diagnostics(&path, &source);
+        for diagnostic in &diagnostics {
+            println!("{}", serde_json::to_string(diagnostic).expect("diagnostic serializes"));
+        }
+        if !diagnostics.is_empty() {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    match cracked_shell::lint::lint(&source) {
+        Ok(diagnostics) => {
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostic.to_json());
+            }
+            if !diagnostics.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("error linting {path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `cracked fmt FILE`: prints `FILE` reformatted with canonical
+/// indentation to stdout, as [`cracked_shell::fmt::format_code`].
+fn run_fmt_subcommand(mut args: impl Iterator<Item = String>) {
+    let path = args.next().unwrap_or_else(|| {
+        eprintln!("fmt requires a file path");
+        std::process::exit(1);
+    });
+    let source = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("error reading {path}: {e}");
+        std::process::exit(1);
+    });
+    match cracked_shell::fmt::format_code(&source) {
+        Ok(formatted) => print!("{formatted}"),
+        Err(e) => {
+            eprintln!("error formatting {path}: {e}");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `cracked translate script.sh`: prints a best-effort Cracked Shell Lisp
+/// translation of a POSIX shell script to stdout, via
+/// [`cracked_shell::translate::translate`]. Constructs it can't
+/// translate are left as `;; TODO` comments rather than failing the
+/// whole run, so the output is always something to start editing from.
+fn run_translate_subcommand(mut args: impl Iterator<Item = String>) {
+    let path = args.next().unwrap_or_else(|| {
+        eprintln!("translate requires a file path");
+        std::process::exit(1);
+    });
+    let source = std::fs::read_to_string(&path).unwrap_or_else(|e| {
+        eprintln!("error reading {path}: {e}");
+        std::process::exit(1);
+    });
+    print!("{}", cracked_shell::translate::translate(&source));
+}
+
+/// `cracked completions bash|zsh|fish`: prints a completion script for
+/// `cracked`'s own flags and subcommands, via
+/// [`cracked_shell::completions::generate`]. Scripts embedding this
+/// interpreter as a library get the same generator for their own
+/// commands through the `register-completion`/`completions` builtins.
+fn run_completions_subcommand(mut args: impl Iterator<Item = String>) {
+    let name = args.next().unwrap_or_else(|| {
+        eprintln!("completions requires a shell name (bash, zsh, or fish)");
+        std::process::exit(1);
+    });
+    let shell = cracked_shell::completions::Shell::parse(&name).unwrap_or_else(|| {
+        eprintln!("unknown shell {name:?} (want bash, zsh, or fish)");
+        std::process::exit(1);
+    });
+    print!(
+        "{}",
+        cracked_shell::completions::generate(shell, &cracked_shell::completions::cracked_spec())
+    );
+}
+
+/// `cracked learn`: runs the guided, checkpointed tutorial in
+/// [`cracked_shell::learn`] -- see that module for why its lesson on
+/// "concurrency" stands in for the ticket's pipelines and job control,
+/// neither of which this interpreter has.
+fn run_learn_subcommand() {
+    cracked_shell::learn::run();
+}
+
+fn run_repl(mut args: impl Iterator<Item = String>) {
+    let mut opts = ReplOptions::default();
+    let mut one_liner: Option<String> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--no-rc" => opts.no_rc = true,
+            "--strict" => opts.strict = true,
+            "--no-plugins" => opts.no_plugins = true,
+            "--rc" => {
+                let path = args.next().unwrap_or_else(|| {
+                    eprintln!("--rc requires a file path");
+                    std::process::exit(1);
+                });
+                opts.rc_file = Some(PathBuf::from(path));
+            }
+            "--crash-report" => {
+                let path = args.next().unwrap_or_else(|| {
+                    eprintln!("--crash-report requires a file path");
+                    std::process::exit(1);
+                });
+                opts.crash_report = Some(PathBuf::from(path));
+            }
+            "-e" | "-c" => {
+                let expr = args.next().unwrap_or_else(|| {
+                    eprintln!("{arg} requires an expression argument");
+                    std::process::exit(1);
+                });
+                one_liner = Some(expr);
+            }
+            other => {
+                eprintln!("unrecognized argument: {other}");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    if let Some(expr) = one_liner {
+        std::process::exit(repl::eval_one_liner_and_print(&expr, &opts));
+    }
+
+    if !std::io::stdin().is_terminal() {
+        let mut source = String::new();
+        if let Err(e) = std::io::stdin().read_to_string(&mut source) {
+            eprintln!("error reading stdin: {e}");
+            std::process::exit(1);
+        }
+        std::process::exit(repl::eval_source_and_print(&source, &opts));
+    }
+
+    repl::run(opts);
+}
@@ -0,0 +1,122 @@
+//! `(memoize f)` wraps any callable in a cache keyed on `equal?`
+//! arguments -- see [`Memo`] and the `memoize`/`memo-clear!`/`memo-size`
+//! builtins in [`crate::builtins::memo`].
+//!
+//! The request behind this module also asked for this to back "an
+//! internal cache for expensive derived data like PATH lookups and glob
+//! results," but this tree doesn't have either of those yet: `proc/run`
+//! hands the program name straight to `std::process::Command`, which does
+//! its own PATH search, and there's no globbing builtin (only
+//! `expand-braces`, which expands `{a,b}`/`{1..5}` syntax, not filesystem
+//! wildcards). `memoize` ships as a general combinator here so a future
+//! `which`/`glob` builtin can be wrapped in it rather than growing its
+//! own bespoke cache.
+//!
+//! Lookups are a linear scan comparing each cached argument list with
+//! [`crate::value::values_equal`] rather than a `HashMap`, since `Value`
+//! doesn't implement `Hash` -- nothing else in the interpreter needs it,
+//! and adding one just for this would mean picking a hash consistent
+//! with `equal?`'s structural notion of equality for every variant. Not
+//! worth it for caches that, in practice, hold a handful of distinct
+//! argument lists.
+
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::{values_equal, Value};
+
+pub struct Memo {
+    inner: Value,
+    entries: Vec<(Vec<Value>, Value)>,
+}
+
+impl Memo {
+    pub fn new(inner: Value) -> Memo {
+        Memo {
+            inner,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Returns the cached result for `args` if one's there, else calls
+    /// the wrapped function, caches its result, and returns that.
+    pub fn call(&mut self, args: Vec<Value>, env: &Environment) -> Result<Value, ShellError> {
+        if let Some((_, cached)) = self.entries.iter().find(|(cached_args, _)| args_equal(cached_args, &args)) {
+            return Ok(cached.clone());
+        }
+        let result = crate::eval::apply(&self.inner, args.clone(), env)?;
+        self.entries.push((args, result.clone()));
+        Ok(result)
+    }
+
+    /// Discards every cached result, so the next call for each argument
+    /// list re-invokes the wrapped function -- the hook for invalidating
+    /// a memoized cache once whatever it depends on (a file, an
+    /// environment variable) might have changed.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+fn args_equal(a: &[Value], b: &[Value]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(x, y)| values_equal(x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    thread_local! {
+        static CALLS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn counting_double(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+        CALLS.with(|c| c.set(c.get() + 1));
+        match args.as_slice() {
+            [Value::Int(n)] => Ok(Value::Int(n * 2)),
+            _ => Err(ShellError::Arity("counting_double expects one int".into())),
+        }
+    }
+
+    #[test]
+    fn repeated_equal_args_hit_the_cache() {
+        CALLS.with(|c| c.set(0));
+        let mut memo = Memo::new(Value::Builtin("double", counting_double));
+        let env = Environment::new_global();
+        assert!(matches!(memo.call(vec![Value::Int(3)], &env), Ok(Value::Int(6))));
+        assert!(matches!(memo.call(vec![Value::Int(3)], &env), Ok(Value::Int(6))));
+        assert_eq!(CALLS.with(|c| c.get()), 1);
+        assert_eq!(memo.len(), 1);
+    }
+
+    #[test]
+    fn different_args_each_call_through() {
+        CALLS.with(|c| c.set(0));
+        let mut memo = Memo::new(Value::Builtin("double", counting_double));
+        let env = Environment::new_global();
+        memo.call(vec![Value::Int(1)], &env).unwrap();
+        memo.call(vec![Value::Int(2)], &env).unwrap();
+        assert_eq!(CALLS.with(|c| c.get()), 2);
+        assert_eq!(memo.len(), 2);
+    }
+
+    #[test]
+    fn clear_forces_a_fresh_call() {
+        CALLS.with(|c| c.set(0));
+        let mut memo = Memo::new(Value::Builtin("double", counting_double));
+        let env = Environment::new_global();
+        memo.call(vec![Value::Int(5)], &env).unwrap();
+        memo.clear();
+        assert!(memo.is_empty());
+        memo.call(vec![Value::Int(5)], &env).unwrap();
+        assert_eq!(CALLS.with(|c| c.get()), 2);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::env::Environment;
+use crate::eval::eval;
+use crate::parser::Parser;
+use crate::transcript::Transcript;
+
+/// What the REPL driver should do after handling a meta-command.
+pub enum MetaOutcome {
+    /// The command was handled; keep looping.
+    Handled,
+    /// `,reset` was issued: replace the environment with a fresh one.
+    Reset,
+    /// `,quit` was issued: stop the REPL.
+    Quit,
+}
+
+/// Returns `Some(command_and_args)` if `line` is a meta-command (starts
+/// with `,`), otherwise `None`.
+pub fn parse_meta_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    let rest = line.strip_prefix(',')?;
+    match rest.split_once(char::is_whitespace) {
+        Some((cmd, args)) => Some((cmd, args.trim())),
+        None => Some((rest, "")),
+    }
+}
+
+/// Executes a meta-command against `env`, printing results to stdout.
+pub fn run_meta_command(
+    cmd: &str,
+    args: &str,
+    env: &Environment,
+    transcript: &mut Transcript,
+) -> MetaOutcome {
+    match cmd {
+        "help" => {
+            if args.is_empty() {
+                println!(
+                    "meta commands: ,help ,help NAME ,bindings ,type EXPR ,expand EXPR ,load FILE ,record FILE|off ,profile on|off|reset|report ,reset ,quit"
+                );
+            } else {
+                match crate::builtins::doc_for(args) {
+                    Some((arity, doc)) => println!("{args} ({arity}): {doc}"),
+                    None => eprintln!("no builtin named {args}"),
+                }
+            }
+            MetaOutcome::Handled
+        }
+        "bindings" => {
+            for (depth, frame) in env.frames().enumerate() {
+                let mut names = frame.local_names();
+                names.sort();
+                for name in names {
+                    if depth == 0 {
+                        println!("{name}");
+                    } else {
+                        println!("{name} (depth {depth})");
+                    }
+                }
+            }
+            MetaOutcome::Handled
+        }
+        "type" => {
+            match Parser::parse(args).and_then(|expr| eval(&expr, env)) {
+                Ok(value) => println!("{}", value.type_name()),
+                Err(e) => eprintln!("{e}"),
+            }
+            MetaOutcome::Handled
+        }
+        "expand" => match Parser::parse(args) {
+            Ok(expr) => {
+                println!("{expr}");
+                MetaOutcome::Handled
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                MetaOutcome::Handled
+            }
+        },
+        "load" => {
+            match crate::repl::load_rc_file(&std::path::PathBuf::from(args.trim()), env) {
+                Ok(()) => {}
+                Err(e) => eprintln!("{e}"),
+            }
+            MetaOutcome::Handled
+        }
+        "record" => {
+            if args.trim() == "off" {
+                transcript.stop();
+            } else if let Err(e) = transcript.start(std::path::Path::new(args.trim())) {
+                eprintln!("{e}");
+            }
+            MetaOutcome::Handled
+        }
+        "profile" => {
+            match args.trim() {
+                "on" => crate::profile::enable(),
+                "off" => crate::profile::disable(),
+                "reset" => crate::profile::reset(),
+                "report" | "" => println!("{}", crate::profile::report()),
+                other => eprintln!("usage: ,profile on|off|reset|report (got {other:?})"),
+            }
+            MetaOutcome::Handled
+        }
+        "reset" => MetaOutcome::Reset,
+        "quit" => MetaOutcome::Quit,
+        other => {
+            eprintln!("unknown meta command: ,{other}");
+            MetaOutcome::Handled
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_meta_line_with_args() {
+        assert_eq!(parse_meta_line(",type (+ 1 2)"), Some(("type", "(+ 1 2)")));
+    }
+
+    #[test]
+    fn parses_meta_line_without_args() {
+        assert_eq!(parse_meta_line(",quit"), Some(("quit", "")));
+    }
+
+    #[test]
+    fn non_meta_line_is_none() {
+        assert_eq!(parse_meta_line("(+ 1 2)"), None);
+    }
+
+    #[test]
+    fn help_with_a_builtin_name_is_handled() {
+        let env = Environment::new_global();
+        let mut transcript = Transcript::new();
+        assert!(matches!(
+            run_meta_command("help", "cons", &env, &mut transcript),
+            MetaOutcome::Handled
+        ));
+    }
+
+    #[test]
+    fn help_with_an_unknown_name_is_still_handled() {
+        let env = Environment::new_global();
+        let mut transcript = Transcript::new();
+        assert!(matches!(
+            run_meta_command("help", "not-a-real-builtin", &env, &mut transcript),
+            MetaOutcome::Handled
+        ));
+    }
+
+    #[test]
+    fn profile_on_off_reset_and_report_are_all_handled() {
+        crate::profile::disable();
+        crate::profile::reset();
+        let env = Environment::new_global();
+        let mut transcript = Transcript::new();
+        assert!(matches!(run_meta_command("profile", "on", &env, &mut transcript), MetaOutcome::Handled));
+        assert!(crate::profile::is_enabled());
+        assert!(matches!(run_meta_command("profile", "report", &env, &mut transcript), MetaOutcome::Handled));
+        assert!(matches!(run_meta_command("profile", "reset", &env, &mut transcript), MetaOutcome::Handled));
+        assert!(matches!(run_meta_command("profile", "off", &env, &mut transcript), MetaOutcome::Handled));
+        assert!(!crate::profile::is_enabled());
+        crate::profile::disable();
+        crate::profile::reset();
+    }
+}
@@ -0,0 +1,233 @@
+//! Automatic `Value` <-> Rust type conversion behind [`crate::Shell::register_fn`],
+//! so an embedder can write `shell.register_fn("double", |n: i64| n * 2)`
+//! instead of matching on `Vec<Value>` by hand the way builtins in
+//! [`crate::builtins`] do.
+//!
+//! Argument types are owned (`String`, not `&str`): the closure a
+//! registered function is wrapped in gets called with a fresh owned
+//! `Vec<Value>` every time, so a borrowed argument would need its `Fn`
+//! bound quantified over a fresh lifetime per call -- doable, but it
+//! roughly doubles the macro below for a convenience `&str` saves over
+//! `String`. Pass `String` instead.
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+use std::fmt;
+use std::rc::Rc;
+
+/// The type behind [`Value::Native`]: like [`crate::value::Builtin`], but
+/// boxed so it can close over state the `fn`-pointer form can't.
+pub type NativeFn = dyn Fn(Vec<Value>, &Environment) -> Result<Value, ShellError>;
+
+/// Converts a single [`Value`] argument into `Self`, or reports a type
+/// error naming what was expected.
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, ShellError>;
+}
+
+macro_rules! impl_from_value {
+    ($ty:ty, $variant:ident, $expected:literal) => {
+        impl FromValue for $ty {
+            fn from_value(value: &Value) -> Result<Self, ShellError> {
+                match value {
+                    Value::$variant(v) => Ok(v.clone()),
+                    other => Err(ShellError::Eval(format!(
+                        "expected {}, got {}",
+                        $expected,
+                        other.type_name()
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+impl_from_value!(i64, Int, "an int");
+impl_from_value!(f64, Float, "a float");
+impl_from_value!(bool, Bool, "a bool");
+impl_from_value!(char, Char, "a char");
+impl_from_value!(String, Str, "a string");
+
+impl FromValue for Value {
+    fn from_value(value: &Value) -> Result<Self, ShellError> {
+        Ok(value.clone())
+    }
+}
+
+/// Converts `Self` into a [`Value`] to return from a registered function.
+pub trait IntoValue {
+    fn into_value(self) -> Value;
+}
+
+macro_rules! impl_into_value {
+    ($ty:ty, $variant:ident) => {
+        impl IntoValue for $ty {
+            fn into_value(self) -> Value {
+                Value::$variant(self)
+            }
+        }
+    };
+}
+
+impl_into_value!(i64, Int);
+impl_into_value!(f64, Float);
+impl_into_value!(bool, Bool);
+impl_into_value!(char, Char);
+impl_into_value!(String, Str);
+
+impl IntoValue for Value {
+    fn into_value(self) -> Value {
+        self
+    }
+}
+
+impl IntoValue for () {
+    fn into_value(self) -> Value {
+        Value::Nil
+    }
+}
+
+/// What a registered function may return: a bare [`IntoValue`] type, or a
+/// `Result` of one -- `Err` becomes a [`ShellError::Eval`] via `E`'s
+/// [`fmt::Display`].
+pub trait IntoReturn {
+    fn into_return(self) -> Result<Value, ShellError>;
+}
+
+impl<T: IntoValue> IntoReturn for T {
+    fn into_return(self) -> Result<Value, ShellError> {
+        Ok(self.into_value())
+    }
+}
+
+impl<T: IntoValue, E: fmt::Display> IntoReturn for Result<T, E> {
+    fn into_return(self) -> Result<Value, ShellError> {
+        self.map(IntoValue::into_value).map_err(|e| ShellError::Eval(e.to_string()))
+    }
+}
+
+/// Implemented once per arity (see the `impl_into_native!` calls below)
+/// for any `Fn(Args...) -> R` whose arguments implement [`FromValue`] and
+/// whose return type implements [`IntoReturn`] -- what
+/// [`crate::Shell::register_fn`] accepts. Also implemented directly for
+/// the same raw `fn(Vec<Value>, &Environment) -> Result<Value, ShellError>`
+/// signature every builtin in [`crate::builtins`] uses, so a plain
+/// [`crate::value::Builtin`] can still be passed through unconverted.
+pub trait IntoNative<Args> {
+    fn into_native(self) -> Rc<NativeFn>;
+}
+
+/// Marker `Args` for the [`IntoNative`] impl that passes a raw
+/// `fn(Vec<Value>, &Environment) -> Result<Value, ShellError>` through
+/// unconverted, distinguishing it from the per-arity tuple impls below.
+pub struct Raw;
+
+impl<F> IntoNative<Raw> for F
+where
+    F: Fn(Vec<Value>, &Environment) -> Result<Value, ShellError> + 'static,
+{
+    fn into_native(self) -> Rc<NativeFn> {
+        Rc::new(self)
+    }
+}
+
+macro_rules! impl_into_native {
+    ($count:expr; $($arg:ident : $idx:tt),*) => {
+        impl<F, R, $($arg),*> IntoNative<($($arg,)*)> for F
+        where
+            F: Fn($($arg),*) -> R + 'static,
+            $($arg: FromValue,)*
+            R: IntoReturn,
+        {
+            #[allow(non_snake_case)]
+            fn into_native(self) -> Rc<NativeFn> {
+                Rc::new(move |args: Vec<Value>, _env: &Environment| {
+                    if args.len() != $count {
+                        return Err(ShellError::Arity(format!(
+                            "expected {} argument(s), got {}",
+                            $count,
+                            args.len()
+                        )));
+                    }
+                    $(let $arg = <$arg as FromValue>::from_value(&args[$idx])?;)*
+                    self($($arg),*).into_return()
+                })
+            }
+        }
+    };
+}
+
+impl_into_native!(0;);
+impl_into_native!(1; A:0);
+impl_into_native!(2; A:0, B:1);
+impl_into_native!(3; A:0, B:1, C:2);
+impl_into_native!(4; A:0, B:1, C:2, D:3);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(f: Rc<NativeFn>, args: Vec<Value>) -> Result<Value, ShellError> {
+        let env = Environment::new_global();
+        f(args, &env)
+    }
+
+    #[test]
+    fn a_zero_arg_closure_converts() {
+        let f = (|| 42i64).into_native();
+        assert!(matches!(call(f, vec![]), Ok(Value::Int(42))));
+    }
+
+    #[test]
+    fn a_one_arg_closure_converts_its_argument_and_return_value() {
+        let f = (|n: i64| n * 2).into_native();
+        assert!(matches!(call(f, vec![Value::Int(21)]), Ok(Value::Int(42))));
+    }
+
+    #[test]
+    fn a_two_arg_closure_can_capture_state() {
+        let total = Rc::new(std::cell::Cell::new(0i64));
+        let counted = total.clone();
+        let f = (move |a: i64, b: i64| {
+            counted.set(counted.get() + 1);
+            a + b
+        })
+        .into_native();
+        assert!(matches!(call(f, vec![Value::Int(1), Value::Int(2)]), Ok(Value::Int(3))));
+        assert_eq!(total.get(), 1);
+    }
+
+    #[test]
+    fn wrong_argument_count_is_an_arity_error() {
+        let f = (|n: i64| n).into_native();
+        assert!(matches!(call(f, vec![]), Err(ShellError::Arity(_))));
+    }
+
+    #[test]
+    fn wrong_argument_type_is_an_eval_error() {
+        let f = (|n: i64| n).into_native();
+        assert!(matches!(call(f, vec![Value::Str("x".into())]), Err(ShellError::Eval(_))));
+    }
+
+    #[test]
+    fn a_result_returning_closure_surfaces_its_error() {
+        let f = (|n: i64| -> Result<i64, String> {
+            if n < 0 {
+                Err("negative".to_string())
+            } else {
+                Ok(n)
+            }
+        })
+        .into_native();
+        assert!(matches!(call(f, vec![Value::Int(-1)]), Err(ShellError::Eval(ref msg)) if msg == "negative"));
+    }
+
+    #[test]
+    fn a_raw_builtin_signature_passes_through_unconverted() {
+        fn raw(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+            Ok(args.into_iter().next().unwrap_or(Value::Nil))
+        }
+        let f = raw.into_native();
+        assert!(matches!(call(f, vec![Value::Int(7)]), Ok(Value::Int(7))));
+    }
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+/// Commands that run at least this long trigger a completion notification.
+pub const LONG_COMMAND_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Emits a terminal bell plus an OSC 9 notification escape carrying
+/// `message`, which most terminal emulators surface as a desktop
+/// notification. There is no cross-platform desktop notification API
+/// wired in, so this terminal-native escape sequence is the honest
+/// portable approximation.
+pub fn notify(message: &str) {
+    print!("\x07\x1b]9;{message}\x1b\\");
+    use std::io::Write;
+    std::io::stdout().flush().ok();
+}
+
+/// Notifies only if `elapsed` reached [`LONG_COMMAND_THRESHOLD`]. Returns
+/// whether a notification was sent, so callers and tests can observe the
+/// decision without scraping stdout.
+pub fn notify_if_long(elapsed: Duration, message: &str) -> bool {
+    if elapsed >= LONG_COMMAND_THRESHOLD {
+        notify(message);
+        true
+    } else {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_commands_do_not_notify() {
+        assert!(!notify_if_long(Duration::from_millis(10), "done"));
+    }
+
+    #[test]
+    fn long_commands_notify() {
+        assert!(notify_if_long(Duration::from_secs(6), "done"));
+    }
+}
@@ -0,0 +1,79 @@
+//! Redirects what `print`/`pp`/`describe` (see [`crate::builtins::introspect`])
+//! write away from the process's real stdout -- used by
+//! [`crate::Shell::capture_output`] so an embedder can read a script's
+//! output as a `String` instead of scraping the host process's stdout.
+//! `inspect` is left out: it reads from stdin as well as writing, so it
+//! has no sensible behavior once stdout is redirected into a closure.
+use std::cell::RefCell;
+
+type Sink = Box<dyn FnMut(&str)>;
+
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = RefCell::new(None);
+}
+
+/// Installs `sink` to receive every future [`write`]/[`writeln`] call in
+/// this thread, replacing any sink already installed.
+pub fn set_sink(sink: impl FnMut(&str) + 'static) {
+    SINK.with(|slot| *slot.borrow_mut() = Some(Box::new(sink)));
+}
+
+/// Removes any installed sink, so output goes back to the real stdout.
+pub fn clear_sink() {
+    SINK.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Writes `text` to the installed sink, or to stdout if none is installed.
+pub fn write(text: &str) {
+    SINK.with(|slot| {
+        let mut slot = slot.borrow_mut();
+        match slot.as_mut() {
+            Some(sink) => sink(text),
+            None => print!("{text}"),
+        }
+    });
+}
+
+/// Like [`write`], with a trailing newline.
+pub fn writeln(text: &str) {
+    write(text);
+    write("\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell as StdRefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn writeln_appends_a_newline() {
+        let captured = Rc::new(StdRefCell::new(String::new()));
+        let sink = captured.clone();
+        set_sink(move |s| sink.borrow_mut().push_str(s));
+        writeln("hello");
+        clear_sink();
+        assert_eq!(*captured.borrow(), "hello\n");
+    }
+
+    #[test]
+    fn multiple_writes_accumulate_in_order() {
+        let captured = Rc::new(StdRefCell::new(String::new()));
+        let sink = captured.clone();
+        set_sink(move |s| sink.borrow_mut().push_str(s));
+        write("a");
+        write("b");
+        clear_sink();
+        assert_eq!(*captured.borrow(), "ab");
+    }
+
+    #[test]
+    fn clear_sink_restores_default_behavior() {
+        let captured = Rc::new(StdRefCell::new(String::new()));
+        let sink = captured.clone();
+        set_sink(move |s| sink.borrow_mut().push_str(s));
+        clear_sink();
+        write("unseen by the sink");
+        assert_eq!(*captured.borrow(), "");
+    }
+}
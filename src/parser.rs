@@ -0,0 +1,662 @@
+use crate::error::{ParseError, ShellError};
+use crate::lexer::{Lexer, Token};
+use crate::span::{Span, Spanned};
+use crate::value::Value;
+
+/// How deeply nested lists may go before [`Parser`] gives up with a clean
+/// error instead of overflowing the Rust call stack on pathological input
+/// like tens of thousands of open parens.
+const DEFAULT_MAX_DEPTH: usize = 512;
+
+/// Recursive-descent parser over a flat token stream.
+pub struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    max_depth: usize,
+}
+
+impl Parser {
+    pub fn new(tokens: Vec<Token>) -> Parser {
+        Parser {
+            tokens,
+            pos: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    /// Like [`Parser::new`], but with a caller-chosen nesting limit instead
+    /// of [`DEFAULT_MAX_DEPTH`].
+    pub fn with_max_depth(tokens: Vec<Token>, max_depth: usize) -> Parser {
+        Parser {
+            tokens,
+            pos: 0,
+            max_depth,
+        }
+    }
+
+    /// Parses a single expression from `source`, ignoring anything after it.
+    pub fn parse(source: &str) -> Result<Value, ShellError> {
+        let tokens = Lexer::tokenize(source)?;
+        let mut parser = Parser::new(tokens);
+        parser.parse_expr()
+    }
+
+    /// Like [`Parser::parse`], but with a caller-chosen nesting limit
+    /// instead of [`DEFAULT_MAX_DEPTH`].
+    pub fn parse_with_max_depth(source: &str, max_depth: usize) -> Result<Value, ShellError> {
+        let tokens = Lexer::tokenize(source)?;
+        let mut parser = Parser::with_max_depth(tokens, max_depth);
+        parser.parse_expr()
+    }
+
+    /// Like [`Parser::parse`], but errors if any tokens remain after the
+    /// first expression instead of silently ignoring them. Intended for
+    /// inputs that are supposed to be exactly one expression -- a `-e`
+    /// one-liner, say -- where leftover tokens are almost always a typo
+    /// (an extra close paren, a second form that should have been wrapped
+    /// together) rather than intentional multi-form input; `parse_all`
+    /// remains the right call for sources that really do hold many forms.
+    pub fn parse_strict(source: &str) -> Result<Value, ShellError> {
+        let tokens_with_spans = Lexer::tokenize_with_spans(source)?;
+        let spans: Vec<Span> = tokens_with_spans.iter().map(|(_, span)| *span).collect();
+        let tokens: Vec<Token> = tokens_with_spans.into_iter().map(|(tok, _)| tok).collect();
+
+        let mut parser = Parser::new(tokens);
+        let value = parser.parse_expr()?;
+        if parser.pos < parser.tokens.len() {
+            return Err(ParseError::expected_found(
+                "trailing-input",
+                format!("unexpected trailing input at {}", spans[parser.pos].start),
+                "end of input",
+                format!("more input at {}", spans[parser.pos].start),
+            )
+            .into());
+        }
+        Ok(value)
+    }
+
+    /// Parses every top-level expression in `source`, in order.
+    pub fn parse_all(source: &str) -> Result<Vec<Value>, ShellError> {
+        let tokens = Lexer::tokenize(source)?;
+        let mut parser = Parser::new(tokens);
+        let mut forms = Vec::new();
+        while parser.peek().is_some() {
+            forms.push(parser.parse_expr()?);
+        }
+        Ok(forms)
+    }
+
+    /// Parses every top-level expression in `source`, each annotated with
+    /// the source span it was read from.
+    pub fn parse_all_spanned(source: &str) -> Result<Vec<Spanned<Value>>, ShellError> {
+        let tokens_with_spans = Lexer::tokenize_with_spans(source)?;
+        let spans: Vec<Span> = tokens_with_spans.iter().map(|(_, span)| *span).collect();
+        let tokens: Vec<Token> = tokens_with_spans.into_iter().map(|(tok, _)| tok).collect();
+
+        let mut parser = Parser::new(tokens);
+        let mut forms = Vec::new();
+        while parser.peek().is_some() {
+            let start_idx = parser.pos;
+            let value = parser.parse_expr()?;
+            let end_idx = parser.pos - 1;
+            forms.push(Spanned {
+                value,
+                span: Span {
+                    start: spans[start_idx].start,
+                    end: spans[end_idx].end,
+                },
+            });
+        }
+        Ok(forms)
+    }
+
+    /// Parses every top-level expression in `source`, recovering from
+    /// errors instead of stopping at the first one, so a caller (e.g. a
+    /// linter or a "show me everything wrong" command) can see every
+    /// diagnostic in one pass. Returns whatever forms parsed successfully
+    /// alongside every error encountered, in source order.
+    pub fn parse_all_recovering(source: &str) -> (Vec<Value>, Vec<ShellError>) {
+        let tokens = match Lexer::tokenize(source) {
+            Ok(tokens) => tokens,
+            Err(e) => return (Vec::new(), vec![e]),
+        };
+
+        let mut parser = Parser::new(tokens);
+        let mut forms = Vec::new();
+        let mut errors = Vec::new();
+        while parser.peek().is_some() {
+            match parser.parse_expr() {
+                Ok(value) => forms.push(value),
+                Err(e) => {
+                    errors.push(e);
+                    parser.synchronize();
+                }
+            }
+        }
+        (forms, errors)
+    }
+
+    /// Below this many top-level forms, [`Parser::parse_all_parallel`]
+    /// just calls [`Parser::parse_all_recovering`] directly -- spinning up
+    /// worker threads for a handful of forms costs more than it saves.
+    const PARALLEL_MIN_FORMS: usize = 64;
+
+    /// Like [`Parser::parse_all_recovering`], but splits `source` into
+    /// contiguous groups of top-level forms and parses each group on its
+    /// own thread, for multi-thousand-line scripts and `load`-heavy
+    /// startups where parsing -- building up the `Value` tree, not just
+    /// tokenizing -- is the dominant cost. Forms and diagnostics come back
+    /// in source order, exactly as [`Parser::parse_all_recovering`] would
+    /// return them, just computed concurrently.
+    ///
+    /// Lexing itself stays single-threaded: the lexer has stateful lexical
+    /// forms (heredocs, block and datum comments, piped `|...|` symbols,
+    /// `#\(`-style char literals) that make guessing safe split points in
+    /// *raw* source text error-prone. The already-tokenized stream has
+    /// none of that ambiguity -- it's flat, plain data -- and tokenizing
+    /// is cheap next to the allocation-heavy work of building nested
+    /// `Value::List`s, so splitting after lexing still parallelizes the
+    /// expensive part.
+    ///
+    /// `Value` is `Rc`-based and therefore not `Send`, so a worker thread
+    /// can't hand its parsed forms back directly; each renders its forms
+    /// to source text instead (a `String` is `Send`) and the caller's
+    /// thread parses that back -- the same crossing
+    /// [`crate::eval::spawn_isolated`] uses to get `async`/`parallel`
+    /// results across a thread boundary.
+    pub fn parse_all_parallel(source: &str) -> (Vec<Value>, Vec<ShellError>) {
+        let tokens = match Lexer::tokenize(source) {
+            Ok(tokens) => tokens,
+            Err(e) => return (Vec::new(), vec![e]),
+        };
+
+        let boundaries = Self::top_level_boundaries(&tokens);
+        let worker_count = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        if boundaries.len() < Self::PARALLEL_MIN_FORMS || worker_count <= 1 {
+            return Self::parse_all_recovering(source);
+        }
+
+        let chunk_forms = boundaries.len().div_ceil(worker_count);
+        let mut start = 0;
+        let handles: Vec<_> = boundaries
+            .chunks(chunk_forms)
+            .map(|chunk| {
+                let end = *chunk.last().expect("chunks() never yields an empty slice");
+                let chunk_tokens = tokens[start..end].to_vec();
+                start = end;
+                std::thread::spawn(move || Self::parse_chunk(chunk_tokens))
+            })
+            .collect();
+
+        let mut forms = Vec::new();
+        let mut errors = Vec::new();
+        for handle in handles {
+            let (rendered, chunk_errors) = handle.join().expect("parser worker thread panicked");
+            for text in rendered {
+                match Parser::parse(&text) {
+                    Ok(value) => forms.push(value),
+                    Err(e) => errors.push(e),
+                }
+            }
+            errors.extend(chunk_errors);
+        }
+        // Tokens after the last boundary belong to a form that never
+        // closed; parse them too so its diagnostic is not dropped.
+        if start < tokens.len() {
+            let (rendered, chunk_errors) = Self::parse_chunk(tokens[start..].to_vec());
+            for text in rendered {
+                match Parser::parse(&text) {
+                    Ok(value) => forms.push(value),
+                    Err(e) => errors.push(e),
+                }
+            }
+            errors.extend(chunk_errors);
+        }
+        (forms, errors)
+    }
+
+    /// Parses every top-level form out of `tokens` on whatever thread
+    /// calls this, recovering from errors the same way
+    /// [`Parser::parse_all_recovering`] does. Used by
+    /// [`Parser::parse_all_parallel`]'s worker threads; forms come back
+    /// rendered to text rather than as `Value`s since `Value` can't cross
+    /// a thread boundary.
+    fn parse_chunk(tokens: Vec<Token>) -> (Vec<String>, Vec<ShellError>) {
+        let mut parser = Parser::new(tokens);
+        let mut rendered = Vec::new();
+        let mut errors = Vec::new();
+        while parser.peek().is_some() {
+            match parser.parse_expr() {
+                Ok(value) => rendered.push(value.to_string()),
+                Err(e) => {
+                    errors.push(e);
+                    parser.synchronize();
+                }
+            }
+        }
+        (rendered, errors)
+    }
+
+    /// Finds the token index immediately after each top-level form in
+    /// `tokens`, so [`Parser::parse_all_parallel`] can split the stream
+    /// into contiguous chunks without cutting a form in half.
+    ///
+    /// `remaining` tracks how many more terminal units (an atom, or a
+    /// balanced list) are needed to finish the form currently being
+    /// scanned, starting at 1. A `quote`/`backtick`/`comma`/`comma-at`
+    /// prefix doesn't change it -- per [`Parser::parse_expr_at`], its
+    /// operand *is* the very next terminal unit, no extra one required.
+    /// `#;` does change it: `parse_expr_at` handles a datum comment with
+    /// two sequential recursive calls (discard one unit, then still parse
+    /// the form's real value), so it needs one additional unit beyond
+    /// whatever was already pending.
+    fn top_level_boundaries(tokens: &[Token]) -> Vec<usize> {
+        let mut boundaries = Vec::new();
+        let mut depth: usize = 0;
+        let mut remaining: usize = 1;
+        for (i, tok) in tokens.iter().enumerate() {
+            if depth > 0 {
+                match tok {
+                    Token::LParen => depth += 1,
+                    Token::RParen => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    remaining -= 1;
+                }
+            } else {
+                match tok {
+                    Token::LParen => depth = 1,
+                    Token::Quote | Token::Backtick | Token::Comma | Token::CommaAt => {}
+                    Token::DatumComment => remaining += 1,
+                    _ => remaining -= 1,
+                }
+            }
+            if depth == 0 && remaining == 0 {
+                boundaries.push(i + 1);
+                remaining = 1;
+            }
+        }
+        boundaries
+    }
+
+    /// Skips tokens until the next likely start of a top-level form, so
+    /// `parse_all_recovering` can keep going after an error instead of
+    /// re-failing on the same malformed input forever.
+    fn synchronize(&mut self) {
+        while let Some(tok) = self.peek() {
+            if matches!(tok, Token::LParen) {
+                return;
+            }
+            self.advance();
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn parse_expr(&mut self) -> Result<Value, ShellError> {
+        self.parse_expr_at(0)
+    }
+
+    fn parse_expr_at(&mut self, depth: usize) -> Result<Value, ShellError> {
+        match self.advance() {
+            Some(Token::LParen) => self.parse_list_at(depth + 1),
+            Some(Token::Quote) => {
+                let quoted = self.parse_expr_at(depth)?;
+                Ok(Value::list(vec![Value::Symbol("quote".into()), quoted]))
+            }
+            Some(Token::Backtick) => {
+                let quoted = self.parse_expr_at(depth)?;
+                Ok(Value::list(vec![Value::Symbol("quasiquote".into()), quoted]))
+            }
+            Some(Token::Comma) => {
+                let quoted = self.parse_expr_at(depth)?;
+                Ok(Value::list(vec![Value::Symbol("unquote".into()), quoted]))
+            }
+            Some(Token::CommaAt) => {
+                let quoted = self.parse_expr_at(depth)?;
+                Ok(Value::list(vec![
+                    Value::Symbol("unquote-splicing".into()),
+                    quoted,
+                ]))
+            }
+            Some(Token::RParen) => Err(unexpected_rparen()),
+            Some(Token::DatumComment) => {
+                self.parse_expr_at(depth)?;
+                self.parse_expr_at(depth)
+            }
+            Some(Token::Symbol(s)) => Ok(Value::Symbol(s)),
+            Some(Token::Keyword(s)) => Ok(Value::Keyword(s)),
+            Some(Token::Int(i)) => Ok(Value::Int(i)),
+            Some(Token::Float(n)) => Ok(Value::Float(n)),
+            Some(Token::Str(s)) => Ok(Value::Str(s)),
+            Some(Token::Bool(b)) => Ok(Value::Bool(b)),
+            Some(Token::Char(c)) => Ok(Value::Char(c)),
+            None => Err(unexpected_eof()),
+        }
+    }
+
+    fn parse_list_at(&mut self, depth: usize) -> Result<Value, ShellError> {
+        if depth > self.max_depth {
+            return Err(max_depth_exceeded(self.max_depth));
+        }
+        let mut items = Vec::new();
+        loop {
+            match self.peek() {
+                Some(Token::RParen) => {
+                    self.advance();
+                    return Ok(Value::list(items));
+                }
+                Some(Token::Symbol(s)) if s == "." => {
+                    self.advance();
+                    if items.is_empty() {
+                        return Err(dot_at_start_of_list());
+                    }
+                    let tail = self.parse_expr_at(depth)?;
+                    match self.advance() {
+                        Some(Token::RParen) => return Ok(Value::dotted(items, tail)),
+                        found => return Err(expected_rparen_after_dotted_tail(&found)),
+                    }
+                }
+                None => return Err(unterminated_list()),
+                _ => items.push(self.parse_expr_at(depth)?),
+            }
+        }
+    }
+}
+
+/// Describes a token for an "expected X, found Y" message. Kept out of
+/// line (not inlined into the recursive parse functions) for the same
+/// reason the error constructors below are: it's dead weight on every
+/// stack frame of a deeply nested parse, not just the one that actually
+/// hits the error.
+#[inline(never)]
+fn describe_token(token: &Option<Token>) -> String {
+    match token {
+        Some(token) => format!("{token:?}"),
+        None => "end of input".to_string(),
+    }
+}
+
+/// Each of these builds a [`ParseError`] out of line from
+/// [`Parser::parse_expr_at`]/[`Parser::parse_list_at`], which recurse once
+/// per nesting level: inlining a multi-field [`ParseError`] construction
+/// into either would add its stack footprint to every frame of that
+/// recursion, not just the one frame that actually errors, turning
+/// thousands-deep nesting into a stack overflow well before
+/// [`DEFAULT_MAX_DEPTH`] is reached.
+#[inline(never)]
+fn unexpected_rparen() -> ShellError {
+    ParseError::expected_found("unexpected-token", "unexpected ')'", "an expression", "')'").into()
+}
+
+#[inline(never)]
+fn unexpected_eof() -> ShellError {
+    ParseError::expected_found("unexpected-eof", "unexpected end of input", "an expression", "end of input").into()
+}
+
+#[inline(never)]
+fn max_depth_exceeded(max_depth: usize) -> ShellError {
+    ParseError::new(
+        "max-depth-exceeded",
+        format!("exceeded maximum nesting depth of {max_depth} while parsing a list"),
+    )
+    .into()
+}
+
+#[inline(never)]
+fn dot_at_start_of_list() -> ShellError {
+    ParseError::new("unexpected-token", "unexpected '.' at start of list").into()
+}
+
+#[inline(never)]
+fn expected_rparen_after_dotted_tail(found: &Option<Token>) -> ShellError {
+    ParseError::expected_found(
+        "unexpected-token",
+        "expected ')' after dotted tail",
+        "')'",
+        describe_token(found),
+    )
+    .into()
+}
+
+#[inline(never)]
+fn unterminated_list() -> ShellError {
+    ParseError::expected_found("unterminated-list", "unterminated list", "')'", "end of input").into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn datum_comment_skips_next_expression() {
+        let value = Parser::parse("(+ 1 #;2 3)").unwrap();
+        assert_eq!(value.to_string(), "(+ 1 3)");
+    }
+
+    #[test]
+    fn parses_char_literals() {
+        let value = Parser::parse(r"#\a").unwrap();
+        assert!(matches!(value, Value::Char('a')));
+        assert_eq!(Parser::parse(r"#\space").unwrap().to_string(), "#\\space");
+    }
+
+    #[test]
+    fn parses_keyword_literals() {
+        let value = Parser::parse(":foo").unwrap();
+        assert!(matches!(value, Value::Keyword(ref s) if s == "foo"));
+    }
+
+    #[test]
+    fn recovers_past_a_stray_close_paren() {
+        let (forms, errors) = Parser::parse_all_recovering("(+ 1 2) ) (+ 3 4)");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(forms.len(), 2);
+        assert_eq!(forms[0].to_string(), "(+ 1 2)");
+        assert_eq!(forms[1].to_string(), "(+ 3 4)");
+    }
+
+    #[test]
+    fn recovering_with_no_errors_matches_parse_all() {
+        let (forms, errors) = Parser::parse_all_recovering("(+ 1 2) (* 3 4)");
+        assert!(errors.is_empty());
+        assert_eq!(forms.len(), 2);
+    }
+
+    #[test]
+    fn spanned_forms_report_their_source_position() {
+        let forms = Parser::parse_all_spanned("(+ 1 2)\n(* 3 4)").unwrap();
+        assert_eq!(forms.len(), 2);
+        assert_eq!(forms[0].span.start, crate::span::Position { line: 1, col: 1 });
+        assert_eq!(forms[1].span.start, crate::span::Position { line: 2, col: 1 });
+    }
+
+    #[test]
+    fn square_brackets_parse_like_parens() {
+        let value = Parser::parse("[+ 1 2]").unwrap();
+        assert_eq!(value.to_string(), "(+ 1 2)");
+    }
+
+    #[test]
+    fn datum_comment_can_skip_a_whole_list() {
+        let value = Parser::parse("(+ 1 #;(a b c) 3)").unwrap();
+        assert_eq!(value.to_string(), "(+ 1 3)");
+    }
+
+    #[test]
+    fn rejects_pathologically_deep_nesting() {
+        let source = "(".repeat(DEFAULT_MAX_DEPTH + 10) + &")".repeat(DEFAULT_MAX_DEPTH + 10);
+        let err = Parser::parse(&source).unwrap_err();
+        assert!(matches!(&err, ShellError::Parse(e) if e.code == "max-depth-exceeded"));
+        assert!(err.to_string().contains("maximum nesting depth"), "{err}");
+    }
+
+    #[test]
+    fn unexpected_close_paren_reports_expected_and_found() {
+        let err = Parser::parse(")").unwrap_err();
+        let ShellError::Parse(err) = err else { panic!("expected a parse error") };
+        assert_eq!(err.code, "unexpected-token");
+        assert_eq!(err.expected.as_deref(), Some("an expression"));
+        assert_eq!(err.found.as_deref(), Some("')'"));
+    }
+
+    #[test]
+    fn unterminated_list_has_a_stable_code() {
+        let err = Parser::parse("(+ 1 2").unwrap_err();
+        assert!(matches!(err, ShellError::Parse(e) if e.code == "unterminated-list"));
+    }
+
+    #[test]
+    fn quasiquote_and_unquote_shorthand_round_trip() {
+        assert_eq!(Parser::parse("'x").unwrap().to_string(), "'x");
+        assert_eq!(Parser::parse("`x").unwrap().to_string(), "`x");
+        assert_eq!(Parser::parse(",x").unwrap().to_string(), ",x");
+        assert_eq!(Parser::parse(",@x").unwrap().to_string(), ",@x");
+        assert_eq!(Parser::parse("`(a ,b ,@c)").unwrap().to_string(), "`(a ,b ,@c)");
+    }
+
+    #[test]
+    fn parses_dotted_list() {
+        let value = Parser::parse("(a b . c)").unwrap();
+        assert_eq!(value.to_string(), "(a b . c)");
+    }
+
+    #[test]
+    fn dotted_list_in_lambda_params_parses() {
+        let value = Parser::parse("(lambda (a . rest) a)").unwrap();
+        assert_eq!(value.to_string(), "(lambda (a . rest) a)");
+    }
+
+    #[test]
+    fn rejects_leading_dot() {
+        assert!(Parser::parse("(. a)").is_err());
+    }
+
+    #[test]
+    fn rejects_multiple_dots() {
+        assert!(Parser::parse("(a . b . c)").is_err());
+    }
+
+    #[test]
+    fn parse_strict_rejects_trailing_tokens() {
+        let err = Parser::parse_strict("(+ 1 2) (+ 3 4)").unwrap_err().to_string();
+        assert!(err.contains("trailing input"), "{err}");
+        assert!(err.contains("1:9"), "{err}");
+    }
+
+    #[test]
+    fn parse_strict_accepts_a_single_form() {
+        let value = Parser::parse_strict("(+ 1 2)").unwrap();
+        assert_eq!(value.to_string(), "(+ 1 2)");
+    }
+
+    #[test]
+    fn parses_piped_symbol_literal() {
+        let value = Parser::parse("|weird symbol|").unwrap();
+        assert!(matches!(value, Value::Symbol(ref s) if s == "weird symbol"));
+        assert_eq!(value.to_string(), "|weird symbol|");
+    }
+
+    #[test]
+    fn custom_max_depth_is_honored() {
+        assert!(Parser::parse_with_max_depth("((1))", 1).is_err());
+        assert!(Parser::parse_with_max_depth("((1))", 2).is_ok());
+    }
+
+    #[test]
+    fn top_level_boundaries_splits_plain_atoms() {
+        let tokens = Lexer::tokenize("a b c").unwrap();
+        assert_eq!(Parser::top_level_boundaries(&tokens), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn top_level_boundaries_keeps_a_quoted_form_together() {
+        let tokens = Lexer::tokenize("'x (+ 1 2)").unwrap();
+        assert_eq!(Parser::top_level_boundaries(&tokens), vec![2, tokens.len()]);
+    }
+
+    #[test]
+    fn top_level_boundaries_keeps_a_datum_comment_and_its_value_together() {
+        let tokens = Lexer::tokenize("#;(foo) (bar)").unwrap();
+        assert_eq!(Parser::top_level_boundaries(&tokens), vec![tokens.len()]);
+    }
+
+    #[test]
+    fn parse_all_parallel_matches_parse_all_for_many_forms() {
+        let source: String = (0..200).map(|i| format!("(+ {i} 1)\n")).collect();
+        let (parallel_forms, errors) = Parser::parse_all_parallel(&source);
+        assert!(errors.is_empty());
+        let sequential_forms = Parser::parse_all(&source).unwrap();
+        assert_eq!(parallel_forms.len(), sequential_forms.len());
+        for (parallel, sequential) in parallel_forms.iter().zip(&sequential_forms) {
+            assert_eq!(parallel.to_string(), sequential.to_string());
+        }
+    }
+
+    #[test]
+    fn parse_all_parallel_falls_back_for_small_sources() {
+        let (forms, errors) = Parser::parse_all_parallel("(+ 1 2) (* 3 4)");
+        assert!(errors.is_empty());
+        assert_eq!(forms.len(), 2);
+    }
+
+    #[test]
+    fn parse_all_parallel_reports_errors_from_any_chunk() {
+        let mut source: String = (0..200).map(|i| format!("(+ {i} 1)\n")).collect();
+        source.push_str(")\n");
+        source.push_str("(+ 1 2)\n");
+        let (forms, errors) = Parser::parse_all_parallel(&source);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(forms.len(), 201);
+    }
+}
+
+/// Property tests over generated [`Value`] trees, as a safety net
+/// alongside the fixed-input tests above.
+#[cfg(test)]
+mod proptests {
+    use super::*;
+    use crate::value::values_equal;
+    use proptest::prelude::*;
+
+    /// Bools, ints, and plain symbols -- deliberately leaving out `Str`
+    /// (its escaping round trip has its own test in `value.rs`) and
+    /// `Float` (Display/parse round-trip
+    /// for floats is its own concern, not this parser's).
+    fn leaf() -> impl Strategy<Value = Value> {
+        prop_oneof![
+            any::<bool>().prop_map(Value::Bool),
+            any::<i64>().prop_map(Value::Int),
+            "[a-zA-Z][a-zA-Z0-9]{0,8}".prop_map(Value::Symbol),
+        ]
+    }
+
+    fn value_tree() -> impl Strategy<Value = Value> {
+        leaf().prop_recursive(4, 64, 4, |inner| {
+            proptest::collection::vec(inner, 0..4).prop_map(Value::list)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn printing_then_parsing_round_trips(value in value_tree()) {
+            let printed = value.to_string();
+            let parsed = Parser::parse(&printed).unwrap();
+            prop_assert!(values_equal(&value, &parsed));
+        }
+
+        #[test]
+        fn parsing_arbitrary_strings_never_panics(source in ".{0,200}") {
+            let _ = Parser::parse(&source);
+        }
+    }
+}
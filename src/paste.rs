@@ -0,0 +1,76 @@
+/// Terminal escape sequences that bracket pasted text
+/// (`\x1b[200~ ... \x1b[201~`), stripped before lexing.
+const PASTE_START: &str = "\x1b[200~";
+const PASTE_END: &str = "\x1b[201~";
+
+/// Strips bracketed-paste start/end markers from a chunk of input.
+pub fn strip_paste_markers(input: &str) -> String {
+    input.replace(PASTE_START, "").replace(PASTE_END, "")
+}
+
+/// Counts parenthesis depth in `source`, ignoring parens inside string
+/// literals, to decide whether a buffered block of input is complete.
+pub fn paren_balance(source: &str) -> i64 {
+    let mut depth: i64 = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in source.chars() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    depth
+}
+
+/// Returns true once `source` contains at least one fully-closed form,
+/// i.e. reading more input is unnecessary to attempt a parse.
+pub fn is_complete(source: &str) -> bool {
+    !source.trim().is_empty() && paren_balance(source) <= 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balances_simple_forms() {
+        assert_eq!(paren_balance("(+ 1 2)"), 0);
+        assert_eq!(paren_balance("(+ 1 (* 2 3)"), 1);
+    }
+
+    #[test]
+    fn balances_square_brackets() {
+        assert_eq!(paren_balance("[+ 1 2]"), 0);
+        assert_eq!(paren_balance("[+ 1 (* 2 3]"), 1);
+    }
+
+    #[test]
+    fn ignores_parens_in_strings() {
+        assert_eq!(paren_balance("(display \"(\")"), 0);
+    }
+
+    #[test]
+    fn strips_bracketed_paste_markers() {
+        let pasted = format!("{PASTE_START}(+ 1 2){PASTE_END}");
+        assert_eq!(strip_paste_markers(&pasted), "(+ 1 2)");
+    }
+
+    #[test]
+    fn detects_incomplete_input() {
+        assert!(!is_complete("(+ 1"));
+        assert!(is_complete("(+ 1 2)"));
+    }
+}
@@ -0,0 +1,150 @@
+//! A persistent, singly-linked cons-list.
+//!
+//! `Value::List` is backed by `Rc<Vec<Value>>`: sharing the whole backing
+//! vector is cheap, but `cons` has to allocate a new vector with the head
+//! copied in front, and `cdr` has to copy every remaining element into a
+//! fresh one -- both O(n), which makes building a list one element at a
+//! time in a loop O(n^2) overall. Swapping `Value::List` itself over to a
+//! linked representation isn't a contained change: the interpreter and
+//! every builtin in `builtins/` pattern-match its contents as a Rust
+//! slice (`[Value::Symbol(name), value]`-style), which only works against
+//! something indexable and contiguous. `Plist` is the cons-cell
+//! alternative instead, exposed as its own type (the `plist/*` builtins)
+//! for scripts that are doing the kind of incremental list-building this
+//! module exists to make cheap -- `cons` and `cdr` only ever allocate or
+//! clone one cell, no matter how long the list is.
+use crate::value::Value;
+use std::rc::Rc;
+
+enum Node {
+    Nil,
+    Cons(Value, Plist),
+}
+
+/// A persistent cons-list: `nil`, or a value paired with the (shared)
+/// `Plist` that follows it. Cloning a `Plist` is an `Rc` bump, and
+/// [`Plist::cons`] only ever allocates the new head cell, reusing the
+/// rest of the structure by reference -- so `cons` and `tail` are both
+/// O(1), and two lists can share a common tail without either copying it.
+#[derive(Clone)]
+pub struct Plist(Rc<Node>);
+
+impl Plist {
+    /// The empty list.
+    pub fn nil() -> Plist {
+        Plist(Rc::new(Node::Nil))
+    }
+
+    pub fn is_nil(&self) -> bool {
+        matches!(*self.0, Node::Nil)
+    }
+
+    /// `self` with `head` prepended, in O(1): only the new cell is
+    /// allocated, `self`'s structure is shared by reference.
+    pub fn cons(&self, head: Value) -> Plist {
+        Plist(Rc::new(Node::Cons(head, self.clone())))
+    }
+
+    /// The first element, in O(1).
+    pub fn head(&self) -> Option<&Value> {
+        match &*self.0 {
+            Node::Cons(head, _) => Some(head),
+            Node::Nil => None,
+        }
+    }
+
+    /// `self` with its first element removed, in O(1): a clone of the
+    /// `Rc` already held by this cell, not a copy of the elements.
+    pub fn tail(&self) -> Option<Plist> {
+        match &*self.0 {
+            Node::Cons(_, tail) => Some(tail.clone()),
+            Node::Nil => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.is_nil()
+    }
+
+    pub fn iter(&self) -> PlistIter {
+        PlistIter(self.clone())
+    }
+
+    /// Builds a `Plist` holding `values` in order, front to back.
+    pub fn from_values<I>(values: I) -> Plist
+    where
+        I: DoubleEndedIterator<Item = Value>,
+    {
+        values.rfold(Plist::nil(), |tail, head| tail.cons(head))
+    }
+}
+
+impl Default for Plist {
+    fn default() -> Plist {
+        Plist::nil()
+    }
+}
+
+pub struct PlistIter(Plist);
+
+impl Iterator for PlistIter {
+    type Item = Value;
+
+    fn next(&mut self) -> Option<Value> {
+        let head = self.0.head().cloned()?;
+        self.0 = self.0.tail().unwrap_or_else(Plist::nil);
+        Some(head)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nil_is_empty() {
+        assert!(Plist::nil().is_nil());
+        assert_eq!(Plist::nil().len(), 0);
+    }
+
+    #[test]
+    fn cons_prepends_and_head_tail_recover_it() {
+        let list = Plist::nil().cons(Value::Int(2)).cons(Value::Int(1));
+        assert!(matches!(list.head(), Some(Value::Int(1))));
+        assert!(matches!(list.tail().unwrap().head(), Some(Value::Int(2))));
+    }
+
+    #[test]
+    fn tail_of_nil_is_none() {
+        assert!(Plist::nil().tail().is_none());
+    }
+
+    #[test]
+    fn cons_shares_the_tail_instead_of_copying_it() {
+        let tail = Plist::nil().cons(Value::Int(3)).cons(Value::Int(2));
+        let a = tail.cons(Value::Int(1));
+        let b = tail.cons(Value::Int(0));
+        assert_eq!(a.tail().unwrap().len(), tail.len());
+        assert_eq!(b.tail().unwrap().len(), tail.len());
+        assert_eq!(a.len(), 3);
+        assert_eq!(b.len(), 3);
+    }
+
+    #[test]
+    fn from_values_preserves_order() {
+        let list = Plist::from_values(vec![Value::Int(1), Value::Int(2), Value::Int(3)].into_iter());
+        let rendered: Vec<String> = list.iter().map(|v| v.to_string()).collect();
+        assert_eq!(rendered, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn iter_visits_every_element_in_order() {
+        let list = Plist::nil().cons(Value::Int(2)).cons(Value::Int(1));
+        let rendered: Vec<String> = list.iter().map(|v| v.to_string()).collect();
+        assert_eq!(rendered, vec!["1", "2"]);
+    }
+}
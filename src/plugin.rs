@@ -0,0 +1,65 @@
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::parser::Parser;
+use std::path::PathBuf;
+
+/// Directory scanned for plugin scripts: `~/.config/cracked/plugins/`.
+pub fn plugins_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cracked").join("plugins"))
+}
+
+/// Loads and evaluates a single plugin file into `env`.
+pub fn load_plugin(path: &PathBuf, env: &Environment) -> Result<(), ShellError> {
+    let source = std::fs::read_to_string(path)?;
+    for form in Parser::parse_all(&source)? {
+        crate::eval::eval(&form, env)?;
+    }
+    Ok(())
+}
+
+/// Loads every `*.lisp` file in the plugins directory, in sorted order.
+/// A missing directory is not an error — there simply are no plugins.
+pub fn load_all(env: &Environment) -> Result<(), ShellError> {
+    let Some(dir) = plugins_dir() else {
+        return Ok(());
+    };
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "lisp"))
+        .collect();
+    paths.sort();
+
+    for path in paths {
+        load_plugin(&path, env)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_plugin_file_is_an_error() {
+        let env = Environment::new_global();
+        let result = load_plugin(&PathBuf::from("/nonexistent/cracked-plugin.lisp"), &env);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn loads_a_plugin_file() {
+        let path = std::env::temp_dir().join("cracked_shell_plugin_test.lisp");
+        std::fs::write(&path, "(define plugin-loaded 1)").unwrap();
+        let env = Environment::new_global();
+        load_plugin(&path, &env).unwrap();
+        assert!(env.get("plugin-loaded").is_some());
+        std::fs::remove_file(&path).ok();
+    }
+}
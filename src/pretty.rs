@@ -0,0 +1,54 @@
+use crate::value::Value;
+
+/// Default terminal width assumed when no real terminal size is available.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Renders `value` on one line, or as an indented multi-line tree if the
+/// flat rendering would exceed `width` columns.
+pub fn pretty(value: &Value, width: usize) -> String {
+    let flat = value.to_string();
+    if flat.len() <= width {
+        flat
+    } else {
+        let mut out = String::new();
+        write_indented(value, 0, &mut out);
+        out
+    }
+}
+
+fn write_indented(value: &Value, depth: usize, out: &mut String) {
+    match value {
+        Value::List(items) if !items.is_empty() => {
+            out.push('(');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                    out.push_str(&"  ".repeat(depth + 1));
+                }
+                write_indented(item, depth + 1, out);
+            }
+            out.push(')');
+        }
+        other => out.push_str(&other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_values_stay_on_one_line() {
+        let v = Value::list(vec![Value::Int(1), Value::Int(2)]);
+        assert_eq!(pretty(&v, 80), "(1 2)");
+    }
+
+    #[test]
+    fn long_values_are_indented() {
+        let items: Vec<Value> = (0..50).map(Value::Int).collect();
+        let v = Value::list(items);
+        let out = pretty(&v, 20);
+        assert!(out.contains('\n'));
+        assert!(out.starts_with('('));
+    }
+}
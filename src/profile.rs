@@ -0,0 +1,193 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Opt-in counters for `(profile-report)`/`,profile`: how often each
+/// special form and builtin ran, and how much wall-clock time went into
+/// each user-defined function. Disabled by default since the timing calls
+/// (one [`std::time::Instant::now`] per lambda call) aren't free, and most
+/// sessions never ask for a profile.
+///
+/// Only calls that go through [`crate::eval::eval_list`] are counted: a
+/// builtin invoked directly through [`crate::eval::apply`] from Rust code
+/// (a memoized function's cache miss, a sandboxed plugin call) isn't
+/// itself a "form" the profiler saw get evaluated, so it's left out,
+/// matching the ticket's framing of "hot spots in scripts."
+///
+/// A lambda is identified by the symbol it was called through (`(add 1
+/// 2)` profiles under `add`), since [`crate::value::Lambda`] itself has no
+/// name -- the same symbol can resolve to a different lambda on every
+/// call if it's reassigned, so this is a record of "time spent calling
+/// whatever `add` meant at the time," not of any one closure. A call
+/// through an anonymous expression, e.g. `((lambda (x) x) 5)`, is counted
+/// under `<anonymous>`.
+#[derive(Default)]
+struct Profiler {
+    enabled: bool,
+    special_forms: HashMap<&'static str, u64>,
+    builtins: HashMap<String, u64>,
+    functions: HashMap<String, (u64, Duration)>,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::default());
+}
+
+pub const ANONYMOUS_FUNCTION: &str = "<anonymous>";
+
+/// Turns profiling on. Counters already recorded are kept, so `(enable)`
+/// after a `(profile-reset)` starts a clean window.
+pub fn enable() {
+    PROFILER.with(|p| p.borrow_mut().enabled = true);
+}
+
+pub fn disable() {
+    PROFILER.with(|p| p.borrow_mut().enabled = false);
+}
+
+pub fn is_enabled() -> bool {
+    PROFILER.with(|p| p.borrow().enabled)
+}
+
+/// Discards every counter without changing whether profiling is enabled.
+pub fn reset() {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        p.special_forms.clear();
+        p.builtins.clear();
+        p.functions.clear();
+    });
+}
+
+pub fn record_special_form(name: &'static str) {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        if p.enabled {
+            *p.special_forms.entry(name).or_insert(0) += 1;
+        }
+    });
+}
+
+pub fn record_builtin_call(name: &str) {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        if p.enabled {
+            *p.builtins.entry(name.to_string()).or_insert(0) += 1;
+        }
+    });
+}
+
+pub fn record_function_call(name: &str, elapsed: Duration) {
+    PROFILER.with(|p| {
+        let mut p = p.borrow_mut();
+        if p.enabled {
+            let entry = p.functions.entry(name.to_string()).or_insert((0, Duration::ZERO));
+            entry.0 += 1;
+            entry.1 += elapsed;
+        }
+    });
+}
+
+/// A human-readable report of everything recorded since the last
+/// [`reset`], each section sorted by descending count (or total time, for
+/// functions) so the hottest entries are first.
+pub fn report() -> String {
+    PROFILER.with(|p| {
+        let p = p.borrow();
+        let mut out = String::new();
+
+        let mut special_forms: Vec<_> = p.special_forms.iter().collect();
+        special_forms.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        out.push_str("special forms:\n");
+        if special_forms.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for (name, count) in special_forms {
+            out.push_str(&format!("  {name}: {count}\n"));
+        }
+
+        let mut builtins: Vec<_> = p.builtins.iter().collect();
+        builtins.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        out.push_str("builtins:\n");
+        if builtins.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for (name, count) in builtins {
+            out.push_str(&format!("  {name}: {count}\n"));
+        }
+
+        let mut functions: Vec<_> = p.functions.iter().collect();
+        functions.sort_by(|a, b| (b.1 .1).cmp(&a.1 .1).then_with(|| a.0.cmp(b.0)));
+        out.push_str("functions:\n");
+        if functions.is_empty() {
+            out.push_str("  (none)\n");
+        }
+        for (name, (count, total)) in functions {
+            out.push_str(&format!("  {name}: {count} call(s), {total:?} total\n"));
+        }
+
+        out
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// [`PROFILER`] is thread-local and the test harness runs each test on
+    /// its own thread; every test still resets and disables profiling on
+    /// the way in and out, so it starts from and leaves the default state.
+    fn cleanup() {
+        disable();
+        reset();
+    }
+
+    #[test]
+    fn disabled_by_default_and_records_nothing() {
+        cleanup();
+        assert!(!is_enabled());
+        record_special_form("if");
+        assert_eq!(report(), "special forms:\n  (none)\nbuiltins:\n  (none)\nfunctions:\n  (none)\n");
+        cleanup();
+    }
+
+    #[test]
+    fn records_counts_once_enabled() {
+        cleanup();
+        enable();
+        record_special_form("if");
+        record_special_form("if");
+        record_builtin_call("cons");
+        record_function_call("add", Duration::from_millis(5));
+        let text = report();
+        assert!(text.contains("if: 2"));
+        assert!(text.contains("cons: 1"));
+        assert!(text.contains("add: 1 call(s)"));
+        cleanup();
+    }
+
+    #[test]
+    fn reset_clears_counters_but_leaves_enabled_state() {
+        cleanup();
+        enable();
+        record_special_form("quote");
+        reset();
+        assert!(is_enabled());
+        assert_eq!(report(), "special forms:\n  (none)\nbuiltins:\n  (none)\nfunctions:\n  (none)\n");
+        cleanup();
+    }
+
+    #[test]
+    fn busier_entries_sort_first() {
+        cleanup();
+        enable();
+        record_builtin_call("cons");
+        record_builtin_call("car");
+        record_builtin_call("car");
+        let text = report();
+        let car_pos = text.find("car: 2").unwrap();
+        let cons_pos = text.find("cons: 1").unwrap();
+        assert!(car_pos < cons_pos);
+        cleanup();
+    }
+}
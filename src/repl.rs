@@ -0,0 +1,456 @@
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::eval::eval;
+use crate::parser::Parser;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+
+/// Options controlling how the REPL starts up.
+#[derive(Default)]
+pub struct ReplOptions {
+    pub no_rc: bool,
+    pub rc_file: Option<PathBuf>,
+    /// Non-interactive `errexit` mode: stop at the first error instead of
+    /// evaluating the remaining top-level forms. There is no pipeline
+    /// construct in this shell yet, so there is no separate `pipefail`
+    /// knob to speak of — a failing form already fails the whole script.
+    pub strict: bool,
+    /// Skip auto-loading `~/.config/cracked/plugins/*.lisp` on startup.
+    pub no_plugins: bool,
+    /// `--crash-report FILE`: on an unhandled error while evaluating
+    /// script-mode source (see [`eval_source_and_print`]), write a
+    /// [`crate::crash_report::CrashReport`] to this path before reporting
+    /// the error as usual.
+    pub crash_report: Option<PathBuf>,
+}
+
+/// Resolves the rc-file to load, honoring `--rc FILE` and `--no-rc`.
+///
+/// Defaults to `~/.config/cracked/init.lisp`.
+pub fn rc_file_path(opts: &ReplOptions) -> Option<PathBuf> {
+    if opts.no_rc {
+        return None;
+    }
+    if let Some(path) = &opts.rc_file {
+        return Some(path.clone());
+    }
+    dirs::config_dir().map(|dir| dir.join("cracked").join("init.lisp"))
+}
+
+/// Path to the file `persist-define` appends to and the REPL replays on
+/// every startup: `~/.config/cracked/defs.lisp`. Kept separate from the rc
+/// file so user-authored startup config and interactively accumulated
+/// definitions don't collide with each other.
+pub fn persisted_defs_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cracked").join("defs.lisp"))
+}
+
+/// Loads and evaluates every form in `path`, ignoring a missing file.
+pub fn load_rc_file(path: &PathBuf, env: &Environment) -> Result<(), ShellError> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    for form in crate::parse_cache::load_or_parse(&source)? {
+        eval(&form, env)?;
+    }
+    Ok(())
+}
+
+/// Evaluates every top-level form in `source` non-interactively, printing
+/// each result, and returns the process exit code to use.
+///
+/// Used for piping a script via stdin (`-e`/`-c` one-liners go through
+/// [`eval_one_liner_and_print`] instead). In
+/// `opts.strict` mode this stops at the first error (`errexit`); otherwise
+/// it keeps evaluating the remaining forms, printing every error, and
+/// still exits non-zero if any form failed.
+///
+/// Each error is reported with the source span of the top-level form it
+/// came from (via [`Parser::parse_all_spanned`]), which is as precise as
+/// this points: [`ShellError`] carries no span of its own, and the `Value`
+/// tree [`eval`] walks has no span on any of its nodes, so there's no way
+/// to point at, say, just the offending symbol inside a ten-line `let` --
+/// only at which top-level form it was in. Narrowing that further would
+/// mean threading a span through every node of the runtime value
+/// representation, not just adding a field to [`ShellError`].
+///
+/// The offending span is also rendered as a source snippet (via
+/// [`crate::diagnostics::render_snippet`]) underneath the error message.
+/// A parse error from [`Parser::parse_all_spanned`] itself has no span to
+/// render a snippet for -- the lexer and parser report a message only,
+/// not a [`crate::span::Span`] -- so those are printed without one.
+pub fn eval_source_and_print(source: &str, opts: &ReplOptions) -> i32 {
+    let env = Environment::new_global();
+    crate::builtins::install(&env);
+
+    if let Some(rc_path) = rc_file_path(opts) {
+        if let Err(e) = load_rc_file(&rc_path, &env) {
+            eprintln!("error loading rc file {}: {e}", rc_path.display());
+        }
+    }
+    if !opts.no_rc {
+        if let Some(defs_path) = persisted_defs_path() {
+            if let Err(e) = load_rc_file(&defs_path, &env) {
+                eprintln!("error loading persisted definitions {}: {e}", defs_path.display());
+            }
+        }
+    }
+    if !opts.no_plugins {
+        if let Err(e) = crate::plugin::load_all(&env) {
+            eprintln!("error loading plugins: {e}");
+        }
+    }
+
+    let forms = match Parser::parse_all_spanned(source) {
+        Ok(forms) => forms,
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+
+    let mut exit_code = 0;
+    let mut recent_forms: Vec<String> = Vec::new();
+    for form in forms {
+        recent_forms.push(form.value.to_string());
+        if recent_forms.len() > crate::crash_report::RECENT_FORMS_LIMIT {
+            recent_forms.remove(0);
+        }
+
+        crate::callstack::clear();
+        match eval(&form.value, &env) {
+            Ok(value) => println!("{value}"),
+            Err(e) => {
+                eprintln!("{e} (at {})", form.span);
+                eprint!("{}", crate::diagnostics::render_snippet(source, &form.span, false));
+                eprint!("{}", crate::callstack::render());
+                if let Some(path) = &opts.crash_report {
+                    let report =
+                        crate::crash_report::CrashReport::capture(&e, form.span.to_string(), &recent_forms, &env);
+                    if let Err(write_err) = report.write_to(path) {
+                        eprintln!("error writing crash report to {}: {write_err}", path.display());
+                    } else {
+                        eprintln!("crash report written to {}", path.display());
+                    }
+                }
+                exit_code = 1;
+                if opts.strict {
+                    return exit_code;
+                }
+            }
+        }
+    }
+    exit_code
+}
+
+/// Evaluates a single `-e`/`-c` one-liner and prints its result, returning
+/// the process exit code to use.
+///
+/// Unlike [`eval_source_and_print`], which treats its input as a script of
+/// possibly many forms, this parses with [`Parser::parse_strict`] so a
+/// stray trailing form -- almost always a missing pair of wrapping parens
+/// rather than an intentional second expression -- is reported as an
+/// error instead of silently ignored.
+pub fn eval_one_liner_and_print(source: &str, opts: &ReplOptions) -> i32 {
+    let env = Environment::new_global();
+    crate::builtins::install(&env);
+
+    if let Some(rc_path) = rc_file_path(opts) {
+        if let Err(e) = load_rc_file(&rc_path, &env) {
+            eprintln!("error loading rc file {}: {e}", rc_path.display());
+        }
+    }
+    if !opts.no_rc {
+        if let Some(defs_path) = persisted_defs_path() {
+            if let Err(e) = load_rc_file(&defs_path, &env) {
+                eprintln!("error loading persisted definitions {}: {e}", defs_path.display());
+            }
+        }
+    }
+    if !opts.no_plugins {
+        if let Err(e) = crate::plugin::load_all(&env) {
+            eprintln!("error loading plugins: {e}");
+        }
+    }
+
+    let form = match Parser::parse_strict(source) {
+        Ok(form) => form,
+        Err(e) => {
+            eprintln!("{e}");
+            return 1;
+        }
+    };
+
+    crate::callstack::clear();
+    match eval(&form, &env) {
+        Ok(value) => {
+            println!("{value}");
+            0
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            eprint!("{}", crate::callstack::render());
+            1
+        }
+    }
+}
+
+/// Runs the interactive read-eval-print loop.
+pub fn run(opts: ReplOptions) {
+    let mut env = Environment::new_global();
+    crate::builtins::install(&env);
+
+    if let Some(rc_path) = rc_file_path(&opts) {
+        if let Err(e) = load_rc_file(&rc_path, &env) {
+            eprintln!("error loading rc file {}: {e}", rc_path.display());
+        }
+    }
+    if !opts.no_rc {
+        if let Some(defs_path) = persisted_defs_path() {
+            if let Err(e) = load_rc_file(&defs_path, &env) {
+                eprintln!("error loading persisted definitions {}: {e}", defs_path.display());
+            }
+        }
+    }
+    if !opts.no_plugins {
+        if let Err(e) = crate::plugin::load_all(&env) {
+            eprintln!("error loading plugins: {e}");
+        }
+    }
+
+    let colorize = std::io::stdout().is_terminal();
+    let stdin = io::stdin();
+    let mut buffer = String::new();
+    let mut history = crate::history::History::new();
+    let mut transcript = crate::transcript::Transcript::new();
+    loop {
+        print!("{}", if buffer.is_empty() { "$> " } else { "... " });
+        io::stdout().flush().ok();
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        let line = crate::paste::strip_paste_markers(&line);
+        if line.trim().is_empty() && buffer.is_empty() {
+            continue;
+        }
+        history.push(&line);
+        transcript.log(line.trim_end());
+
+        if buffer.is_empty() {
+            if line.trim() == ",history" {
+                for entry in history.entries() {
+                    println!("{entry}");
+                }
+                continue;
+            }
+            if let Some((cmd, args)) = crate::meta::parse_meta_line(&line) {
+                match crate::meta::run_meta_command(cmd, args, &env, &mut transcript) {
+                    crate::meta::MetaOutcome::Handled => continue,
+                    crate::meta::MetaOutcome::Reset => {
+                        // Drop any recursive functions' self-capturing
+                        // cycle before abandoning the old global frame, or
+                        // it leaks for the rest of the process's life.
+                        env.clear();
+                        env = Environment::new_global();
+                        crate::builtins::install(&env);
+                        continue;
+                    }
+                    crate::meta::MetaOutcome::Quit => break,
+                }
+            }
+        }
+
+        buffer.push_str(&line);
+        if !crate::paste::is_complete(&buffer) {
+            continue;
+        }
+        let source = std::mem::take(&mut buffer);
+
+        match Parser::parse_all_spanned(&source) {
+            Ok(forms) => {
+                for form in forms {
+                    crate::callstack::clear();
+                    let started = std::time::Instant::now();
+                    let result = eval(&form.value, &env);
+                    crate::notify::notify_if_long(started.elapsed(), "command finished");
+                    match result {
+                        Ok(value) => {
+                            let truncated = crate::color::truncate(&value, false);
+                            let text = crate::pretty::pretty(&truncated, crate::pretty::DEFAULT_WIDTH);
+                            println!("{}", crate::color::colorize_value(&value, &text, colorize));
+                            transcript.log(&text);
+                        }
+                        Err(e) => {
+                            let message = format!("{e} (at {})", form.span);
+                            eprintln!("{}", crate::color::render_error(&message, colorize));
+                            transcript.log(&message);
+                            let snippet = crate::diagnostics::render_snippet(&source, &form.span, colorize);
+                            if !snippet.is_empty() {
+                                eprint!("{snippet}");
+                                transcript.log(snippet.trim_end());
+                            }
+                            let backtrace = crate::callstack::render();
+                            if !backtrace.is_empty() {
+                                eprint!("{backtrace}");
+                                transcript.log(backtrace.trim_end());
+                            }
+                            break;
+                        }
+                    }
+                }
+            }
+            Err(e) => eprintln!("{}", crate::color::render_error(&e.to_string(), colorize)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn persisted_defs_path_lives_next_to_the_rc_file() {
+        let path = persisted_defs_path().expect("config dir should resolve in tests");
+        assert_eq!(path.file_name().unwrap(), "defs.lisp");
+        assert_eq!(path.parent().unwrap().file_name().unwrap(), "cracked");
+    }
+
+    #[test]
+    fn missing_rc_file_is_not_an_error() {
+        let env = Environment::new_global();
+        let result = load_rc_file(&PathBuf::from("/nonexistent/cracked-init.lisp"), &env);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn rc_file_defines_persist_into_env() {
+        let dir = std::env::temp_dir().join("cracked_shell_repl_test_rcfile.lisp");
+        std::fs::write(&dir, "(define greeting 42)").unwrap();
+        let env = Environment::new_global();
+        load_rc_file(&dir, &env).unwrap();
+        assert!(env.get("greeting").is_some());
+        std::fs::remove_file(&dir).ok();
+    }
+
+    #[test]
+    fn no_rc_flag_disables_loading() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert!(rc_file_path(&opts).is_none());
+    }
+
+    #[test]
+    fn eval_source_prints_and_succeeds() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(eval_source_and_print("(+ 1 2)", &opts), 0);
+    }
+
+    #[test]
+    fn eval_source_reports_errors() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(eval_source_and_print("(undefined-fn)", &opts), 1);
+    }
+
+    #[test]
+    fn non_strict_mode_keeps_evaluating_after_an_error() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(
+            eval_source_and_print("(undefined-fn) (define x 1)", &opts),
+            1
+        );
+    }
+
+    #[test]
+    fn strict_mode_stops_at_first_error() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: true,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(
+            eval_source_and_print("(undefined-fn) (define x 1)", &opts),
+            1
+        );
+    }
+
+    #[test]
+    fn an_evaluation_error_writes_a_crash_report_when_configured() {
+        let path = std::env::temp_dir().join("cracked_shell_repl_crash_report_test.json");
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: Some(path.clone()),
+        };
+        assert_eq!(eval_source_and_print("(define x 1) (undefined-fn)", &opts), 1);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert!(report["error"].as_str().unwrap().contains("undefined-fn"));
+        assert_eq!(report["environment"]["x"]["int"], serde_json::json!(1));
+        assert!(report["recent_forms"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|f| f == "(undefined-fn)"));
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn one_liner_evaluates_a_single_form() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(eval_one_liner_and_print("(+ 1 2)", &opts), 0);
+    }
+
+    #[test]
+    fn one_liner_rejects_trailing_forms() {
+        let opts = ReplOptions {
+            no_rc: true,
+            rc_file: None,
+            strict: false,
+            no_plugins: true,
+            crash_report: None,
+        };
+        assert_eq!(
+            eval_one_liner_and_print("(+ 1 2) (+ 3 4)", &opts),
+            1
+        );
+    }
+}
@@ -0,0 +1,77 @@
+//! A chunked string builder for assembling a string out of many pieces.
+//!
+//! `String::push_str` in a loop is already amortized O(1) per call (the
+//! backing buffer doubles), so the quadratic cost this module exists to
+//! avoid isn't there -- it's in code that instead builds a *new* string
+//! each time, e.g. `s = s + &next` or `s = format!("{s}{next}")` in a
+//! loop, which copies everything accumulated so far on every iteration.
+//! `RopeBuilder` just wraps `String::push_str` under a name that makes
+//! the append-only, no-new-copy usage the obvious one, and is what
+//! [`crate::builtins::string`]'s `str/append` and `str/join` build their
+//! result with instead of the collect-then-join pattern they'd otherwise
+//! need.
+pub struct RopeBuilder {
+    buf: String,
+}
+
+impl RopeBuilder {
+    pub fn new() -> RopeBuilder {
+        RopeBuilder { buf: String::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> RopeBuilder {
+        RopeBuilder { buf: String::with_capacity(capacity) }
+    }
+
+    /// Appends `piece`, in amortized O(piece.len()) -- no prior content is
+    /// ever copied.
+    pub fn push(&mut self, piece: &str) {
+        self.buf.push_str(piece);
+    }
+
+    pub fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    /// Consumes the builder, returning the assembled string.
+    pub fn finish(self) -> String {
+        self.buf
+    }
+}
+
+impl Default for RopeBuilder {
+    fn default() -> RopeBuilder {
+        RopeBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pieces_are_appended_in_order() {
+        let mut rope = RopeBuilder::new();
+        rope.push("a");
+        rope.push("b");
+        rope.push("c");
+        assert_eq!(rope.finish(), "abc");
+    }
+
+    #[test]
+    fn empty_builder_finishes_to_an_empty_string() {
+        assert_eq!(RopeBuilder::new().finish(), "");
+    }
+
+    #[test]
+    fn len_tracks_the_assembled_length() {
+        let mut rope = RopeBuilder::with_capacity(8);
+        rope.push("hello");
+        assert_eq!(rope.len(), 5);
+        assert!(!rope.is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::value::Value;
+
+/// Builtins that touch the filesystem or spawn processes -- unsafe to hand
+/// to an untrusted snippet or plugin unless the caller explicitly opts in.
+const PRIVILEGED: &[&str] = &["proc/run", "proc/run-lines", "load-plugin", "save-session", "load-session"];
+
+/// Builds a child of `parent` suitable for evaluating untrusted snippets
+/// or plugins. Ordinary lookups fall back to `parent` -- copy-on-write,
+/// since [`Environment::child`] never touches `parent`'s frame and
+/// `parent` never sees whatever the sandbox goes on to define -- but every
+/// name in [`PRIVILEGED`] is shadowed with a stub that errors instead of
+/// running, unless it's named in `allow`.
+pub fn sandbox(parent: &Environment, allow: &[&str]) -> Environment {
+    let child = Environment::child(parent);
+    for name in PRIVILEGED {
+        if !allow.contains(name) {
+            child.define(*name, Value::Builtin(name, forbidden));
+        }
+    }
+    child
+}
+
+fn forbidden(_args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+    Err(ShellError::Eval(
+        "this builtin is disabled in a sandboxed evaluator".into(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builtins;
+
+    #[test]
+    fn privileged_builtins_are_disabled_by_default() {
+        let parent = Environment::new_global();
+        builtins::install(&parent);
+        let child = sandbox(&parent, &[]);
+        assert!(matches!(
+            crate::eval::apply(&child.get("proc/run").unwrap(), vec![Value::Str("echo".into())], &child),
+            Err(ShellError::Eval(_))
+        ));
+    }
+
+    #[test]
+    fn proc_run_lines_is_disabled_by_default_too() {
+        let parent = Environment::new_global();
+        builtins::install(&parent);
+        let child = sandbox(&parent, &[]);
+        assert!(matches!(
+            crate::eval::apply(&child.get("proc/run-lines").unwrap(), vec![Value::Str("echo".into())], &child),
+            Err(ShellError::Eval(_))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn whitelisted_builtins_pass_through_to_the_parent() {
+        let parent = Environment::new_global();
+        builtins::install(&parent);
+        builtins::install_namespace(&parent, "proc");
+        let child = sandbox(&parent, &["proc/run"]);
+        assert!(matches!(child.get("proc/run"), Some(Value::Builtin("proc/run", _))));
+    }
+
+    #[test]
+    fn sandbox_defines_never_leak_into_the_parent() {
+        let parent = Environment::new_global();
+        let child = sandbox(&parent, &[]);
+        child.define("secret", Value::Int(1));
+        assert!(parent.get("secret").is_none());
+    }
+
+    #[test]
+    fn sandbox_still_sees_ordinary_parent_bindings() {
+        let parent = Environment::new_global();
+        parent.define("x", Value::Int(7));
+        let child = sandbox(&parent, &[]);
+        assert!(matches!(child.get("x"), Some(Value::Int(7))));
+    }
+}
@@ -0,0 +1,551 @@
+//! A `serde::Serializer`/`serde::Deserializer` pair over [`Value`],
+//! mirroring `serde_json::Value`'s role: [`to_value`]/[`from_value`] let
+//! an embedder move a serde-derived struct into script data and read a
+//! typed result back out. Maps, structs, and struct-variant fields all
+//! round-trip as an association list of `(key . value)` pairs -- the
+//! same shape [`crate::convert`]'s `HashMap` conversion already uses --
+//! rather than introducing a dedicated map [`Value`] variant. Enum
+//! variants round-trip as a bare `:variant` keyword (unit variants) or a
+//! `(:variant . payload)` pair (newtype/tuple/struct variants).
+use crate::error::ShellError;
+use crate::value::Value;
+use serde::de::value::StringDeserializer;
+use serde::de::{DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{
+    SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct,
+    SerializeTupleVariant,
+};
+use serde::{Deserializer, Serialize, Serializer};
+use std::rc::Rc;
+
+/// Serializes `value` into a [`Value`] via its [`Serialize`] impl.
+pub fn to_value<T: Serialize>(value: &T) -> Result<Value, ShellError> {
+    value.serialize(ValueSerializer)
+}
+
+/// Deserializes a `T` out of `value` via its `Deserialize` impl.
+pub fn from_value<T: DeserializeOwned>(value: &Value) -> Result<T, ShellError> {
+    T::deserialize(ValueDeserializer(value.clone()))
+}
+
+fn alist(pairs: Vec<(Value, Value)>) -> Value {
+    Value::list(pairs.into_iter().map(|(k, v)| Value::dotted(vec![k], v)).collect())
+}
+
+/// Tags a variant's payload the same way across newtype, tuple, and
+/// struct variants: `(:variant . payload)`, where `payload` is whatever
+/// that variant kind would otherwise serialize to on its own (a bare
+/// value, a list, or an alist).
+fn tagged(variant: &'static str, payload: Value) -> Value {
+    Value::dotted(vec![Value::Keyword(variant.to_string())], payload)
+}
+
+pub struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = TupleVariantSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = StructSerializer;
+    type SerializeStructVariant = StructVariantSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Value, ShellError> {
+        Ok(Value::Bool(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_i16(self, v: i16) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_i32(self, v: i32) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_i64(self, v: i64) -> Result<Value, ShellError> {
+        Ok(Value::Int(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_u16(self, v: u16) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_u32(self, v: u32) -> Result<Value, ShellError> {
+        Ok(Value::Int(v as i64))
+    }
+    fn serialize_u64(self, v: u64) -> Result<Value, ShellError> {
+        i64::try_from(v).map(Value::Int).map_err(|_| ShellError::Eval(format!("{v} does not fit in a 64-bit signed int")))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Value, ShellError> {
+        Ok(Value::Float(v as f64))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Value, ShellError> {
+        Ok(Value::Float(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Value, ShellError> {
+        Ok(Value::Char(v))
+    }
+    fn serialize_str(self, v: &str) -> Result<Value, ShellError> {
+        Ok(Value::Str(v.to_string()))
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value, ShellError> {
+        Ok(Value::list(v.iter().map(|b| Value::Int(*b as i64)).collect()))
+    }
+    fn serialize_none(self) -> Result<Value, ShellError> {
+        Ok(Value::Nil)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Value, ShellError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Value, ShellError> {
+        Ok(Value::Nil)
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value, ShellError> {
+        Ok(Value::Nil)
+    }
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Value, ShellError> {
+        Ok(Value::Keyword(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> Result<Value, ShellError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, ShellError> {
+        Ok(tagged(variant, value.serialize(self)?))
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<SeqSerializer, ShellError> {
+        Ok(SeqSerializer { items: Vec::new() })
+    }
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, ShellError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer, ShellError> {
+        self.serialize_seq(Some(len))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<TupleVariantSerializer, ShellError> {
+        Ok(TupleVariantSerializer { variant, items: Vec::with_capacity(len) })
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<MapSerializer, ShellError> {
+        Ok(MapSerializer { pairs: Vec::new(), pending_key: None })
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<StructSerializer, ShellError> {
+        Ok(StructSerializer { pairs: Vec::new() })
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+        _len: usize,
+    ) -> Result<StructVariantSerializer, ShellError> {
+        Ok(StructVariantSerializer { variant, pairs: Vec::new() })
+    }
+}
+
+pub struct SeqSerializer {
+    items: Vec<Value>,
+}
+
+impl SerializeSeq for SeqSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ShellError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        Ok(Value::list(self.items))
+    }
+}
+
+impl SerializeTuple for SeqSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ShellError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for SeqSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ShellError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        SerializeSeq::end(self)
+    }
+}
+
+pub struct TupleVariantSerializer {
+    variant: &'static str,
+    items: Vec<Value>,
+}
+
+impl SerializeTupleVariant for TupleVariantSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ShellError> {
+        self.items.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        Ok(tagged(self.variant, Value::list(self.items)))
+    }
+}
+
+pub struct MapSerializer {
+    pairs: Vec<(Value, Value)>,
+    pending_key: Option<Value>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), ShellError> {
+        self.pending_key = Some(key.serialize(ValueSerializer)?);
+        Ok(())
+    }
+    fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), ShellError> {
+        let key = self
+            .pending_key
+            .take()
+            .ok_or_else(|| ShellError::Eval("serialize_value called before serialize_key".into()))?;
+        self.pairs.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        Ok(alist(self.pairs))
+    }
+}
+
+pub struct StructSerializer {
+    pairs: Vec<(Value, Value)>,
+}
+
+impl SerializeStruct for StructSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, name: &'static str, value: &T) -> Result<(), ShellError> {
+        self.pairs.push((Value::Symbol(name.to_string()), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        Ok(alist(self.pairs))
+    }
+}
+
+pub struct StructVariantSerializer {
+    variant: &'static str,
+    pairs: Vec<(Value, Value)>,
+}
+
+impl SerializeStructVariant for StructVariantSerializer {
+    type Ok = Value;
+    type Error = ShellError;
+    fn serialize_field<T: ?Sized + Serialize>(&mut self, name: &'static str, value: &T) -> Result<(), ShellError> {
+        self.pairs.push((Value::Symbol(name.to_string()), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, ShellError> {
+        Ok(tagged(self.variant, alist(self.pairs)))
+    }
+}
+
+/// `true` if every element of `items` is a `(key . value)` pair --
+/// [`ValueDeserializer::deserialize_any`]'s heuristic for telling an
+/// alist (produced by [`MapSerializer`]/[`StructSerializer`]) apart from
+/// an ordinary list when the target type isn't known up front. Callers
+/// that do know the target type (`deserialize_map`, `deserialize_seq`,
+/// ...) don't need this -- they commit to one shape unconditionally.
+fn is_alist(items: &[Value]) -> bool {
+    !items.is_empty() && items.iter().all(|item| matches!(item, Value::DottedList(key, _) if key.len() == 1))
+}
+
+pub struct ValueDeserializer(Value);
+
+struct SeqAccessImpl {
+    iter: std::vec::IntoIter<Value>,
+}
+
+impl<'de> SeqAccess<'de> for SeqAccessImpl {
+    type Error = ShellError;
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, ShellError> {
+        match self.iter.next() {
+            Some(v) => seed.deserialize(ValueDeserializer(v)).map(Some),
+            None => Ok(None),
+        }
+    }
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct AlistAccess {
+    iter: std::vec::IntoIter<Value>,
+    value: Option<Value>,
+}
+
+impl<'de> MapAccess<'de> for AlistAccess {
+    type Error = ShellError;
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, ShellError> {
+        match self.iter.next() {
+            Some(Value::DottedList(items, tail)) if items.len() == 1 => {
+                self.value = Some((*tail).clone());
+                seed.deserialize(ValueDeserializer(items[0].clone())).map(Some)
+            }
+            Some(other) => Err(ShellError::Eval(format!("expected a (key . value) pair, got {}", other.type_name()))),
+            None => Ok(None),
+        }
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, ShellError> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| ShellError::Eval("next_value_seed called before next_key_seed".into()))?;
+        seed.deserialize(ValueDeserializer(value))
+    }
+}
+
+struct EnumPayload {
+    variant: String,
+    payload: Value,
+}
+
+impl<'de> EnumAccess<'de> for EnumPayload {
+    type Error = ShellError;
+    type Variant = Self;
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), ShellError> {
+        let deserializer: StringDeserializer<ShellError> = self.variant.clone().into_deserializer();
+        let value = seed.deserialize(deserializer)?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> VariantAccess<'de> for EnumPayload {
+    type Error = ShellError;
+    fn unit_variant(self) -> Result<(), ShellError> {
+        Err(ShellError::Eval("expected a unit variant, got a payload".into()))
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, ShellError> {
+        seed.deserialize(ValueDeserializer(self.payload))
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, ShellError> {
+        match self.payload {
+            Value::List(items) => {
+                let items = Rc::try_unwrap(items).unwrap_or_else(|rc| (*rc).clone());
+                visitor.visit_seq(SeqAccessImpl { iter: items.into_iter() })
+            }
+            other => Err(ShellError::Eval(format!("expected a tuple variant payload, got {}", other.type_name()))),
+        }
+    }
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, ShellError> {
+        match self.payload {
+            Value::List(items) => {
+                let items = Rc::try_unwrap(items).unwrap_or_else(|rc| (*rc).clone());
+                visitor.visit_map(AlistAccess { iter: items.into_iter(), value: None })
+            }
+            other => Err(ShellError::Eval(format!("expected a struct variant payload, got {}", other.type_name()))),
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = ShellError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShellError> {
+        match self.0 {
+            Value::Nil => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::Int(n) => visitor.visit_i64(n),
+            Value::Float(n) => visitor.visit_f64(n),
+            Value::Str(s) | Value::Symbol(s) | Value::Keyword(s) => visitor.visit_string(s),
+            Value::Char(c) => visitor.visit_char(c),
+            Value::List(items) => {
+                let items = Rc::try_unwrap(items).unwrap_or_else(|rc| (*rc).clone());
+                if is_alist(&items) {
+                    visitor.visit_map(AlistAccess { iter: items.into_iter(), value: None })
+                } else {
+                    visitor.visit_seq(SeqAccessImpl { iter: items.into_iter() })
+                }
+            }
+            Value::DottedList(items, tail) if items.len() == 1 => {
+                visitor.visit_map(AlistAccess { iter: vec![Value::DottedList(items, tail)].into_iter(), value: None })
+            }
+            other => Err(ShellError::Eval(format!("cannot deserialize a {} without a target type", other.type_name()))),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShellError> {
+        match self.0 {
+            Value::Nil => visitor.visit_none(),
+            other => visitor.visit_some(ValueDeserializer(other)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, ShellError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShellError> {
+        match self.0 {
+            Value::List(items) => {
+                let items = Rc::try_unwrap(items).unwrap_or_else(|rc| (*rc).clone());
+                visitor.visit_seq(SeqAccessImpl { iter: items.into_iter() })
+            }
+            other => Err(ShellError::Eval(format!("expected a list, got {}", other.type_name()))),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, ShellError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, ShellError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ShellError> {
+        match self.0 {
+            Value::List(items) => {
+                let items = Rc::try_unwrap(items).unwrap_or_else(|rc| (*rc).clone());
+                visitor.visit_map(AlistAccess { iter: items.into_iter(), value: None })
+            }
+            other => Err(ShellError::Eval(format!("expected an association list, got {}", other.type_name()))),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ShellError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ShellError> {
+        match self.0 {
+            Value::Keyword(variant) => {
+                let deserializer: StringDeserializer<ShellError> = variant.into_deserializer();
+                visitor.visit_enum(deserializer)
+            }
+            Value::DottedList(items, tail) if items.len() == 1 => match &items[0] {
+                Value::Keyword(variant) => visitor.visit_enum(EnumPayload { variant: variant.clone(), payload: (*tail).clone() }),
+                _ => Err(ShellError::Eval("expected a (:variant . payload) pair".into())),
+            },
+            other => Err(ShellError::Eval(format!("expected an enum value, got {}", other.type_name()))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i64,
+        y: i64,
+        label: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: i64, height: i64 },
+    }
+
+    #[test]
+    fn a_struct_round_trips_through_an_alist() {
+        let point = Point { x: 1, y: 2, label: Some("origin".into()) };
+        let value = to_value(&point).unwrap();
+        assert!(matches!(value, Value::List(_)));
+        let back: Point = from_value(&value).unwrap();
+        assert_eq!(back, point);
+    }
+
+    #[test]
+    fn an_option_none_field_round_trips() {
+        let point = Point { x: 0, y: 0, label: None };
+        let value = to_value(&point).unwrap();
+        let back: Point = from_value(&value).unwrap();
+        assert_eq!(back, point);
+    }
+
+    #[test]
+    fn a_vec_round_trips_as_a_list() {
+        let items = vec![1i64, 2, 3];
+        let value = to_value(&items).unwrap();
+        assert!(matches!(value, Value::List(_)));
+        let back: Vec<i64> = from_value(&value).unwrap();
+        assert_eq!(back, items);
+    }
+
+    #[test]
+    fn a_unit_enum_variant_round_trips_as_a_keyword() {
+        let value = to_value(&Shape::Empty).unwrap();
+        assert!(matches!(value, Value::Keyword(ref k) if k == "Empty"));
+        let back: Shape = from_value(&value).unwrap();
+        assert_eq!(back, Shape::Empty);
+    }
+
+    #[test]
+    fn a_newtype_enum_variant_round_trips_as_a_tagged_pair() {
+        let value = to_value(&Shape::Circle(2.5)).unwrap();
+        let back: Shape = from_value(&value).unwrap();
+        assert_eq!(back, Shape::Circle(2.5));
+    }
+
+    #[test]
+    fn a_struct_enum_variant_round_trips_as_a_tagged_alist() {
+        let value = to_value(&Shape::Rect { width: 3, height: 4 }).unwrap();
+        let back: Shape = from_value(&value).unwrap();
+        assert_eq!(back, Shape::Rect { width: 3, height: 4 });
+    }
+
+    #[test]
+    fn a_hashmap_round_trips_through_an_alist() {
+        let mut map = std::collections::HashMap::new();
+        map.insert("a".to_string(), 1i64);
+        map.insert("b".to_string(), 2i64);
+        let value = to_value(&map).unwrap();
+        let back: std::collections::HashMap<String, i64> = from_value(&value).unwrap();
+        assert_eq!(back, map);
+    }
+}
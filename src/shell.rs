@@ -0,0 +1,164 @@
+use crate::builtins;
+use crate::env::Environment;
+use crate::error::ShellError;
+use crate::eval::eval;
+use crate::native::IntoNative;
+use crate::parser::Parser;
+use crate::value::Value;
+use serde::de::DeserializeOwned;
+
+/// A high-level facade for embedding the interpreter in a Rust
+/// application, so it can evaluate source and register its own builtins
+/// without assembling [`Parser`]/[`eval`]/[`Environment`] by hand the way
+/// [`crate::repl`] does.
+///
+/// [`Shell::register_fn`] accepts either a plain `fn(Vec<Value>,
+/// &Environment) -> Result<Value, ShellError>` -- the same signature every
+/// builtin in [`crate::builtins`] is written as -- or a capturing closure
+/// over owned Rust argument types (`i64`, `f64`, `bool`, `char`, `String`,
+/// or `Value` itself), converted automatically; see [`crate::native`] for
+/// the conversion traits and which arities are supported.
+pub struct Shell {
+    env: Environment,
+}
+
+impl Shell {
+    /// A fresh shell with every core and namespaced builtin available,
+    /// same as a new REPL session.
+    pub fn new() -> Shell {
+        let env = Environment::new_global();
+        builtins::install(&env);
+        Shell { env }
+    }
+
+    /// Parses and evaluates every top-level form in `source` in order,
+    /// returning the last form's value -- [`Value::Nil`] for empty input.
+    /// Stops and returns the first error, like [`crate::repl::eval_source_and_print`]
+    /// in `--strict` mode.
+    pub fn eval_str(&self, source: &str) -> Result<Value, ShellError> {
+        let mut result = Value::Nil;
+        for form in Parser::parse_all(source)? {
+            result = eval(&form, &self.env)?;
+        }
+        Ok(result)
+    }
+
+    /// Binds `name` to `f` in the shell's global environment, converting
+    /// `f`'s arguments and return value to and from `Value` automatically
+    /// (see [`crate::native`]). The resulting binding is a
+    /// [`Value::Native`], callable from scripts exactly like any other
+    /// function.
+    pub fn register_fn<F, Args>(&self, name: &'static str, f: F)
+    where
+        F: IntoNative<Args> + 'static,
+    {
+        self.env.define(name, Value::Native(name, f.into_native()));
+    }
+
+    /// Routes everything `print`/`pp`/`describe` write to `sink` instead
+    /// of the process's real stdout, until [`Shell::stop_capturing_output`]
+    /// is called. See [`crate::output`] for which builtins this covers.
+    pub fn capture_output(&self, sink: impl FnMut(&str) + 'static) {
+        crate::output::set_sink(sink);
+    }
+
+    /// Stops redirecting output installed by [`Shell::capture_output`].
+    pub fn stop_capturing_output(&self) {
+        crate::output::clear_sink();
+    }
+
+    /// Discards every binding -- including ones [`Shell::register_fn`]
+    /// added -- and reinstalls the core builtins, the same transition
+    /// `,reset` makes in the interactive REPL.
+    pub fn reset(&mut self) {
+        self.env.clear();
+        self.env = Environment::new_global();
+        builtins::install(&self.env);
+    }
+
+    /// The environment this shell evaluates against, for callers that need
+    /// lower-level access (e.g. to call [`Environment::get`] directly).
+    pub fn env(&self) -> &Environment {
+        &self.env
+    }
+
+    /// Like [`Shell::eval_str`], but converts the result into `T` via
+    /// [`crate::serde_value::from_value`] instead of handing back a raw
+    /// [`Value`].
+    pub fn eval_into<T: DeserializeOwned>(&self, source: &str) -> Result<T, ShellError> {
+        let value = self.eval_str(source)?;
+        crate::serde_value::from_value(&value)
+    }
+}
+
+impl Default for Shell {
+    fn default() -> Shell {
+        Shell::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn eval_str_returns_the_last_forms_value() {
+        let shell = Shell::new();
+        assert!(matches!(shell.eval_str("(+ 1 2) (* 3 4)"), Ok(Value::Int(12))));
+    }
+
+    #[test]
+    fn eval_str_of_empty_source_is_nil() {
+        let shell = Shell::new();
+        assert!(matches!(shell.eval_str(""), Ok(Value::Nil)));
+    }
+
+    #[test]
+    fn eval_str_propagates_an_error() {
+        let shell = Shell::new();
+        assert!(shell.eval_str("(undefined-fn)").is_err());
+    }
+
+    fn double(args: Vec<Value>, _env: &Environment) -> Result<Value, ShellError> {
+        match args.as_slice() {
+            [Value::Int(n)] => Ok(Value::Int(n * 2)),
+            _ => Err(ShellError::Arity("double expects 1 integer argument".into())),
+        }
+    }
+
+    #[test]
+    fn register_fn_makes_a_rust_function_callable_from_scripts() {
+        let shell = Shell::new();
+        shell.register_fn("double", double);
+        assert!(matches!(shell.eval_str("(double 21)"), Ok(Value::Int(42))));
+    }
+
+    #[test]
+    fn capture_output_collects_print_instead_of_writing_to_stdout() {
+        let shell = Shell::new();
+        let captured = Rc::new(RefCell::new(String::new()));
+        let sink = captured.clone();
+        shell.capture_output(move |s| sink.borrow_mut().push_str(s));
+        shell.eval_str("(print \"hi\")").unwrap();
+        shell.stop_capturing_output();
+        assert_eq!(*captured.borrow(), "\"hi\"\n");
+    }
+
+    #[test]
+    fn eval_into_converts_the_result_via_serde() {
+        let shell = Shell::new();
+        let doubled: i64 = shell.eval_into("(* 21 2)").unwrap();
+        assert_eq!(doubled, 42);
+    }
+
+    #[test]
+    fn reset_forgets_bindings_but_leaves_core_builtins_in_place() {
+        let mut shell = Shell::new();
+        shell.eval_str("(define x 1)").unwrap();
+        shell.reset();
+        assert!(shell.eval_str("x").is_err());
+        assert!(matches!(shell.eval_str("(+ 1 1)"), Ok(Value::Int(2))));
+    }
+}
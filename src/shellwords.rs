@@ -0,0 +1,94 @@
+use crate::error::{ParseError, ShellError};
+
+/// Splits a line into shell-style words: whitespace-separated, with
+/// single- and double-quoted runs kept as one word and `\` escaping the
+/// next character outside of single quotes.
+///
+/// This is a reading mode distinct from the Lisp lexer in [`crate::lexer`]
+/// — it exists for commands typed as plain words (e.g. `ls -la "my file"`)
+/// rather than s-expressions.
+pub fn split(input: &str) -> Result<Vec<String>, ShellError> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::new("unterminated-quote", "unterminated single quote").into()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => current.push(c),
+                            None => return Err(ParseError::new("unterminated-quote", "unterminated double quote").into()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err(ParseError::new("unterminated-quote", "unterminated double quote").into()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(c) => current.push(c),
+                    None => return Err(ParseError::new("trailing-backslash", "trailing backslash").into()),
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_whitespace() {
+        assert_eq!(split("ls -la foo").unwrap(), vec!["ls", "-la", "foo"]);
+    }
+
+    #[test]
+    fn keeps_quoted_runs_together() {
+        assert_eq!(
+            split(r#"echo "hello world" 'and this'"#).unwrap(),
+            vec!["echo", "hello world", "and this"]
+        );
+    }
+
+    #[test]
+    fn honors_backslash_escapes() {
+        assert_eq!(split(r"a\ b c").unwrap(), vec!["a b", "c"]);
+    }
+
+    #[test]
+    fn reports_unterminated_quotes() {
+        assert!(split(r#"echo "unterminated"#).is_err());
+    }
+}
@@ -0,0 +1,80 @@
+/// The Levenshtein edit distance between `a` and `b`: the minimum number
+/// of single-character insertions, deletions, or substitutions to turn
+/// one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev_diag = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let above = row[j];
+            row[j] = (row[j] + 1).min(row[j - 1] + 1).min(prev_diag + cost);
+            prev_diag = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The closest of `candidates` to `target`, if any is close enough to be
+/// worth suggesting rather than noise -- within a third of `target`'s own
+/// length, at least one and at most three edits away. A target shorter
+/// than six characters still allows one edit, so `x` can suggest another
+/// single-letter name such as `y`. Ties go to
+/// whichever candidate [`IntoIterator`] yields first.
+pub fn suggest<'a>(target: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let threshold = (target.chars().count() / 3).clamp(1, 3);
+    candidates
+        .into_iter()
+        .filter(|c| *c != target)
+        .map(|c| (c, edit_distance(target, c)))
+        .filter(|(_, distance)| *distance <= threshold)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(c, _)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_strings_have_zero_distance() {
+        assert_eq!(edit_distance("length", "length"), 0);
+    }
+
+    #[test]
+    fn a_single_transposition_is_two_edits() {
+        assert_eq!(edit_distance("lenght", "length"), 2);
+    }
+
+    #[test]
+    fn completely_different_strings_cost_their_combined_length() {
+        assert_eq!(edit_distance("abc", "xyz"), 3);
+    }
+
+    #[test]
+    fn suggests_the_closest_candidate() {
+        assert_eq!(
+            suggest("lenght", ["length", "list", "string-length"]),
+            Some("length")
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_something_too_far_off() {
+        assert_eq!(suggest("foo", ["completely-unrelated-name"]), None);
+    }
+
+    #[test]
+    fn does_not_suggest_the_target_itself() {
+        assert_eq!(suggest("length", ["length"]), None);
+    }
+
+    #[test]
+    fn empty_candidates_suggest_nothing() {
+        assert_eq!(suggest("length", []), None);
+    }
+}
@@ -0,0 +1,75 @@
+use std::io::IsTerminal;
+
+/// Returns the terminal's column count, falling back to
+/// [`crate::pretty::DEFAULT_WIDTH`] when it cannot be determined.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn width() -> usize {
+    terminal_size::terminal_size()
+        .map(|(w, _)| w.0 as usize)
+        .unwrap_or(crate::pretty::DEFAULT_WIDTH)
+}
+
+/// Returns the terminal's row count, falling back to 24 when it cannot
+/// be determined.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn height() -> usize {
+    terminal_size::terminal_size()
+        .map(|(_, h)| h.0 as usize)
+        .unwrap_or(24)
+}
+
+/// wasm32 has no terminal to query -- there's no `terminal_size`
+/// dependency for this target at all (see `Cargo.toml`), so these
+/// always report the same fallbacks [`width`]/[`height`] use when a real
+/// terminal can't be found.
+#[cfg(target_arch = "wasm32")]
+pub fn width() -> usize {
+    crate::pretty::DEFAULT_WIDTH
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn height() -> usize {
+    24
+}
+
+pub fn stdout_is_tty() -> bool {
+    std::io::stdout().is_terminal()
+}
+
+pub fn stdin_is_tty() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// Wraps `text` in the ANSI color escape named by `name`, or returns it
+/// unchanged for an unrecognized color name.
+pub fn color(name: &str, text: &str) -> String {
+    let code = match name {
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        _ => return text.to_string(),
+    };
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+pub fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_known_names() {
+        assert_eq!(color("red", "x"), "\x1b[31mx\x1b[0m");
+    }
+
+    #[test]
+    fn unknown_color_is_passthrough() {
+        assert_eq!(color("mauve", "x"), "x");
+    }
+}
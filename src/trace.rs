@@ -0,0 +1,49 @@
+//! `tracing` instrumentation for the evaluator and process spawning, plus
+//! the machinery behind `cracked --trace-json out.json`. Entirely gated
+//! behind the `tracing` feature -- see Cargo.toml.
+//!
+//! There is no pipeline stage to instrument alongside those two: this
+//! interpreter has no pipe/pipeline construct (see how `src/translate.rs`
+//! comments out shell `|` rather than translating it), so a form
+//! evaluating and a process running are the only execution stages there
+//! are to spans over.
+
+use crate::value::Value;
+use tracing_chrome::ChromeLayerBuilder;
+use tracing_subscriber::prelude::*;
+
+/// Holds the [`tracing_chrome::FlushGuard`] [`install`] returns. Dropping
+/// it flushes the Chrome trace file to disk, so whatever calls [`install`]
+/// must keep this alive for as long as it wants events recorded --
+/// typically for the whole process lifetime, by holding it in a `let`
+/// binding in `main` that never goes out of scope until exit.
+pub struct TraceGuard(#[allow(dead_code)] tracing_chrome::FlushGuard);
+
+/// Installs a global [`tracing_subscriber`] that writes every span to
+/// `path` in Chrome's `chrome://tracing`/Perfetto JSON format, as opened
+/// by `--trace-json`. Must be called at most once per process -- a second
+/// call would try to install a second global subscriber, which `tracing`
+/// rejects by panicking.
+pub fn install(path: &std::path::Path) -> TraceGuard {
+    let (layer, guard) = ChromeLayerBuilder::new().file(path).build();
+    tracing_subscriber::registry().with(layer).init();
+    TraceGuard(guard)
+}
+
+/// The span wrapping one [`crate::eval::eval_list`] call, named after the
+/// symbol at the head of the form when there is one (`if`, `+`, a
+/// function name, ...) so a flamegraph reads by form name rather than
+/// showing "eval_list" at every level.
+pub fn eval_span(head: &Value) -> tracing::Span {
+    match head {
+        Value::Symbol(name) => tracing::trace_span!("eval", form = %name),
+        _ => tracing::trace_span!("eval", form = "<expr>"),
+    }
+}
+
+/// The span wrapping one external command's spawn-through-wait lifetime,
+/// named after the program so a flamegraph shows which command the time
+/// went into.
+pub fn process_span(program: &str) -> tracing::Span {
+    tracing::trace_span!("proc/run", program = %program)
+}
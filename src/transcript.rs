@@ -0,0 +1,63 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Records REPL input/output lines to a file when recording is active.
+///
+/// Started and stopped via the `,record FILE` / `,record off` meta
+/// commands; a no-op when no file has been opened.
+#[derive(Default)]
+pub struct Transcript {
+    file: Option<File>,
+}
+
+impl Transcript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn start(&mut self, path: &Path) -> io::Result<()> {
+        self.file = Some(OpenOptions::new().create(true).append(true).open(path)?);
+        Ok(())
+    }
+
+    pub fn stop(&mut self) {
+        self.file = None;
+    }
+
+    pub fn log(&mut self, line: &str) {
+        if let Some(file) = &mut self.file {
+            writeln!(file, "{line}").ok();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logs_nothing_until_started() {
+        let mut transcript = Transcript::new();
+        transcript.log("ignored");
+        assert!(!transcript.is_recording());
+    }
+
+    #[test]
+    fn records_lines_to_file() {
+        let path = std::env::temp_dir().join("cracked_shell_transcript_test.log");
+        std::fs::remove_file(&path).ok();
+        let mut transcript = Transcript::new();
+        transcript.start(&path).unwrap();
+        transcript.log("$> (+ 1 2)");
+        transcript.log("3");
+        transcript.stop();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "$> (+ 1 2)\n3\n");
+        std::fs::remove_file(&path).ok();
+    }
+}
@@ -0,0 +1,406 @@
+//! Best-effort translator from a small, common subset of POSIX shell into
+//! Cracked Shell Lisp, backing `cracked translate`. This is not a POSIX
+//! parser: it recognizes a handful of single-line shapes line by line
+//! (plain commands, `VAR=value` assignments, command substitution, and
+//! one-line `if`/`then`/`else`/`fi`) and leaves everything else as a
+//! `;; TODO` comment followed by the original line, also commented out,
+//! since faithfully emulating pipelines, shell's looping constructs, or
+//! multi-line blocks would require language features (a pipe, a loop)
+//! this interpreter doesn't have yet.
+
+/// Translates `source`, a POSIX shell script, into Cracked Shell Lisp,
+/// one line of output per translated input line. Constructs it can't
+/// translate are kept as a `;; TODO` comment followed by the original
+/// line, also commented out (two output lines), so nothing from the
+/// input is silently dropped.
+///
+/// A block (`if`/`for`/`while`/`until`/`case`) that isn't closed on the
+/// same line it opens on is comment-wrapped in its entirety, rather than
+/// translating its body line by line: without a real parser there's no
+/// way to tell that e.g. an `echo` three lines into an unclosed `if`
+/// still belongs to that `if`, and guessing wrong would silently turn a
+/// conditional command into an unconditional one.
+pub fn translate(source: &str) -> String {
+    let mut out = String::new();
+    let mut open_block: Option<&'static str> = None;
+    for line in source.lines() {
+        let trimmed = line.trim();
+        if let Some(closer) = open_block {
+            out.push_str(";; ");
+            out.push_str(trimmed);
+            out.push('\n');
+            if trimmed == closer {
+                open_block = None;
+            }
+            continue;
+        }
+        if let Some((keyword, closer)) = block_opener(trimmed) {
+            if !ends_with_closer(trimmed, closer) {
+                out.push_str(";; TODO: multi-line ");
+                out.push_str(keyword);
+                out.push_str(" isn't supported by this best-effort translator\n;; ");
+                out.push_str(trimmed);
+                out.push('\n');
+                open_block = Some(closer);
+                continue;
+            }
+        }
+        translate_line(line, &mut out);
+    }
+    out
+}
+
+/// `(message-keyword, closing-keyword)` for `trimmed`, if it opens a
+/// block construct.
+fn block_opener(trimmed: &str) -> Option<(&'static str, &'static str)> {
+    if trimmed.starts_with("if ") {
+        Some(("if", "fi"))
+    } else if trimmed.starts_with("for ") {
+        Some(("for", "done"))
+    } else if trimmed.starts_with("while ") {
+        Some(("while", "done"))
+    } else if trimmed.starts_with("until ") {
+        Some(("until", "done"))
+    } else if trimmed.starts_with("case ") {
+        Some(("case", "esac"))
+    } else {
+        None
+    }
+}
+
+fn ends_with_closer(trimmed: &str, closer: &str) -> bool {
+    trimmed == closer || trimmed.ends_with(&format!("; {closer}"))
+}
+
+fn translate_line(line: &str, out: &mut String) {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        out.push('\n');
+        return;
+    }
+    if let Some(comment) = trimmed.strip_prefix('#') {
+        out.push_str(";;");
+        out.push_str(comment);
+        out.push('\n');
+        return;
+    }
+    if let Some(lisp) = translate_if(trimmed) {
+        out.push_str(&lisp);
+        out.push('\n');
+        return;
+    }
+    if let Some(reason) = unsupported_reason(trimmed) {
+        emit_todo(trimmed, reason, out);
+        return;
+    }
+    if let Some(lisp) = statement_expr(trimmed) {
+        out.push_str(&lisp);
+        out.push('\n');
+        return;
+    }
+    emit_todo(trimmed, "unrecognized construct", out);
+}
+
+/// Why `line` can't be translated at all, if it's a shape this
+/// translator already knows it has no answer for -- as opposed to one it
+/// simply fails to parse, which [`translate_line`] reports more
+/// generically. Only reached for single-line constructs: a block that
+/// spans multiple lines is already intercepted by [`translate`].
+fn unsupported_reason(line: &str) -> Option<&'static str> {
+    if line.starts_with("for ") || line.starts_with("while ") || line.starts_with("until ") {
+        return Some("no loop construct in Cracked Shell yet");
+    }
+    if line.starts_with("case ") {
+        return Some("no case/pattern-match construct in Cracked Shell yet");
+    }
+    if line.starts_with("if ") {
+        return Some("unsupported if shape");
+    }
+    if matches!(line, "fi" | "then" | "else" | "do" | "done" | "esac") {
+        return Some("stray block keyword without a matching opener");
+    }
+    if contains_top_level_pipe(line) {
+        return Some("no pipeline construct in Cracked Shell yet");
+    }
+    None
+}
+
+fn emit_todo(original: &str, reason: &str, out: &mut String) {
+    out.push_str(";; TODO: ");
+    out.push_str(reason);
+    out.push('\n');
+    out.push_str(";; ");
+    out.push_str(original);
+    out.push('\n');
+}
+
+/// True if `line` contains a `|` outside of any quoting -- `||` counts,
+/// since this translator doesn't distinguish "pipe" from "or" either.
+fn contains_top_level_pipe(line: &str) -> bool {
+    let mut in_single = false;
+    let mut in_double = false;
+    for c in line.chars() {
+        match c {
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '|' if !in_single && !in_double => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// One-line `if COND; then STMT; fi` or `if COND; then STMT; else STMT;
+/// fi`. Any other one-line `if` shape falls through to
+/// [`unsupported_reason`]; an `if` spanning more than one line never
+/// reaches here, since [`translate`] comments out the whole block.
+fn translate_if(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("if ")?;
+    let (cond_part, after_then) = rest.split_once("; then ")?;
+    let after_then = after_then.strip_suffix("; fi")?;
+    let (then_part, else_part) = match after_then.split_once("; else ") {
+        Some((t, e)) => (t, Some(e)),
+        None => (after_then, None),
+    };
+
+    let cond = translate_condition(cond_part.trim())?;
+    let then_expr = statement_expr(then_part)?;
+    let else_expr = match else_part {
+        Some(e) => statement_expr(e)?,
+        None => "nil".to_string(),
+    };
+    Some(format!("(if {cond} {then_expr} {else_expr})"))
+}
+
+/// `[ LHS OP RHS ]` or `test LHS OP RHS`, for the handful of operators
+/// this interpreter already has a builtin for. `!=`, `-ne`, `-le`,
+/// `-ge`, and file-test operators (`-f`, `-d`, `-z`, ...) have no
+/// equivalent builtin (there's no `not`, `<=`, or filesystem predicate
+/// yet), so they're left untranslated.
+fn translate_condition(cond: &str) -> Option<String> {
+    let inner = cond
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .map(str::trim)
+        .or_else(|| cond.strip_prefix("test ").map(str::trim))?;
+    let words = crate::shellwords::split(inner).ok()?;
+    let (lhs, op, rhs) = match words.as_slice() {
+        [lhs, op, rhs] => (lhs.as_str(), op.as_str(), rhs.as_str()),
+        _ => return None,
+    };
+    match op {
+        "=" => Some(format!("(equal? {} {})", translate_word(lhs)?, translate_word(rhs)?)),
+        "-eq" => Some(format!("(= {} {})", translate_word(lhs)?, translate_word(rhs)?)),
+        "-lt" => Some(format!("(< {} {})", translate_word(lhs)?, translate_word(rhs)?)),
+        "-gt" => Some(format!("(> {} {})", translate_word(lhs)?, translate_word(rhs)?)),
+        _ => None,
+    }
+}
+
+/// A single statement: `NAME=value` or a plain command. Shared between
+/// top-level lines and the branches of a one-line `if`.
+fn statement_expr(stmt: &str) -> Option<String> {
+    let stmt = stmt.trim();
+    if stmt.is_empty() {
+        return None;
+    }
+    assignment_expr(stmt).or_else(|| command_expr(stmt))
+}
+
+fn assignment_expr(stmt: &str) -> Option<String> {
+    let (name, value) = stmt.split_once('=')?;
+    if !is_ident(name) {
+        return None;
+    }
+    Some(format!("(define {name} {})", translate_value(value.trim())?))
+}
+
+/// The right-hand side of an assignment, or an `if`/`test` operand:
+/// `$(...)` and `` `...` `` subshells contain their own whitespace and
+/// can't be split out by [`crate::shellwords::split`] first, so they're
+/// recognized before falling back to ordinary word-splitting.
+fn translate_value(value: &str) -> Option<String> {
+    if value.is_empty() {
+        return Some("\"\"".to_string());
+    }
+    if let Some(inner) = strip_command_substitution(value) {
+        return translate_command_words(&crate::shellwords::split(inner).ok()?);
+    }
+    match crate::shellwords::split(value).ok()?.as_slice() {
+        [one] => translate_word(one),
+        _ => None,
+    }
+}
+
+/// A plain command invoked for its own effect, e.g. `mkdir "$dir"`.
+fn command_expr(stmt: &str) -> Option<String> {
+    let words = crate::shellwords::split(stmt).ok()?;
+    if words.is_empty() {
+        return None;
+    }
+    translate_command_words(&words)
+}
+
+fn translate_command_words(words: &[String]) -> Option<String> {
+    let (program, args) = words.split_first()?;
+    let mut out = format!("(proc/run {}", lisp_string_literal(program));
+    for arg in args {
+        out.push(' ');
+        out.push_str(&translate_command_arg(arg)?);
+    }
+    out.push(')');
+    Some(out)
+}
+
+fn strip_command_substitution(word: &str) -> Option<&str> {
+    if let Some(inner) = word.strip_prefix("$(").and_then(|s| s.strip_suffix(')')) {
+        return Some(inner);
+    }
+    if word.len() >= 2 {
+        if let Some(inner) = word.strip_prefix('`').and_then(|s| s.strip_suffix('`')) {
+            return Some(inner);
+        }
+    }
+    None
+}
+
+/// A word in assignment/condition position, where a bare integer becomes
+/// a Lisp number rather than a string.
+fn translate_word(word: &str) -> Option<String> {
+    if let Some(inner) = strip_command_substitution(word) {
+        return translate_command_words(&crate::shellwords::split(inner).ok()?);
+    }
+    if let Some(name) = word.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+        return is_ident(name).then(|| name.to_string());
+    }
+    if let Some(name) = word.strip_prefix('$') {
+        return is_ident(name).then(|| name.to_string());
+    }
+    if word.contains('$') {
+        return None;
+    }
+    if is_integer(word) {
+        return Some(word.to_string());
+    }
+    Some(lisp_string_literal(word))
+}
+
+/// A word in `proc/run` argument position, where every argument must be
+/// a string [`crate::builtins::process`] can hand the child process, so
+/// unlike [`translate_word`] an integer-looking word stays a string.
+fn translate_command_arg(word: &str) -> Option<String> {
+    if let Some(inner) = strip_command_substitution(word) {
+        return translate_command_words(&crate::shellwords::split(inner).ok()?);
+    }
+    if let Some(name) = word.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+        return is_ident(name).then(|| name.to_string());
+    }
+    if let Some(name) = word.strip_prefix('$') {
+        return is_ident(name).then(|| name.to_string());
+    }
+    if word.contains('$') {
+        return None;
+    }
+    Some(lisp_string_literal(word))
+}
+
+fn is_ident(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+fn lisp_string_literal(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_a_string_assignment() {
+        assert_eq!(translate("name=\"world\"\n"), "(define name \"world\")\n");
+    }
+
+    #[test]
+    fn translates_an_integer_assignment() {
+        assert_eq!(translate("count=0\n"), "(define count 0)\n");
+    }
+
+    #[test]
+    fn translates_a_variable_reference() {
+        assert_eq!(translate("path=$HOME\n"), "(define path HOME)\n");
+    }
+
+    #[test]
+    fn translates_command_substitution_into_a_nested_proc_run() {
+        assert_eq!(
+            translate("name=$(whoami)\n"),
+            "(define name (proc/run \"whoami\"))\n"
+        );
+    }
+
+    #[test]
+    fn translates_a_plain_command_into_proc_run() {
+        assert_eq!(
+            translate("echo \"hello world\"\n"),
+            "(proc/run \"echo\" \"hello world\")\n"
+        );
+    }
+
+    #[test]
+    fn translates_a_one_line_if_else() {
+        assert_eq!(
+            translate("if [ \"$a\" = \"$b\" ]; then echo yes; else echo no; fi\n"),
+            "(if (equal? a b) (proc/run \"echo\" \"yes\") (proc/run \"echo\" \"no\"))\n"
+        );
+    }
+
+    #[test]
+    fn flags_a_pipeline_as_a_todo() {
+        let out = translate("cat file | grep foo\n");
+        assert!(out.starts_with(";; TODO: no pipeline construct in Cracked Shell yet\n"));
+        assert!(out.contains(";; cat file | grep foo\n"));
+    }
+
+    #[test]
+    fn flags_a_for_loop_as_a_todo() {
+        let out = translate("for f in a b c; do echo $f; done\n");
+        assert!(out.starts_with(";; TODO: no loop construct in Cracked Shell yet\n"));
+    }
+
+    #[test]
+    fn flags_a_multiline_if_as_a_todo_without_mistranslating_its_body() {
+        let out = translate("if [ -f foo ]; then\n  echo yes\nfi\n");
+        assert_eq!(
+            out,
+            ";; TODO: multi-line if isn't supported by this best-effort translator\n\
+             ;; if [ -f foo ]; then\n\
+             ;; echo yes\n\
+             ;; fi\n"
+        );
+    }
+
+    #[test]
+    fn keeps_comments_and_blank_lines() {
+        assert_eq!(translate("# a comment\n\n"), ";; a comment\n\n");
+    }
+}
@@ -0,0 +1,390 @@
+use crate::env::Environment;
+use crate::error::ShellError;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::mpsc::Receiver;
+
+/// Builtins take ownership of their argument vector rather than borrowing
+/// it: `eval_list` builds a fresh `Vec<Value>` for every call and drops it
+/// right after, so there's no other owner to borrow from anyway, and
+/// taking it by value lets builtins like `cons` use [`std::rc::Rc::try_unwrap`]
+/// to mutate a uniquely-referenced list in place instead of cloning it.
+pub type Builtin = fn(Vec<Value>, &Environment) -> Result<Value, ShellError>;
+
+#[derive(Clone)]
+pub struct Lambda {
+    pub params: Vec<String>,
+    /// The name bound to every argument past `params`, collected into a
+    /// list -- set by `(lambda args ...)` (where `params` is empty and
+    /// this is the whole parameter spec) or `(lambda (x . rest) ...)`
+    /// (where `params` holds the fixed leading names). `None` for an
+    /// ordinary fixed-arity parameter list.
+    pub rest: Option<String>,
+    pub body: Vec<Value>,
+    /// The environment the lambda was defined in, captured at creation
+    /// time so it closes over its surrounding bindings -- including ones
+    /// defined after the lambda, since `Environment` shares its frame by
+    /// reference rather than snapshotting it.
+    pub env: Environment,
+}
+
+/// The state of a background evaluation started by `async` or `parallel`.
+///
+/// The worker thread communicates back a rendered string rather than a
+/// `Value`, since `Value` holds `Rc` (as does `Environment`) and so is
+/// not `Send` -- it can never cross a thread boundary directly. See
+/// [`crate::eval::spawn_isolated`] for the crossing mechanism this type
+/// is built around; any code that needs to move work to another thread
+/// should go through it rather than inventing a parallel scheme.
+pub enum FutureState {
+    Pending(Receiver<Result<String, String>>),
+    Done(Result<String, String>),
+}
+
+#[derive(Clone)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Symbol(String),
+    /// A `:foo` keyword: self-evaluating, unlike a `Symbol`.
+    Keyword(String),
+    Char(char),
+    List(Rc<Vec<Value>>),
+    /// An improper list read from `(a b . rest)` syntax: `rest` is not
+    /// itself a list. Used as a lambda parameter spec to bind a variadic
+    /// rest parameter (see [`crate::eval::apply`]); outside of that, just
+    /// parsed and printed, not otherwise evaluated.
+    DottedList(Rc<Vec<Value>>, Rc<Value>),
+    /// A persistent cons-list: unlike `List`, `cons` and `cdr` against this
+    /// variant are O(1) rather than O(n). See [`crate::plist`] and the
+    /// `plist/*` builtins.
+    Plist(crate::plist::Plist),
+    Builtin(&'static str, Builtin),
+    Lambda(Rc<Lambda>),
+    Future(Rc<RefCell<FutureState>>),
+    /// `(memoize f)`'s caching wrapper around `f`. See [`crate::memo`] and
+    /// the `memoize`/`memo-clear!`/`memo-size` builtins.
+    Memo(Rc<RefCell<crate::memo::Memo>>),
+    /// A contiguous buffer of `f64`s, as opposed to the `Rc<Vec<Value>>` of
+    /// boxed, individually-matched elements behind `List`. See
+    /// [`crate::builtins::vector`] and the `vector-from-list`/`vector-map`/
+    /// `vector-sum` builtins -- there's no SIMD here (no nightly
+    /// `portable_simd`, no platform intrinsics in this dependency-light,
+    /// stable-Rust crate), just a flat buffer that numeric bulk operations
+    /// can walk without allocating or pattern-matching a `Value` per
+    /// element.
+    Vector(Rc<Vec<f64>>),
+    /// A first-class error, produced by `catch` around a failing
+    /// expression or directly by `make-error`. See [`ErrorRecord`] and the
+    /// `make-error`/`error-kind`/`error-message`/`error-location`/
+    /// `error-irritants` builtins.
+    Error(Rc<ErrorRecord>),
+    /// A closure-backed builtin registered from outside this crate via
+    /// [`crate::Shell::register_fn`]. Unlike [`Value::Builtin`], which is
+    /// a bare function pointer so cloning or comparing it never has to
+    /// reason about captured state, this can close over whatever an
+    /// embedder captured -- at the cost of the `Rc` indirection core
+    /// builtins don't pay. See [`crate::native`].
+    Native(&'static str, Rc<crate::native::NativeFn>),
+}
+
+/// The data behind a [`Value::Error`]: a [`ShellError`](crate::error::ShellError)
+/// flattened into plain, inspectable fields, plus whatever extra irritants
+/// a `make-error` call was given. `location` is the name of the innermost
+/// function call that was in progress when the error was raised (the
+/// innermost frame [`crate::callstack`] still holds when `catch` sees the
+/// error), or `None` for a `make-error`-built error, or one raised outside
+/// of any function call -- there's no
+/// per-expression source span reaching the evaluator, so this is as
+/// precise a location as is available today.
+#[derive(Debug, Clone)]
+pub struct ErrorRecord {
+    pub kind: String,
+    pub message: String,
+    pub location: Option<String>,
+    pub irritants: Vec<Value>,
+}
+
+impl Value {
+    pub fn list(items: Vec<Value>) -> Value {
+        Value::List(Rc::new(items))
+    }
+
+    pub fn dotted(items: Vec<Value>, tail: Value) -> Value {
+        Value::DottedList(Rc::new(items), Rc::new(tail))
+    }
+
+    pub fn vector(items: Vec<f64>) -> Value {
+        Value::Vector(Rc::new(items))
+    }
+
+    pub fn error(kind: String, message: String, location: Option<String>, irritants: Vec<Value>) -> Value {
+        Value::Error(Rc::new(ErrorRecord { kind, message, location, irritants }))
+    }
+
+    pub fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Bool(false))
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Nil => "nil",
+            Value::Bool(_) => "bool",
+            Value::Int(_) => "int",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::Symbol(_) => "symbol",
+            Value::Keyword(_) => "keyword",
+            Value::Char(_) => "char",
+            Value::List(_) => "list",
+            Value::DottedList(..) => "dotted-list",
+            Value::Plist(_) => "plist",
+            Value::Builtin(..) => "builtin",
+            Value::Lambda(_) => "lambda",
+            Value::Future(_) => "future",
+            Value::Memo(_) => "memo",
+            Value::Vector(_) => "vector",
+            Value::Error(_) => "error",
+            Value::Native(..) => "native-fn",
+        }
+    }
+}
+
+/// Structural equality matching Scheme's `equal?`: same type and same
+/// contents, recursively for lists. Numbers must share the same
+/// exactness -- `(equal? 1 1.0)` is false, since `1` and `1.0` are
+/// different variants ([`Value::Int`] vs [`Value::Float`]), the same way
+/// `eqv?` would treat them. `Builtin`s and `Native`s compare by name alone
+/// -- every registered builtin has a distinct name, so this is equivalent
+/// to identity without relying on comparing function pointers directly
+/// (unreliable across codegen units). `Lambda`, `Future`, and `Memo`
+/// compare by identity (`Rc::ptr_eq`) -- there's no meaningful way to
+/// compare a lambda's captured environment or a future's in-flight state
+/// structurally. `Vector`s compare element-wise like `List`, since they're
+/// just as much plain data. `Error`s compare by their fields, recursing
+/// into `irritants` the same way `List` recurses into its elements.
+pub fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Nil, Value::Nil) => true,
+        (Value::Bool(x), Value::Bool(y)) => x == y,
+        (Value::Int(x), Value::Int(y)) => x == y,
+        (Value::Float(x), Value::Float(y)) => x == y,
+        (Value::Str(x), Value::Str(y)) => x == y,
+        (Value::Symbol(x), Value::Symbol(y)) => x == y,
+        (Value::Keyword(x), Value::Keyword(y)) => x == y,
+        (Value::Char(x), Value::Char(y)) => x == y,
+        (Value::List(x), Value::List(y)) => {
+            x.len() == y.len() && x.iter().zip(y.iter()).all(|(a, b)| values_equal(a, b))
+        }
+        (Value::DottedList(xi, xt), Value::DottedList(yi, yt)) => {
+            xi.len() == yi.len()
+                && xi.iter().zip(yi.iter()).all(|(a, b)| values_equal(a, b))
+                && values_equal(xt, yt)
+        }
+        (Value::Plist(x), Value::Plist(y)) => {
+            x.len() == y.len() && x.iter().zip(y.iter()).all(|(a, b)| values_equal(&a, &b))
+        }
+        (Value::Builtin(xn, _), Value::Builtin(yn, _)) => xn == yn,
+        (Value::Native(xn, _), Value::Native(yn, _)) => xn == yn,
+        (Value::Lambda(x), Value::Lambda(y)) => Rc::ptr_eq(x, y),
+        (Value::Future(x), Value::Future(y)) => Rc::ptr_eq(x, y),
+        (Value::Memo(x), Value::Memo(y)) => Rc::ptr_eq(x, y),
+        (Value::Vector(x), Value::Vector(y)) => x == y,
+        (Value::Error(x), Value::Error(y)) => {
+            x.kind == y.kind
+                && x.message == y.message
+                && x.location == y.location
+                && x.irritants.len() == y.irritants.len()
+                && x.irritants.iter().zip(y.irritants.iter()).all(|(a, b)| values_equal(a, b))
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Nil => write!(f, "nil"),
+            Value::Bool(b) => write!(f, "{}", if *b { "#t" } else { "#f" }),
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Float(n) => write!(f, "{n}"),
+            Value::Str(s) => write!(f, "\"{}\"", escape_string(s)),
+            Value::Symbol(s) => write!(f, "{}", render_symbol(s)),
+            Value::Keyword(s) => write!(f, ":{s}"),
+            Value::Char(' ') => write!(f, "#\\space"),
+            Value::Char('\n') => write!(f, "#\\newline"),
+            Value::Char('\t') => write!(f, "#\\tab"),
+            Value::Char(c) => write!(f, "#\\{c}"),
+            Value::List(items) => {
+                if let [Value::Symbol(head), arg] = items.as_slice() {
+                    let shorthand = match head.as_str() {
+                        "quote" => Some("'"),
+                        "quasiquote" => Some("`"),
+                        "unquote" => Some(","),
+                        "unquote-splicing" => Some(",@"),
+                        _ => None,
+                    };
+                    if let Some(prefix) = shorthand {
+                        return write!(f, "{prefix}{arg}");
+                    }
+                }
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Value::DottedList(items, tail) => {
+                write!(f, "(")?;
+                for item in items.iter() {
+                    write!(f, "{item} ")?;
+                }
+                write!(f, ". {tail})")
+            }
+            Value::Plist(plst) => {
+                write!(f, "(")?;
+                for (i, item) in plst.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Value::Builtin(name, _) => write!(f, "#<builtin:{name}>"),
+            Value::Lambda(lambda) => {
+                write!(f, "(lambda (")?;
+                for (i, param) in lambda.params.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{param}")?;
+                }
+                write!(f, ")")?;
+                for expr in &lambda.body {
+                    write!(f, " {expr}")?;
+                }
+                write!(f, ")")
+            }
+            Value::Future(state) => match &*state.borrow() {
+                FutureState::Pending(_) => write!(f, "#<future:pending>"),
+                FutureState::Done(Ok(v)) => write!(f, "#<future:done {v}>"),
+                FutureState::Done(Err(e)) => write!(f, "#<future:error {e}>"),
+            },
+            Value::Memo(memo) => write!(f, "#<memo:{} cached>", memo.borrow().len()),
+            Value::Vector(items) => {
+                write!(f, "#(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Value::Error(e) => {
+                write!(f, "#<error :{} \"{}\"", e.kind, e.message)?;
+                for irritant in &e.irritants {
+                    write!(f, " {irritant}")?;
+                }
+                write!(f, ">")
+            }
+            Value::Native(name, _) => write!(f, "#<native:{name}>"),
+        }
+    }
+}
+
+/// Escapes a string's contents for round-tripping through [`Value::Str`]'s
+/// `Display` -- the inverse of the lexer's `\"`/`\\`/`\n`/`\t` string
+/// escapes (`src/lexer.rs`'s `read_string`), so anything this prints back
+/// out (`save-session`, `persist-define`, plain printing) reads back as
+/// the same string instead of silently truncating at an embedded quote.
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Renders a symbol name as the lexer would need it written back, wrapping
+/// it in `|...|` (with `|` and `\` escaped) when it contains characters
+/// that would otherwise change its meaning or split it into multiple
+/// tokens -- whitespace, parens, or a leading quote/comment character.
+fn render_symbol(s: &str) -> String {
+    let needs_escaping = s.is_empty()
+        || s.chars()
+            .any(|c| c.is_whitespace() || matches!(c, '(' | ')' | '[' | ']' | '|' | '\\'))
+        || matches!(s.chars().next(), Some('\'' | '`' | ',' | ':' | ';' | '"' | '#'));
+    if !needs_escaping {
+        return s.to_string();
+    }
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('|');
+    for c in s.chars() {
+        if c == '|' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('|');
+    out
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_symbols_print_unescaped() {
+        assert_eq!(Value::Symbol("foo-bar?".into()).to_string(), "foo-bar?");
+    }
+
+    #[test]
+    fn symbols_needing_escaping_print_piped_with_backslashes() {
+        assert_eq!(
+            Value::Symbol("weird symbol".into()).to_string(),
+            "|weird symbol|"
+        );
+        assert_eq!(
+            Value::Symbol("has|pipe".into()).to_string(),
+            "|has\\|pipe|"
+        );
+    }
+
+    #[test]
+    fn strings_escape_quotes_and_backslashes_when_printed() {
+        assert_eq!(
+            Value::Str("say \"hi\"".into()).to_string(),
+            "\"say \\\"hi\\\"\""
+        );
+        assert_eq!(Value::Str("a\\b".into()).to_string(), "\"a\\\\b\"");
+    }
+
+    #[test]
+    fn a_printed_string_containing_a_quote_round_trips_through_the_parser() {
+        let original = Value::Str("say \"hi\"\nwith a\ttab".into());
+        let printed = original.to_string();
+        let reparsed = crate::parser::Parser::parse(&printed).unwrap();
+        assert!(values_equal(&reparsed, &original));
+    }
+}
@@ -0,0 +1,27 @@
+//! A `wasm-bindgen` entry point for running Cracked Shell source from
+//! JavaScript, e.g. a browser playground or a docs page -- gated behind
+//! the `wasm` Cargo feature so the dependency stays out of ordinary
+//! native builds.
+//!
+//! "The core" this targets is the interpreter itself: [`crate::lexer`],
+//! [`crate::parser`], [`crate::eval`], [`crate::value`], [`crate::env`],
+//! and the [`crate::Shell`] facade built on them, all of which are plain
+//! Rust with no OS dependency once [`crate::builtins::process`]'s actual
+//! process spawning is left out (see its `#[cfg(not(target_arch =
+//! "wasm32"))]` gate) and [`crate::eval::spawn_isolated`]'s `async`/
+//! `parallel` fall back to running inline instead of spawning a thread.
+//! [`crate::repl`], [`crate::plugin`], and the other CLI-only modules
+//! that read `$HOME` or the filesystem are untouched and simply aren't
+//! part of what a browser playground needs.
+use wasm_bindgen::prelude::*;
+
+/// Evaluates every top-level form in `source` against a fresh
+/// [`crate::Shell`] and returns the last form's value rendered as a
+/// string, or throws with the error's message on failure.
+#[wasm_bindgen]
+pub fn eval(source: &str) -> Result<String, JsValue> {
+    crate::Shell::new()
+        .eval_str(source)
+        .map(|value| value.to_string())
+        .map_err(|e| JsValue::from_str(&e.to_string()))
+}